@@ -979,3 +979,8 @@ class cached_property:
         return val
 
     __class_getitem__ = classmethod(GenericAlias)
+
+try:
+    from _functools import cached_property
+except ImportError:
+    pass
@@ -673,3 +673,8 @@ class finalize:
             finalize._shutdown = True
             if reenable_gc:
                 gc.enable()
+
+try:
+    from _weakref import finalize
+except ImportError:
+    pass
@@ -0,0 +1,28 @@
+import threading
+
+
+class Widget:
+    def __init__(self):
+        self.width = 10
+        self.height = 20
+        self.depth = 30
+
+
+widget = Widget()
+
+
+def reader():
+    w = widget
+    total = 0
+    for _ in range(1_000):
+        total += w.width + w.height + w.depth
+    return total
+
+
+# ---
+
+threads = [threading.Thread(target=reader) for _ in range(4)]
+for thread in threads:
+    thread.start()
+for thread in threads:
+    thread.join()
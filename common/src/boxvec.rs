@@ -61,6 +61,15 @@ impl<T> BoxVec<T> {
         }
     }
 
+    /// An empty vector with no backing allocation; handy as a placeholder
+    /// when moving a `BoxVec` out of a struct.
+    pub fn empty() -> BoxVec<T> {
+        BoxVec {
+            xs: Box::new([]),
+            len: 0,
+        }
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.len
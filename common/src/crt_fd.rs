@@ -60,6 +60,10 @@ impl Fd {
         )
     }
 
+    pub fn dup(&self) -> io::Result<Self> {
+        cvt(unsafe { suppress_iph!(libc::dup(self.0)) }, Fd)
+    }
+
     pub fn fsync(&self) -> io::Result<()> {
         cvt(unsafe { suppress_iph!(fsync(self.0)) }, drop)
     }
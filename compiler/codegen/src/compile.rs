@@ -554,7 +554,7 @@ impl Compiler {
 
     fn compile_statement(&mut self, statement: &ast::Stmt) -> CompileResult<()> {
         trace!("Compiling {:?}", statement);
-        self.set_source_location(statement.location);
+        self.set_source_range(statement.location, statement.end_location);
         use ast::StmtKind::*;
 
         match &statement.node {
@@ -1109,12 +1109,162 @@ impl Compiler {
 
     fn compile_try_star_statement(
         &mut self,
-        _body: &[ast::Stmt],
-        _handlers: &[ast::Excepthandler],
-        _orelse: &[ast::Stmt],
-        _finalbody: &[ast::Stmt],
+        body: &[ast::Stmt],
+        handlers: &[ast::Excepthandler],
+        orelse: &[ast::Stmt],
+        finalbody: &[ast::Stmt],
     ) -> CompileResult<()> {
-        Err(self.error(CodegenErrorType::NotImplementedYet))
+        let handler_block = self.new_block();
+        let finally_block = self.new_block();
+
+        // Setup a finally block if we have a finally statement.
+        if !finalbody.is_empty() {
+            emit!(
+                self,
+                Instruction::SetupFinally {
+                    handler: finally_block,
+                }
+            );
+        }
+
+        let else_block = self.new_block();
+
+        // try:
+        emit!(
+            self,
+            Instruction::SetupExcept {
+                handler: handler_block,
+            }
+        );
+        self.compile_statements(body)?;
+        emit!(self, Instruction::PopBlock);
+        emit!(self, Instruction::Jump { target: else_block });
+
+        // except* handlers:
+        self.switch_to_block(handler_block);
+        // The exception is on top of the stack; every clause splits off the
+        // part it matches and leaves the rest (or None) for the next clause.
+        for handler in handlers {
+            let ast::ExcepthandlerKind::ExceptHandler { type_, name, body } = &handler.node;
+            let exc_type = type_.as_ref().expect("except* requires an exception type");
+            let next_handler = self.new_block();
+            let no_match = self.new_block();
+
+            // Nothing left to match against:
+            emit!(self, Instruction::Duplicate);
+            self.emit_constant(ConstantData::None);
+            emit!(
+                self,
+                Instruction::TestOperation {
+                    op: bytecode::TestOperator::Is,
+                }
+            );
+            emit!(
+                self,
+                Instruction::JumpIfTrue {
+                    target: next_handler,
+                }
+            );
+
+            // Split the group; leaves the non-matching rest below the
+            // matching subgroup, either of which may be None:
+            self.compile_expression(exc_type)?;
+            emit!(self, Instruction::ExceptionGroupMatch);
+            emit!(self, Instruction::Duplicate);
+            self.emit_constant(ConstantData::None);
+            emit!(
+                self,
+                Instruction::TestOperation {
+                    op: bytecode::TestOperator::Is,
+                }
+            );
+            emit!(self, Instruction::JumpIfTrue { target: no_match });
+
+            // We have a match, store in name (except* x as y)
+            if let Some(alias) = name {
+                self.store_name(alias)?
+            } else {
+                // Drop the matched subgroup from top of stack:
+                emit!(self, Instruction::Pop);
+            }
+
+            // Handler code:
+            self.compile_statements(body)?;
+            emit!(
+                self,
+                Instruction::Jump {
+                    target: next_handler,
+                }
+            );
+
+            // Drop the None left by an unsuccessful split:
+            self.switch_to_block(no_match);
+            emit!(self, Instruction::Pop);
+
+            self.switch_to_block(next_handler);
+        }
+
+        // If anything is left unmatched, raise it again; otherwise drop the
+        // None and leave the handler normally.
+        let reraise_block = self.new_block();
+        emit!(self, Instruction::Duplicate);
+        self.emit_constant(ConstantData::None);
+        emit!(
+            self,
+            Instruction::TestOperation {
+                op: bytecode::TestOperator::Is,
+            }
+        );
+        emit!(
+            self,
+            Instruction::JumpIfFalse {
+                target: reraise_block,
+            }
+        );
+        emit!(self, Instruction::Pop);
+        emit!(self, Instruction::PopException);
+
+        if !finalbody.is_empty() {
+            emit!(self, Instruction::PopBlock); // pop excepthandler block
+                                                // We enter the finally block, without exception.
+            emit!(self, Instruction::EnterFinally);
+        }
+
+        emit!(
+            self,
+            Instruction::Jump {
+                target: finally_block,
+            }
+        );
+
+        self.switch_to_block(reraise_block);
+        emit!(
+            self,
+            Instruction::Raise {
+                kind: bytecode::RaiseKind::Raise,
+            }
+        );
+
+        // We successfully ran the try block:
+        // else:
+        self.switch_to_block(else_block);
+        self.compile_statements(orelse)?;
+
+        if !finalbody.is_empty() {
+            emit!(self, Instruction::PopBlock); // pop finally block
+
+            // We enter the finallyhandler block, without return / exception.
+            emit!(self, Instruction::EnterFinally);
+        }
+
+        // finally:
+        self.switch_to_block(finally_block);
+        if !finalbody.is_empty() {
+            self.compile_statements(finalbody)?;
+            emit!(self, Instruction::EndFinally);
+        }
+
+        Ok(())
     }
 
     fn is_forbidden_arg_name(name: &str) -> bool {
@@ -1594,9 +1744,387 @@ impl Compiler {
         subject: &ast::Expr,
         cases: &[ast::MatchCase],
     ) -> CompileResult<()> {
-        eprintln!("match subject: {subject:?}");
-        eprintln!("match cases: {cases:?}");
-        Err(self.error(CodegenErrorType::NotImplementedYet))
+        let end_block = self.new_block();
+        self.compile_expression(subject)?;
+        for case in cases {
+            let next_block = self.new_block();
+            emit!(self, Instruction::Duplicate);
+            self.compile_pattern(&case.pattern, next_block)?;
+            if let Some(guard) = &case.guard {
+                self.compile_jump_if(guard, false, next_block)?;
+            }
+            // the pattern consumed the copy; drop the subject itself before
+            // running the body
+            emit!(self, Instruction::Pop);
+            self.compile_statements(&case.body)?;
+            emit!(self, Instruction::Jump { target: end_block });
+            self.switch_to_block(next_block);
+        }
+        // no case matched, so only the subject is left to clean up
+        emit!(self, Instruction::Pop);
+        self.switch_to_block(end_block);
+        Ok(())
+    }
+
+    /// Compile a single pattern of a match statement. The value being
+    /// matched is on top of the stack when the emitted code starts, and is
+    /// consumed by it; a failed match jumps to `fail_block` with the value
+    /// already popped. Capture names are bound as soon as their sub-pattern
+    /// matches, which PEP 634 allows even when the whole pattern later fails.
+    fn compile_pattern(
+        &mut self,
+        pattern: &ast::Pattern,
+        fail_block: ir::BlockIdx,
+    ) -> CompileResult<()> {
+        self.set_source_range(pattern.location, pattern.end_location);
+        match &pattern.node {
+            ast::PatternKind::MatchValue { value } => {
+                self.compile_expression(value)?;
+                emit!(
+                    self,
+                    Instruction::CompareOperation {
+                        op: bytecode::ComparisonOperator::Equal
+                    }
+                );
+                emit!(self, Instruction::JumpIfFalse { target: fail_block });
+            }
+            ast::PatternKind::MatchSingleton { value } => {
+                self.emit_constant(compile_constant(value));
+                emit!(
+                    self,
+                    Instruction::TestOperation {
+                        op: bytecode::TestOperator::Is
+                    }
+                );
+                emit!(self, Instruction::JumpIfFalse { target: fail_block });
+            }
+            ast::PatternKind::MatchSequence { patterns } => {
+                self.compile_pattern_sequence(patterns, fail_block)?;
+            }
+            ast::PatternKind::MatchMapping {
+                keys,
+                patterns,
+                rest,
+            } => {
+                self.compile_pattern_mapping(keys, patterns, rest.as_deref(), fail_block)?;
+            }
+            ast::PatternKind::MatchClass {
+                cls,
+                patterns,
+                kwd_attrs,
+                kwd_patterns,
+            } => {
+                self.compile_pattern_class(cls, patterns, kwd_attrs, kwd_patterns, fail_block)?;
+            }
+            ast::PatternKind::MatchStar { .. } => {
+                // only valid directly inside a sequence pattern, where
+                // compile_pattern_sequence handles it
+                return Err(self.error_loc(CodegenErrorType::InvalidStarExpr, pattern.location));
+            }
+            ast::PatternKind::MatchAs {
+                pattern: sub_pattern,
+                name,
+            } => match (sub_pattern, name) {
+                // the wildcard pattern `_`
+                (None, None) => emit!(self, Instruction::Pop),
+                (None, Some(name)) => self.store_name(name)?,
+                (Some(sub_pattern), name) => {
+                    let cleanup_block = self.new_block();
+                    let matched_block = self.new_block();
+                    emit!(self, Instruction::Duplicate);
+                    self.compile_pattern(sub_pattern, cleanup_block)?;
+                    match name {
+                        Some(name) => self.store_name(name)?,
+                        None => emit!(self, Instruction::Pop),
+                    }
+                    emit!(
+                        self,
+                        Instruction::Jump {
+                            target: matched_block
+                        }
+                    );
+                    self.switch_to_block(cleanup_block);
+                    emit!(self, Instruction::Pop);
+                    emit!(self, Instruction::Jump { target: fail_block });
+                    self.switch_to_block(matched_block);
+                }
+            },
+            ast::PatternKind::MatchOr { patterns } => {
+                let matched_block = self.new_block();
+                let (last, rest) = patterns.split_last().expect("or-pattern can't be empty");
+                for alternative in rest {
+                    let next_block = self.new_block();
+                    emit!(self, Instruction::Duplicate);
+                    self.compile_pattern(alternative, next_block)?;
+                    emit!(self, Instruction::Pop);
+                    emit!(
+                        self,
+                        Instruction::Jump {
+                            target: matched_block
+                        }
+                    );
+                    self.switch_to_block(next_block);
+                }
+                self.compile_pattern(last, fail_block)?;
+                self.switch_to_block(matched_block);
+            }
+        }
+        Ok(())
+    }
+
+    /// Start a cleanup block popping `pops` values a partially matched
+    /// pattern left behind before giving up; `emit_pattern_cleanups` lays
+    /// the recorded blocks out once the success path is complete.
+    fn pattern_cleanup_block(
+        &mut self,
+        cleanups: &mut Vec<(ir::BlockIdx, usize)>,
+        pops: usize,
+    ) -> ir::BlockIdx {
+        let block = self.new_block();
+        cleanups.push((block, pops));
+        block
+    }
+
+    fn emit_pattern_cleanups(
+        &mut self,
+        cleanups: Vec<(ir::BlockIdx, usize)>,
+        fail_block: ir::BlockIdx,
+        matched_block: ir::BlockIdx,
+    ) {
+        emit!(
+            self,
+            Instruction::Jump {
+                target: matched_block
+            }
+        );
+        for (block, pops) in cleanups {
+            self.switch_to_block(block);
+            for _ in 0..pops {
+                emit!(self, Instruction::Pop);
+            }
+            emit!(self, Instruction::Jump { target: fail_block });
+        }
+        self.switch_to_block(matched_block);
+    }
+
+    fn compile_pattern_sequence(
+        &mut self,
+        patterns: &[ast::Pattern],
+        fail_block: ir::BlockIdx,
+    ) -> CompileResult<()> {
+        let star = patterns
+            .iter()
+            .position(|pattern| matches!(pattern.node, ast::PatternKind::MatchStar { .. }));
+        let matched_block = self.new_block();
+        let mut cleanups = Vec::new();
+
+        emit!(self, Instruction::MatchSequence);
+        let subject_cleanup = self.pattern_cleanup_block(&mut cleanups, 1);
+        emit!(
+            self,
+            Instruction::JumpIfFalse {
+                target: subject_cleanup
+            }
+        );
+
+        let required = patterns.len() - star.is_some() as usize;
+        emit!(self, Instruction::GetLen);
+        self.emit_constant(ConstantData::Integer {
+            value: required.into(),
+        });
+        let op = if star.is_some() {
+            bytecode::ComparisonOperator::GreaterOrEqual
+        } else {
+            bytecode::ComparisonOperator::Equal
+        };
+        emit!(self, Instruction::CompareOperation { op });
+        emit!(
+            self,
+            Instruction::JumpIfFalse {
+                target: subject_cleanup
+            }
+        );
+
+        match star {
+            None => emit!(
+                self,
+                Instruction::UnpackSequence {
+                    size: patterns.len().to_u32()
+                }
+            ),
+            Some(index) => {
+                let after = patterns.len() - index - 1;
+                let (before, after) = (|| Some((index.to_u8()?, after.to_u8()?)))()
+                    .ok_or_else(|| self.error(CodegenErrorType::TooManyStarUnpack))?;
+                let args = bytecode::UnpackExArgs { before, after };
+                emit!(self, Instruction::UnpackEx { args });
+            }
+        }
+
+        // the elements now sit on the stack with the first one on top
+        for (i, pattern) in patterns.iter().enumerate() {
+            let remaining = patterns.len() - i - 1;
+            if let ast::PatternKind::MatchStar { name } = &pattern.node {
+                self.set_source_range(pattern.location, pattern.end_location);
+                match name {
+                    Some(name) => self.store_name(name)?,
+                    None => emit!(self, Instruction::Pop),
+                }
+            } else {
+                let element_fail = if remaining == 0 {
+                    fail_block
+                } else {
+                    self.pattern_cleanup_block(&mut cleanups, remaining)
+                };
+                self.compile_pattern(pattern, element_fail)?;
+            }
+        }
+
+        self.emit_pattern_cleanups(cleanups, fail_block, matched_block);
+        Ok(())
+    }
+
+    fn compile_pattern_mapping(
+        &mut self,
+        keys: &[ast::Expr],
+        patterns: &[ast::Pattern],
+        rest: Option<&str>,
+        fail_block: ir::BlockIdx,
+    ) -> CompileResult<()> {
+        let matched_block = self.new_block();
+        let mut cleanups = Vec::new();
+
+        emit!(self, Instruction::MatchMapping);
+        let subject_cleanup = self.pattern_cleanup_block(&mut cleanups, 1);
+        emit!(
+            self,
+            Instruction::JumpIfFalse {
+                target: subject_cleanup
+            }
+        );
+
+        if !keys.is_empty() {
+            for key in keys {
+                self.compile_expression(key)?;
+            }
+            emit!(
+                self,
+                Instruction::BuildTuple {
+                    size: keys.len().to_u32()
+                }
+            );
+            emit!(self, Instruction::MatchKeys);
+            // stack: subject, keys tuple, value tuple or None
+            emit!(self, Instruction::Duplicate);
+            self.emit_constant(ConstantData::None);
+            emit!(
+                self,
+                Instruction::TestOperation {
+                    op: bytecode::TestOperator::Is
+                }
+            );
+            let missing_cleanup = self.pattern_cleanup_block(&mut cleanups, 3);
+            emit!(
+                self,
+                Instruction::JumpIfTrue {
+                    target: missing_cleanup
+                }
+            );
+            emit!(
+                self,
+                Instruction::UnpackSequence {
+                    size: patterns.len().to_u32()
+                }
+            );
+            for (i, pattern) in patterns.iter().enumerate() {
+                // the values still to match, plus the keys tuple and subject
+                let pops = patterns.len() - i - 1 + 2;
+                let value_fail = self.pattern_cleanup_block(&mut cleanups, pops);
+                self.compile_pattern(pattern, value_fail)?;
+            }
+        } else if rest.is_some() {
+            // CopyDictWithoutKeys still expects a tuple of keys
+            emit!(self, Instruction::BuildTuple { size: 0 });
+        }
+
+        match rest {
+            Some(rest) => {
+                emit!(self, Instruction::CopyDictWithoutKeys);
+                self.store_name(rest)?;
+            }
+            // drop the keys tuple
+            None if !keys.is_empty() => emit!(self, Instruction::Pop),
+            None => {}
+        }
+        // drop the subject
+        emit!(self, Instruction::Pop);
+
+        self.emit_pattern_cleanups(cleanups, fail_block, matched_block);
+        Ok(())
+    }
+
+    fn compile_pattern_class(
+        &mut self,
+        cls: &ast::Expr,
+        patterns: &[ast::Pattern],
+        kwd_attrs: &[String],
+        kwd_patterns: &[ast::Pattern],
+        fail_block: ir::BlockIdx,
+    ) -> CompileResult<()> {
+        let matched_block = self.new_block();
+        let mut cleanups = Vec::new();
+
+        self.compile_expression(cls)?;
+        self.emit_constant(ConstantData::Tuple {
+            elements: kwd_attrs
+                .iter()
+                .map(|attr| ConstantData::Str {
+                    value: attr.clone(),
+                })
+                .collect(),
+        });
+        emit!(
+            self,
+            Instruction::MatchClass {
+                nargs: patterns.len().to_u32()
+            }
+        );
+        // stack: extracted attribute tuple or None
+        emit!(self, Instruction::Duplicate);
+        self.emit_constant(ConstantData::None);
+        emit!(
+            self,
+            Instruction::TestOperation {
+                op: bytecode::TestOperator::Is
+            }
+        );
+        let unmatched_cleanup = self.pattern_cleanup_block(&mut cleanups, 1);
+        emit!(
+            self,
+            Instruction::JumpIfTrue {
+                target: unmatched_cleanup
+            }
+        );
+
+        let total = patterns.len() + kwd_patterns.len();
+        emit!(
+            self,
+            Instruction::UnpackSequence {
+                size: total.to_u32()
+            }
+        );
+        for (i, pattern) in patterns.iter().chain(kwd_patterns).enumerate() {
+            let remaining = total - i - 1;
+            let attr_fail = if remaining == 0 {
+                fail_block
+            } else {
+                self.pattern_cleanup_block(&mut cleanups, remaining)
+            };
+            self.compile_pattern(pattern, attr_fail)?;
+        }
+
+        self.emit_pattern_cleanups(cleanups, fail_block, matched_block);
+        Ok(())
     }
 
     fn compile_chained_comparison(
@@ -2038,7 +2566,7 @@ impl Compiler {
 
     fn compile_expression(&mut self, expression: &ast::Expr) -> CompileResult<()> {
         trace!("Compiling {:?}", expression);
-        self.set_source_location(expression.location);
+        self.set_source_range(expression.location, expression.end_location);
 
         use ast::ExprKind::*;
         match &expression.node {
@@ -2049,11 +2577,15 @@ impl Compiler {
             } => self.compile_call(func, args, keywords)?,
             BoolOp { op, values } => self.compile_bool_op(op, values)?,
             BinOp { left, op, right } => {
-                self.compile_expression(left)?;
-                self.compile_expression(right)?;
+                if let Some(constant) = try_fold_constant(expression) {
+                    self.emit_constant(constant);
+                } else {
+                    self.compile_expression(left)?;
+                    self.compile_expression(right)?;
 
-                // Perform operation:
-                self.compile_op(op, false);
+                    // Perform operation:
+                    self.compile_op(op, false);
+                }
             }
             Subscript { value, slice, .. } => {
                 self.compile_expression(value)?;
@@ -2061,16 +2593,20 @@ impl Compiler {
                 emit!(self, Instruction::Subscript);
             }
             UnaryOp { op, operand } => {
-                self.compile_expression(operand)?;
-
-                // Perform operation:
-                let op = match op {
-                    ast::Unaryop::UAdd => bytecode::UnaryOperator::Plus,
-                    ast::Unaryop::USub => bytecode::UnaryOperator::Minus,
-                    ast::Unaryop::Not => bytecode::UnaryOperator::Not,
-                    ast::Unaryop::Invert => bytecode::UnaryOperator::Invert,
-                };
-                emit!(self, Instruction::UnaryOperation { op });
+                if let Some(constant) = try_fold_constant(expression) {
+                    self.emit_constant(constant);
+                } else {
+                    self.compile_expression(operand)?;
+
+                    // Perform operation:
+                    let op = match op {
+                        ast::Unaryop::UAdd => bytecode::UnaryOperator::Plus,
+                        ast::Unaryop::USub => bytecode::UnaryOperator::Minus,
+                        ast::Unaryop::Not => bytecode::UnaryOperator::Not,
+                        ast::Unaryop::Invert => bytecode::UnaryOperator::Invert,
+                    };
+                    emit!(self, Instruction::UnaryOperation { op });
+                }
             }
             Attribute { value, attr, .. } => {
                 self.compile_expression(value)?;
@@ -2096,11 +2632,15 @@ impl Compiler {
                 }
             }
             Tuple { elts, .. } => {
-                let (size, unpack) = self.gather_elements(0, elts)?;
-                if unpack {
-                    emit!(self, Instruction::BuildTupleUnpack { size });
+                if let Some(constant) = try_fold_constant(expression) {
+                    self.emit_constant(constant);
                 } else {
-                    emit!(self, Instruction::BuildTuple { size });
+                    let (size, unpack) = self.gather_elements(0, elts)?;
+                    if unpack {
+                        emit!(self, Instruction::BuildTupleUnpack { size });
+                    } else {
+                        emit!(self, Instruction::BuildTuple { size });
+                    }
                 }
             }
             Set { elts, .. } => {
@@ -2753,6 +3293,16 @@ impl Compiler {
         self.current_source_location = location;
     }
 
+    /// Like [`Self::set_source_location`], but also recording how far the
+    /// producing node extends, so the emitted instructions carry the node's
+    /// whole source span (PEP 657).
+    fn set_source_range(&mut self, location: Location, end_location: Option<Location>) {
+        self.current_source_location = match end_location {
+            Some(end) => location.span_to(end),
+            None => location,
+        };
+    }
+
     fn get_source_line_number(&self) -> u32 {
         self.current_source_location.row().to_u32()
     }
@@ -2828,6 +3378,61 @@ fn try_get_constant_string(values: &[ast::Expr]) -> Option<String> {
     }
 }
 
+/// Fold an expression built entirely out of literals into the single constant
+/// it always evaluates to, so it loads in one `LoadConst`. Anything whose
+/// value or failure mode has to be decided at runtime (division by zero, true
+/// division of integers, ...) is left alone.
+fn try_fold_constant(expression: &ast::Expr) -> Option<ConstantData> {
+    use ast::ExprKind::*;
+    match &expression.node {
+        Constant { value, .. } => Some(compile_constant(value)),
+        Tuple { elts, .. } => {
+            let elements = elts.iter().map(try_fold_constant).collect::<Option<_>>()?;
+            Some(ConstantData::Tuple { elements })
+        }
+        UnaryOp {
+            op: ast::Unaryop::USub,
+            operand,
+        } => match try_fold_constant(operand)? {
+            ConstantData::Integer { value } => Some(ConstantData::Integer { value: -value }),
+            ConstantData::Float { value } => Some(ConstantData::Float { value: -value }),
+            _ => None,
+        },
+        BinOp { left, op, right } => {
+            let (left, right) = (try_fold_constant(left)?, try_fold_constant(right)?);
+            match (left, right) {
+                (ConstantData::Integer { value: a }, ConstantData::Integer { value: b }) => {
+                    let value = match op {
+                        ast::Operator::Add => a + b,
+                        ast::Operator::Sub => a - b,
+                        ast::Operator::Mult => a * b,
+                        _ => return None,
+                    };
+                    Some(ConstantData::Integer { value })
+                }
+                (ConstantData::Float { value: a }, ConstantData::Float { value: b }) => {
+                    let value = match op {
+                        ast::Operator::Add => a + b,
+                        ast::Operator::Sub => a - b,
+                        ast::Operator::Mult => a * b,
+                        ast::Operator::Div if b != 0.0 => a / b,
+                        _ => return None,
+                    };
+                    Some(ConstantData::Float { value })
+                }
+                (ConstantData::Str { value: mut a }, ConstantData::Str { value: b })
+                    if matches!(op, ast::Operator::Add) =>
+                {
+                    a.push_str(&b);
+                    Some(ConstantData::Str { value: a })
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 fn compile_location(location: &Location) -> bytecode::Location {
     bytecode::Location::new(location.row(), location.column())
 }
@@ -2867,8 +3472,12 @@ mod tests {
     use rustpython_parser as parser;
 
     fn compile_exec(source: &str) -> CodeObject {
+        compile_exec_optimized(source, 0)
+    }
+
+    fn compile_exec_optimized(source: &str, optimize: u8) -> CodeObject {
         let mut compiler: Compiler = Compiler::new(
-            CompileOpts::default(),
+            CompileOpts { optimize },
             "source_path".to_owned(),
             "<module>".to_owned(),
         );
@@ -2878,6 +3487,28 @@ mod tests {
         compiler.pop_code_object()
     }
 
+    /// Number of instructions in `code`, including its nested code objects
+    fn instruction_count(code: &CodeObject) -> usize {
+        code.instructions.len()
+            + code
+                .constants
+                .iter()
+                .map(|constant| match constant {
+                    ConstantData::Code { code } => instruction_count(code),
+                    _ => 0,
+                })
+                .sum::<usize>()
+    }
+
+    fn assert_peephole_shrinks(source: &str) {
+        let unoptimized = instruction_count(&compile_exec_optimized(source, 0));
+        let optimized = instruction_count(&compile_exec_optimized(source, 1));
+        assert!(
+            optimized < unoptimized,
+            "expected the optimizer to drop instructions: {optimized} >= {unoptimized}"
+        );
+    }
+
     macro_rules! assert_dis_snapshot {
         ($value:expr) => {
             insta::assert_snapshot!(
@@ -2918,6 +3549,93 @@ if (True and False) or (False and True):
         ));
     }
 
+    #[test]
+    fn test_fold_constant_expressions() {
+        let code = compile_exec("x = 2 * 3 + 1\ny = (1, 2, 3)\nz = 'a' + 'b'\n");
+        assert!(!code.instructions.iter().any(|unit| matches!(
+            unit.op,
+            Instruction::BinaryOperation { .. } | Instruction::BuildTuple { .. }
+        )));
+        assert!(code.constants.iter().any(
+            |constant| matches!(constant, ConstantData::Tuple { elements } if elements.len() == 3)
+        ));
+        assert!(code
+            .constants
+            .iter()
+            .any(|constant| matches!(constant, ConstantData::Str { value } if value == "ab")));
+    }
+
+    #[test]
+    fn test_peephole_dead_store() {
+        // StoreFast x / DeleteFast x collapses into a single Pop
+        assert_peephole_shrinks(
+            "\
+def f():
+    x = 1
+    del x
+",
+        );
+    }
+
+    #[test]
+    fn test_peephole_jump_to_jump() {
+        // the then-branch jumps to the end of the `if`, which holds nothing
+        // but the jump back to the loop header; retargeting the first jump
+        // leaves that block unreachable and it is dropped
+        assert_peephole_shrinks(
+            "\
+while a:
+    if b:
+        x = 1
+    else:
+        continue
+",
+        );
+    }
+
+    #[test]
+    fn test_match_statement() {
+        let code = compile_exec(
+            "\
+match command:
+    case [x, *rest] if x:
+        pass
+    case {'key': value, **extra}:
+        pass
+    case Point(0, y=1) | None:
+        pass
+    case _:
+        pass
+",
+        );
+        let has = |f: fn(Instruction) -> bool| code.instructions.iter().any(|unit| f(unit.op));
+        assert!(has(|op| matches!(op, Instruction::MatchSequence)));
+        assert!(has(|op| matches!(op, Instruction::MatchMapping)));
+        assert!(has(|op| matches!(op, Instruction::MatchKeys)));
+        assert!(has(|op| matches!(op, Instruction::CopyDictWithoutKeys)));
+        assert!(has(|op| matches!(op, Instruction::MatchClass { .. })));
+    }
+
+    #[test]
+    fn test_except_star() {
+        let code = compile_exec(
+            "\
+try:
+    pass
+except* ValueError as e:
+    pass
+except* (TypeError, OSError):
+    pass
+",
+        );
+        let splits = code
+            .instructions
+            .iter()
+            .filter(|unit| matches!(unit.op, Instruction::ExceptionGroupMatch))
+            .count();
+        assert_eq!(splits, 2);
+    }
+
     #[test]
     fn test_nested_double_async_with() {
         assert_dis_snapshot!(compile_exec(
@@ -83,9 +83,10 @@ impl CodeInfo {
     pub fn finalize_code(mut self, optimize: u8) -> CodeObject {
         if optimize > 0 {
             self.dce();
+            self.peephole();
         }
 
-        let max_stackdepth = self.max_stackdepth();
+        let (max_stackdepth, max_blockdepth) = self.max_depths();
         let cell2arg = self.cell2arg();
 
         let CodeInfo {
@@ -162,6 +163,7 @@ impl CodeInfo {
             obj_name,
 
             max_stackdepth,
+            max_blockdepth,
             instructions: instructions.into_boxed_slice(),
             locations: locations.into_boxed_slice(),
             constants: constants.into_iter().collect(),
@@ -221,15 +223,100 @@ impl CodeInfo {
         }
     }
 
-    fn max_stackdepth(&self) -> u32 {
+    /// A few peephole optimizations over the block graph; the code they leave
+    /// behind behaves identically, it's just shorter.
+    fn peephole(&mut self) {
+        self.retarget_jump_chains();
+        for block in &mut self.blocks {
+            peephole_block(&mut block.instructions);
+        }
+        self.unlink_unreachable();
+    }
+
+    /// Point every jump that lands on an unconditional `Jump` straight at
+    /// that jump's eventual target, skipping any chain of jump-to-jumps.
+    fn retarget_jump_chains(&mut self) {
+        for block_idx in 0..self.blocks.len() {
+            for instr_idx in 0..self.blocks[block_idx].instructions.len() {
+                let target = self.blocks[block_idx].instructions[instr_idx].target;
+                if target != BlockIdx::NULL {
+                    self.blocks[block_idx].instructions[instr_idx].target =
+                        self.follow_jump_chain(target);
+                }
+            }
+        }
+    }
+
+    fn follow_jump_chain(&self, mut target: BlockIdx) -> BlockIdx {
+        // bounded so that a cycle of jumps (an infinite loop in the source)
+        // can't hang the pass
+        for _ in 0..self.blocks.len() {
+            // a block without instructions falls through to its successor
+            let mut entered = target;
+            while self.blocks[entered].instructions.is_empty()
+                && self.blocks[entered].next != BlockIdx::NULL
+            {
+                entered = self.blocks[entered].next;
+            }
+            match self.blocks[entered].instructions.first() {
+                Some(info) if matches!(info.instr, Instruction::Jump { .. }) => {
+                    target = info.target;
+                }
+                _ => break,
+            }
+        }
+        target
+    }
+
+    /// Unlink blocks that retargeting left without any way to be reached
+    /// from the emission order, so they don't take up space in the final
+    /// bytecode.
+    fn unlink_unreachable(&mut self) {
+        let mut reachable = vec![false; self.blocks.len()];
+        let mut work = vec![BlockIdx(0)];
+        while let Some(idx) = work.pop() {
+            if std::mem::replace(&mut reachable[idx.idx()], true) {
+                continue;
+            }
+            let block = &self.blocks[idx];
+            let mut falls_through = true;
+            for info in &block.instructions {
+                if info.target != BlockIdx::NULL {
+                    work.push(info.target);
+                }
+                if info.instr.unconditional_branch() {
+                    falls_through = false;
+                    break;
+                }
+            }
+            if falls_through && block.next != BlockIdx::NULL {
+                work.push(block.next);
+            }
+        }
+        for idx in 0..self.blocks.len() {
+            if !reachable[idx] {
+                continue;
+            }
+            let mut next = self.blocks[idx].next;
+            while next != BlockIdx::NULL && !reachable[next.idx()] {
+                next = self.blocks[next].next;
+            }
+            self.blocks[idx].next = next;
+        }
+    }
+
+    /// The maximum depths the value stack and the block stack reach on any
+    /// path through the code, so the frame can allocate both up front.
+    fn max_depths(&self) -> (u32, u32) {
         let mut maxdepth = 0u32;
+        let mut max_blockdepth = 0u32;
         let mut stack = Vec::with_capacity(self.blocks.len());
-        let mut start_depths = vec![u32::MAX; self.blocks.len()];
-        start_depths[0] = 0;
+        let mut start_depths = vec![(u32::MAX, u32::MAX); self.blocks.len()];
+        start_depths[0] = (0, 0);
         stack.push(BlockIdx(0));
         const DEBUG: bool = false;
         'process_blocks: while let Some(block) = stack.pop() {
-            let mut depth = start_depths[block.idx()];
+            let (mut depth, mut blockdepth) = start_depths[block.idx()];
             if DEBUG {
                 eprintln!("===BLOCK {}===", block.0);
             }
@@ -253,6 +340,10 @@ impl CodeInfo {
                 if new_depth > maxdepth {
                     maxdepth = new_depth
                 }
+                let new_blockdepth = blockdepth.checked_add_signed(instr.block_effect()).unwrap();
+                if new_blockdepth > max_blockdepth {
+                    max_blockdepth = new_blockdepth
+                }
                 // we don't want to worry about Break/Continue, they use unwinding to jump to
                 // their targets and as such the stack size is taken care of in frame.rs by setting
                 // it back to the level it was at when SetupLoop was run
@@ -267,19 +358,25 @@ impl CodeInfo {
                     if target_depth > maxdepth {
                         maxdepth = target_depth
                     }
-                    stackdepth_push(&mut stack, &mut start_depths, i.target, target_depth);
+                    depths_push(
+                        &mut stack,
+                        &mut start_depths,
+                        i.target,
+                        (target_depth, new_blockdepth),
+                    );
                 }
                 depth = new_depth;
+                blockdepth = new_blockdepth;
                 if instr.unconditional_branch() {
                     continue 'process_blocks;
                 }
             }
-            stackdepth_push(&mut stack, &mut start_depths, block.next, depth);
+            depths_push(&mut stack, &mut start_depths, block.next, (depth, blockdepth));
         }
         if DEBUG {
             eprintln!("DONE: {maxdepth}");
         }
-        maxdepth
+        (maxdepth, max_blockdepth)
     }
 }
 
@@ -302,15 +399,52 @@ impl InstrDisplayContext for CodeInfo {
     }
 }
 
-fn stackdepth_push(
+/// Rewrite instruction pairs within a block whose combined effect can be had
+/// for less: a store into a local that is deleted right away only pops its
+/// value, and duplicating a value just to pop the copy does nothing at all.
+fn peephole_block(instructions: &mut Vec<InstructionInfo>) {
+    let mut i = 0;
+    while i + 1 < instructions.len() {
+        let (cur, next) = (instructions[i], instructions[i + 1]);
+        let advance = match (cur.instr, next.instr) {
+            (Instruction::StoreFast(_), Instruction::DeleteFast(_)) if cur.arg.0 == next.arg.0 => {
+                instructions[i] = InstructionInfo {
+                    instr: Instruction::Pop,
+                    arg: OpArg::null(),
+                    target: BlockIdx::NULL,
+                    location: cur.location,
+                };
+                instructions.remove(i + 1);
+                false
+            }
+            (Instruction::Duplicate, Instruction::Pop) => {
+                instructions.drain(i..=i + 1);
+                false
+            }
+            _ => true,
+        };
+        if advance {
+            i += 1;
+        } else {
+            // rewriting may have brought a new pair together at or before `i`
+            i = i.saturating_sub(1);
+        }
+    }
+}
+
+fn depths_push(
     stack: &mut Vec<BlockIdx>,
-    start_depths: &mut [u32],
+    start_depths: &mut [(u32, u32)],
     target: BlockIdx,
-    depth: u32,
+    (depth, blockdepth): (u32, u32),
 ) {
-    let block_depth = &mut start_depths[target.idx()];
-    if *block_depth == u32::MAX || depth > *block_depth {
-        *block_depth = depth;
+    let (start_depth, start_blockdepth) = &mut start_depths[target.idx()];
+    if *start_depth == u32::MAX {
+        (*start_depth, *start_blockdepth) = (depth, blockdepth);
+        stack.push(target);
+    } else if depth > *start_depth || blockdepth > *start_blockdepth {
+        *start_depth = depth.max(*start_depth);
+        *start_blockdepth = blockdepth.max(*start_blockdepth);
         stack.push(target);
     }
 }
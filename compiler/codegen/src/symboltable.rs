@@ -829,14 +829,15 @@ impl SymbolTableBuilder {
                 self.scan_statements(orelse)?;
                 self.scan_statements(finalbody)?;
             }
-            Match {
-                subject: _,
-                cases: _,
-            } => {
-                return Err(SymbolTableError {
-                    error: "match expression is not implemented yet".to_owned(),
-                    location: Location::default(),
-                });
+            Match { subject, cases } => {
+                self.scan_expression(subject, ExpressionContext::Load)?;
+                for case in cases {
+                    self.scan_pattern(&case.pattern)?;
+                    if let Some(guard) = &case.guard {
+                        self.scan_expression(guard, ExpressionContext::Load)?;
+                    }
+                    self.scan_statements(&case.body)?;
+                }
             }
             Raise { exc, cause } => {
                 if let Some(expression) = exc {
@@ -861,6 +862,58 @@ impl SymbolTableBuilder {
         Ok(())
     }
 
+    fn scan_patterns(&mut self, patterns: &[ast::Pattern]) -> SymbolTableResult {
+        for pattern in patterns {
+            self.scan_pattern(pattern)?;
+        }
+        Ok(())
+    }
+
+    fn scan_pattern(&mut self, pattern: &ast::Pattern) -> SymbolTableResult {
+        use ast::PatternKind::*;
+        let location = pattern.location;
+        match &pattern.node {
+            MatchValue { value } => self.scan_expression(value, ExpressionContext::Load)?,
+            MatchSingleton { .. } => {}
+            MatchSequence { patterns } | MatchOr { patterns } => self.scan_patterns(patterns)?,
+            MatchMapping {
+                keys,
+                patterns,
+                rest,
+            } => {
+                self.scan_expressions(keys, ExpressionContext::Load)?;
+                self.scan_patterns(patterns)?;
+                if let Some(rest) = rest {
+                    self.register_name(rest, SymbolUsage::Assigned, location)?;
+                }
+            }
+            MatchClass {
+                cls,
+                patterns,
+                kwd_attrs: _,
+                kwd_patterns,
+            } => {
+                self.scan_expression(cls, ExpressionContext::Load)?;
+                self.scan_patterns(patterns)?;
+                self.scan_patterns(kwd_patterns)?;
+            }
+            MatchStar { name } => {
+                if let Some(name) = name {
+                    self.register_name(name, SymbolUsage::Assigned, location)?;
+                }
+            }
+            MatchAs { pattern, name } => {
+                if let Some(pattern) = pattern {
+                    self.scan_pattern(pattern)?;
+                }
+                if let Some(name) = name {
+                    self.register_name(name, SymbolUsage::Assigned, location)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn scan_expression(
         &mut self,
         expression: &ast::Expr,
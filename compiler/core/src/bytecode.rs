@@ -98,6 +98,7 @@ pub struct CodeObject<C: Constant = ConstantData> {
     pub source_path: C::Name,
     pub first_line_number: u32,
     pub max_stackdepth: u32,
+    pub max_blockdepth: u32,
     pub obj_name: C::Name,
     // Name of the object that created this code object
     pub cell2arg: Option<Box<[i32]>>,
@@ -582,6 +583,36 @@ pub enum Instruction {
     GetAIter,
     GetANext,
     EndAsyncFor,
+    /// Push `len(TOS)` without popping the subject, for match statement
+    /// length checks.
+    GetLen,
+    /// Push a boolean telling whether TOS is a mapping, keeping TOS itself.
+    MatchMapping,
+    /// Push a boolean telling whether TOS is a sequence (but not a str,
+    /// bytes or iterator), keeping TOS itself.
+    MatchSequence,
+    /// TOS is a tuple of keys and TOS1 the match subject. Push a tuple of
+    /// the corresponding values, or None if one of the keys is missing; the
+    /// keys stay on the stack for a later [`CopyDictWithoutKeys`].
+    ///
+    /// [`CopyDictWithoutKeys`]: Instruction::CopyDictWithoutKeys
+    MatchKeys,
+    /// TOS is a tuple of keys and TOS1 the match subject. Replace the keys
+    /// with a dict holding a copy of the subject without those keys, for a
+    /// `**rest` mapping pattern.
+    CopyDictWithoutKeys,
+    /// TOS is a tuple of keyword attribute names, TOS1 the class being
+    /// matched against and TOS2 the subject. Pop all three and push a tuple
+    /// of extracted attributes (`nargs` positional via `__match_args__`,
+    /// then the keyword ones), or None if the subject doesn't match.
+    MatchClass {
+        nargs: Arg<u32>,
+    },
+    /// TOS is an exception type (or tuple of types) and TOS1 an exception.
+    /// Pop both and push the part of the exception that does not match the
+    /// type, then the matching subgroup; either may be None. Implements the
+    /// splitting done by an `except*` clause.
+    ExceptionGroupMatch,
     ExtendedArg,
 }
 const _: () = assert!(mem::size_of::<Instruction>() == 1);
@@ -1055,6 +1086,7 @@ impl<C: Constant> CodeObject<C> {
             kwonlyarg_count: self.kwonlyarg_count,
             first_line_number: self.first_line_number,
             max_stackdepth: self.max_stackdepth,
+            max_blockdepth: self.max_blockdepth,
             cell2arg: self.cell2arg,
         }
     }
@@ -1084,6 +1116,7 @@ impl<C: Constant> CodeObject<C> {
             kwonlyarg_count: self.kwonlyarg_count,
             first_line_number: self.first_line_number,
             max_stackdepth: self.max_stackdepth,
+            max_blockdepth: self.max_blockdepth,
             cell2arg: self.cell2arg.clone(),
         }
     }
@@ -1199,12 +1232,12 @@ impl Instruction {
                     + 1
             }
             CallFunctionPositional { nargs } => -(nargs.get(arg) as i32) - 1 + 1,
-            CallMethodPositional { nargs } => -(nargs.get(arg) as i32) - 3 + 1,
+            CallMethodPositional { nargs } => -(nargs.get(arg) as i32) - 2 + 1,
             CallFunctionKeyword { nargs } => -1 - (nargs.get(arg) as i32) - 1 + 1,
-            CallMethodKeyword { nargs } => -1 - (nargs.get(arg) as i32) - 3 + 1,
+            CallMethodKeyword { nargs } => -1 - (nargs.get(arg) as i32) - 2 + 1,
             CallFunctionEx { has_kwargs } => -1 - (has_kwargs.get(arg) as i32) - 1 + 1,
-            CallMethodEx { has_kwargs } => -1 - (has_kwargs.get(arg) as i32) - 3 + 1,
-            LoadMethod { .. } => -1 + 3,
+            CallMethodEx { has_kwargs } => -1 - (has_kwargs.get(arg) as i32) - 2 + 1,
+            LoadMethod { .. } => -1 + 2,
             ForIter { .. } => {
                 if jump {
                     -1
@@ -1263,10 +1296,31 @@ impl Instruction {
             GetAIter => 0,
             GetANext => 1,
             EndAsyncFor => -2,
+            GetLen | MatchMapping | MatchSequence | MatchKeys => 1,
+            CopyDictWithoutKeys => 0,
+            MatchClass { .. } => -2,
+            ExceptionGroupMatch => 0,
             ExtendedArg => 0,
         }
     }
 
+    /// Gets how this instruction changes the depth of the runtime block
+    /// stack. The effect is the same whether or not a jump is taken: the
+    /// handler targets of the `Setup*` instructions are reached by unwinding,
+    /// which swaps the just-pushed block for its handler block.
+    pub fn block_effect(&self) -> i32 {
+        match self {
+            SetupLoop
+            | SetupExcept { .. }
+            | SetupFinally { .. }
+            | SetupWith { .. }
+            | SetupAsyncWith { .. }
+            | EnterFinally => 1,
+            PopBlock | EndFinally | WithCleanupFinish | PopException => -1,
+            _ => 0,
+        }
+    }
+
     pub fn display<'a>(
         &'a self,
         arg: OpArg,
@@ -1424,6 +1478,13 @@ impl Instruction {
             GetAIter => w!(GetAIter),
             GetANext => w!(GetANext),
             EndAsyncFor => w!(EndAsyncFor),
+            GetLen => w!(GetLen),
+            MatchMapping => w!(MatchMapping),
+            MatchSequence => w!(MatchSequence),
+            MatchKeys => w!(MatchKeys),
+            CopyDictWithoutKeys => w!(CopyDictWithoutKeys),
+            MatchClass { nargs } => w!(MatchClass, nargs),
+            ExceptionGroupMatch => w!(ExceptionGroupMatch),
             ExtendedArg => w!(ExtendedArg, Arg::<u32>::marker()),
         }
     }
@@ -7,11 +7,18 @@ use serde::{Deserialize, Serialize};
 pub struct Location {
     pub(super) row: u32,
     pub(super) column: u32,
+    pub(super) end_row: u32,
+    pub(super) end_column: u32,
 }
 
 impl Default for Location {
     fn default() -> Self {
-        Self { row: 1, column: 0 }
+        Self {
+            row: 1,
+            column: 0,
+            end_row: 1,
+            end_column: 0,
+        }
     }
 }
 
@@ -36,7 +43,20 @@ impl Location {
     pub fn new(row: usize, column: usize) -> Self {
         let row = row.try_into().expect("Location::row over u32");
         let column = column.try_into().expect("Location::column over u32");
-        Location { row, column }
+        Location {
+            row,
+            column,
+            end_row: row,
+            end_column: column,
+        }
+    }
+
+    /// This location, recording that the range it points at runs up to `end`
+    /// (whose own row/column mark the exclusive end of the range).
+    pub fn span_to(mut self, end: Location) -> Self {
+        self.end_row = end.row;
+        self.end_column = end.column;
+        self
     }
 
     /// Current row
@@ -49,22 +69,40 @@ impl Location {
         self.column as usize
     }
 
+    /// Row where the range this location covers ends; equal to `row` for a
+    /// location that marks a single point
+    pub fn end_row(&self) -> usize {
+        self.end_row as usize
+    }
+
+    /// Column just past the range this location covers; equal to `column`
+    /// for a location that marks a single point
+    pub fn end_column(&self) -> usize {
+        self.end_column as usize
+    }
+
     pub fn reset(&mut self) {
         self.row = 1;
         self.column = 0;
+        self.end_row = 1;
+        self.end_column = 0;
     }
 
     pub fn go_right(&mut self) {
         self.column += 1;
+        self.end_column = self.column;
     }
 
     pub fn go_left(&mut self) {
         self.column -= 1;
+        self.end_column = self.column;
     }
 
     pub fn newline(&mut self) {
         self.row += 1;
         self.column = 0;
+        self.end_row = self.row;
+        self.end_column = 0;
     }
 
     pub fn with_col_offset<T: TryInto<isize>>(&self, offset: T) -> Self
@@ -78,6 +116,8 @@ impl Location {
         Self {
             row: self.row,
             column,
+            end_row: self.end_row,
+            end_column: self.end_column.wrapping_add(column.wrapping_sub(self.column)),
         }
     }
 
@@ -92,6 +132,8 @@ impl Location {
         Self {
             row,
             column: self.column,
+            end_row: self.end_row.wrapping_add(row.wrapping_sub(self.row)),
+            end_column: self.end_column,
         }
     }
 }
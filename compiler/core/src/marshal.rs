@@ -1,4 +1,5 @@
 use core::fmt;
+use std::collections::HashMap;
 use std::convert::Infallible;
 
 use num_bigint::{BigInt, Sign};
@@ -6,7 +7,7 @@ use num_complex::Complex64;
 
 use crate::{bytecode::*, Location};
 
-pub const FORMAT_VERSION: u32 = 4;
+pub const FORMAT_VERSION: u32 = 6;
 
 #[derive(Debug)]
 pub enum MarshalError {
@@ -54,8 +55,8 @@ enum Type {
     Complex = b'y',
     // Long = b'l',  // i32
     Bytes = b's', // = TYPE_STRING
-    // Interned = b't',
-    // Ref = b'r',
+    Interned = b't',
+    Ref = b'r',
     Tuple = b'(',
     List = b'[',
     Dict = b'{',
@@ -70,7 +71,8 @@ enum Type {
     // ShortAscii = b'z',
     // ShortAsciiInterned = b'Z',
 }
-// const FLAG_REF: u8 = b'\x80';
+/// Set on the type byte of a value that a later [`Type::Ref`] refers back to.
+const FLAG_REF: u8 = b'\x80';
 
 impl TryFrom<u8> for Type {
     type Error = MarshalError;
@@ -88,8 +90,8 @@ impl TryFrom<u8> for Type {
             b'y' => Complex,
             // b'l' => Long,
             b's' => Bytes,
-            // b't' => Interned,
-            // b'r' => Ref,
+            b't' => Interned,
+            b'r' => Ref,
             b'(' => Tuple,
             b'[' => List,
             b'{' => Dict,
@@ -186,6 +188,8 @@ pub fn deserialize_code<R: Read, Bag: ConstantBag>(
             Ok(Location {
                 row: rdr.read_u32()?,
                 column: rdr.read_u32()?,
+                end_row: rdr.read_u32()?,
+                end_column: rdr.read_u32()?,
             })
         })
         .collect::<Result<Box<[Location]>>>()?;
@@ -201,6 +205,7 @@ pub fn deserialize_code<R: Read, Bag: ConstantBag>(
 
     let first_line_number = rdr.read_u32()?;
     let max_stackdepth = rdr.read_u32()?;
+    let max_blockdepth = rdr.read_u32()?;
 
     let len = rdr.read_u32()?;
     let obj_name = bag.make_name(rdr.read_str(len)?);
@@ -244,6 +249,7 @@ pub fn deserialize_code<R: Read, Bag: ConstantBag>(
         source_path,
         first_line_number,
         max_stackdepth,
+        max_blockdepth,
         obj_name,
         cell2arg,
         constants,
@@ -277,6 +283,20 @@ pub trait MarshalBag: Copy {
         &self,
         it: impl Iterator<Item = (Self::Value, Self::Value)>,
     ) -> Result<Self::Value>;
+    /// A string the writer marked as interned; bags with no interner can
+    /// fall back to a plain string.
+    fn make_interned_str(&self, value: &str) -> Self::Value {
+        self.make_str(value)
+    }
+    /// Remember a value the writer marked as shared, so a later
+    /// back-reference can resolve to it. Indices are handed out in the
+    /// order values finish deserializing, matching the writer.
+    fn register_ref(&self, _value: &Self::Value) {}
+    /// Resolve a back-reference to the `index`th value passed to
+    /// [`register_ref`](Self::register_ref).
+    fn get_ref(&self, _index: u32) -> Option<Self::Value> {
+        None
+    }
     type ConstantBag: ConstantBag;
     fn constant_bag(self) -> Self::ConstantBag;
 }
@@ -341,7 +361,8 @@ impl<Bag: ConstantBag> MarshalBag for Bag {
 }
 
 pub fn deserialize_value<R: Read, Bag: MarshalBag>(rdr: &mut R, bag: Bag) -> Result<Bag::Value> {
-    let typ = Type::try_from(rdr.read_u8()?)?;
+    let type_byte = rdr.read_u8()?;
+    let typ = Type::try_from(type_byte & !FLAG_REF)?;
     let value = match typ {
         Type::True => bag.make_bool(true),
         Type::False => bag.make_bool(false),
@@ -370,6 +391,15 @@ pub fn deserialize_value<R: Read, Bag: MarshalBag>(rdr: &mut R, bag: Bag) -> Res
             let value = rdr.read_str(len)?;
             bag.make_str(value)
         }
+        Type::Interned => {
+            let len = rdr.read_u32()?;
+            let value = rdr.read_str(len)?;
+            bag.make_interned_str(value)
+        }
+        Type::Ref => {
+            let index = rdr.read_u32()?;
+            bag.get_ref(index).ok_or(MarshalError::BadType)?
+        }
         Type::Tuple => {
             let len = rdr.read_u32()?;
             let it = (0..len).map(|_| deserialize_value(rdr, bag));
@@ -407,6 +437,9 @@ pub fn deserialize_value<R: Read, Bag: MarshalBag>(rdr: &mut R, bag: Bag) -> Res
         }
         Type::Code => bag.make_code(deserialize_code(rdr, bag.constant_bag())?),
     };
+    if type_byte & FLAG_REF != 0 {
+        bag.register_ref(&value);
+    }
     Ok(value)
 }
 
@@ -414,6 +447,13 @@ pub trait Dumpable: Sized {
     type Error;
     type Constant: Constant;
     fn with_dump<R>(&self, f: impl FnOnce(DumpableValue<'_, Self>) -> R) -> Result<R, Self::Error>;
+    /// A stable identity for back-reference tracking. Values that may appear
+    /// several times in one dump return `Some`, so every occurrence after the
+    /// first is written as a [`Type::Ref`] to the first; code-object
+    /// constants return `None` and are always written in full.
+    fn identity(&self) -> Option<usize> {
+        None
+    }
 }
 
 pub enum DumpableValue<'a, D: Dumpable> {
@@ -422,6 +462,7 @@ pub enum DumpableValue<'a, D: Dumpable> {
     Complex(Complex64),
     Boolean(bool),
     Str(&'a str),
+    InternedStr(&'a str),
     Bytes(&'a [u8]),
     Code(&'a CodeObject<D::Constant>),
     Tuple(&'a [D]),
@@ -483,7 +524,9 @@ impl Write for Vec<u8> {
 }
 
 pub(crate) fn write_len<W: Write>(buf: &mut W, len: usize) {
-    let Ok(len) = len.try_into() else { panic!("too long to serialize") };
+    let Ok(len) = len.try_into() else {
+        panic!("too long to serialize")
+    };
     buf.write_u32(len);
 }
 
@@ -492,13 +535,59 @@ pub(crate) fn write_vec<W: Write>(buf: &mut W, slice: &[u8]) {
     buf.write_slice(slice);
 }
 
+/// Back-reference table for [`serialize_object`]: the identity of every
+/// already-written shareable value, mapped to the index the reader will
+/// know it by.
+#[derive(Default)]
+pub struct DumpRefs {
+    indices: HashMap<usize, u32>,
+}
+
+/// Serialize `value`, writing any shareable value (per
+/// [`Dumpable::identity`]) that was already written through `refs` as a
+/// back-reference to its first occurrence. Sharing round-trips through
+/// [`deserialize_value`]; cyclic values remain unsupported, as they always
+/// were.
+pub fn serialize_object<W: Write, D: Dumpable>(
+    buf: &mut W,
+    value: &D,
+    refs: &mut DumpRefs,
+) -> Result<(), D::Error> {
+    let identity = value.identity();
+    if let Some(id) = identity {
+        if let Some(&index) = refs.indices.get(&id) {
+            buf.write_u8(Type::Ref as u8);
+            buf.write_u32(index);
+            return Ok(());
+        }
+    }
+    value.with_dump(|val| serialize_rec(buf, val, refs, identity.is_some()))??;
+    if let Some(id) = identity {
+        // indices are handed out in completion order, which is the order the
+        // reader finishes rebuilding the values in
+        let index = refs.indices.len().try_into().expect("too many refs");
+        refs.indices.insert(id, index);
+    }
+    Ok(())
+}
+
 pub fn serialize_value<W: Write, D: Dumpable>(
     buf: &mut W,
     constant: DumpableValue<'_, D>,
 ) -> Result<(), D::Error> {
+    serialize_rec(buf, constant, &mut DumpRefs::default(), false)
+}
+
+fn serialize_rec<W: Write, D: Dumpable>(
+    buf: &mut W,
+    constant: DumpableValue<'_, D>,
+    refs: &mut DumpRefs,
+    shared: bool,
+) -> Result<(), D::Error> {
+    let flag = if shared { FLAG_REF } else { 0 };
     match constant {
         DumpableValue::Integer(int) => {
-            buf.write_u8(Type::Int as u8);
+            buf.write_u8(Type::Int as u8 | flag);
             let (sign, bytes) = int.to_bytes_le();
             let len: i32 = bytes.len().try_into().expect("too long to serialize");
             let len = if sign == Sign::Minus { -len } else { len };
@@ -506,72 +595,76 @@ pub fn serialize_value<W: Write, D: Dumpable>(
             buf.write_slice(&bytes);
         }
         DumpableValue::Float(f) => {
-            buf.write_u8(Type::Float as u8);
+            buf.write_u8(Type::Float as u8 | flag);
             buf.write_u64(f.to_bits());
         }
         DumpableValue::Complex(c) => {
-            buf.write_u8(Type::Complex as u8);
+            buf.write_u8(Type::Complex as u8 | flag);
             buf.write_u64(c.re.to_bits());
             buf.write_u64(c.im.to_bits());
         }
         DumpableValue::Boolean(b) => {
-            buf.write_u8(if b { Type::True } else { Type::False } as u8);
+            buf.write_u8(if b { Type::True } else { Type::False } as u8 | flag);
         }
         DumpableValue::Str(s) => {
-            buf.write_u8(Type::Unicode as u8);
+            buf.write_u8(Type::Unicode as u8 | flag);
+            write_vec(buf, s.as_bytes());
+        }
+        DumpableValue::InternedStr(s) => {
+            buf.write_u8(Type::Interned as u8 | flag);
             write_vec(buf, s.as_bytes());
         }
         DumpableValue::Bytes(b) => {
-            buf.write_u8(Type::Bytes as u8);
+            buf.write_u8(Type::Bytes as u8 | flag);
             write_vec(buf, b);
         }
         DumpableValue::Code(c) => {
-            buf.write_u8(Type::Code as u8);
+            buf.write_u8(Type::Code as u8 | flag);
             serialize_code(buf, c);
         }
         DumpableValue::Tuple(tup) => {
-            buf.write_u8(Type::Tuple as u8);
+            buf.write_u8(Type::Tuple as u8 | flag);
             write_len(buf, tup.len());
             for val in tup {
-                val.with_dump(|val| serialize_value(buf, val))??
+                serialize_object(buf, val, refs)?
             }
         }
         DumpableValue::None => {
-            buf.write_u8(Type::None as u8);
+            buf.write_u8(Type::None as u8 | flag);
         }
         DumpableValue::Ellipsis => {
-            buf.write_u8(Type::Ellipsis as u8);
+            buf.write_u8(Type::Ellipsis as u8 | flag);
         }
         DumpableValue::StopIter => {
-            buf.write_u8(Type::StopIter as u8);
+            buf.write_u8(Type::StopIter as u8 | flag);
         }
         DumpableValue::List(l) => {
-            buf.write_u8(Type::List as u8);
+            buf.write_u8(Type::List as u8 | flag);
             write_len(buf, l.len());
             for val in l {
-                val.with_dump(|val| serialize_value(buf, val))??
+                serialize_object(buf, val, refs)?
             }
         }
         DumpableValue::Set(set) => {
-            buf.write_u8(Type::Set as u8);
+            buf.write_u8(Type::Set as u8 | flag);
             write_len(buf, set.len());
             for val in set {
-                val.with_dump(|val| serialize_value(buf, val))??
+                serialize_object(buf, val, refs)?
             }
         }
         DumpableValue::Frozenset(set) => {
-            buf.write_u8(Type::FrozenSet as u8);
+            buf.write_u8(Type::FrozenSet as u8 | flag);
             write_len(buf, set.len());
             for val in set {
-                val.with_dump(|val| serialize_value(buf, val))??
+                serialize_object(buf, val, refs)?
             }
         }
         DumpableValue::Dict(d) => {
-            buf.write_u8(Type::Dict as u8);
+            buf.write_u8(Type::Dict as u8 | flag);
             write_len(buf, d.len());
             for (k, v) in d {
-                k.with_dump(|val| serialize_value(buf, val))??;
-                v.with_dump(|val| serialize_value(buf, val))??;
+                serialize_object(buf, k, refs)?;
+                serialize_object(buf, v, refs)?;
             }
         }
     }
@@ -588,6 +681,8 @@ pub fn serialize_code<W: Write, C: Constant>(buf: &mut W, code: &CodeObject<C>)
     for loc in &*code.locations {
         buf.write_u32(loc.row);
         buf.write_u32(loc.column);
+        buf.write_u32(loc.end_row);
+        buf.write_u32(loc.end_column);
     }
 
     buf.write_u16(code.flags.bits());
@@ -600,6 +695,7 @@ pub fn serialize_code<W: Write, C: Constant>(buf: &mut W, code: &CodeObject<C>)
 
     buf.write_u32(code.first_line_number);
     buf.write_u32(code.max_stackdepth);
+    buf.write_u32(code.max_blockdepth);
 
     write_vec(buf, code.obj_name.as_ref().as_bytes());
 
@@ -16,9 +16,9 @@ enum ParameterKind {
 impl ParameterKind {
     fn from_ident(ident: &Ident) -> Option<ParameterKind> {
         match ident.to_string().as_str() {
-            "positional" => Some(ParameterKind::PositionalOnly),
+            "positional" | "positional_only" => Some(ParameterKind::PositionalOnly),
             "any" => Some(ParameterKind::PositionalOrKeyword),
-            "named" => Some(ParameterKind::KeywordOnly),
+            "named" | "keyword_only" => Some(ParameterKind::KeywordOnly),
             "flatten" => Some(ParameterKind::Flatten),
             _ => None,
         }
@@ -56,7 +56,8 @@ impl ArgAttribute {
                 err_span!(
                     first_arg,
                     "The first argument to #[pyarg()] must be the parameter type, either \
-                         'positional', 'any', 'named', or 'flatten'."
+                         'positional' ('positional_only'), 'any', 'named' ('keyword_only'), \
+                         or 'flatten'."
                 )
             })?;
 
@@ -119,7 +120,10 @@ impl ArgAttribute {
     }
 }
 
-fn generate_field((i, field): (usize, &Field)) -> Result<TokenStream> {
+/// Generates the initializer for one field, and reports whether the field can
+/// consume keyword arguments (used to reject keywords outright for structs
+/// whose parameters are all positional-only).
+fn generate_field((i, field): (usize, &Field)) -> Result<(TokenStream, bool)> {
     let mut pyarg_attrs = field
         .attrs
         .iter()
@@ -140,18 +144,24 @@ fn generate_field((i, field): (usize, &Field)) -> Result<TokenStream> {
     let name = field.ident.as_ref();
     let name_string = name.map(Ident::to_string);
     if matches!(&name_string, Some(s) if s.starts_with("_phantom")) {
-        return Ok(quote! {
-            #name: ::std::marker::PhantomData,
-        });
+        return Ok((
+            quote! {
+                #name: ::std::marker::PhantomData,
+            },
+            false,
+        ));
     }
     let field_name = match name {
         Some(id) => id.to_token_stream(),
         None => syn::Index::from(i).into_token_stream(),
     };
     if let ParameterKind::Flatten = attr.kind {
-        return Ok(quote! {
-            #field_name: ::rustpython_vm::function::FromArgs::from_args(vm, args)?,
-        });
+        return Ok((
+            quote! {
+                #field_name: ::rustpython_vm::function::FromArgs::from_args(vm, args)?,
+            },
+            true,
+        ));
     }
     let pyname = attr
         .name
@@ -160,6 +170,7 @@ fn generate_field((i, field): (usize, &Field)) -> Result<TokenStream> {
     let middle = quote! {
         .map(|x| ::rustpython_vm::convert::TryFromObject::try_from_object(vm, x)).transpose()?
     };
+    let required = attr.default.is_none();
     let ending = if let Some(default) = attr.default {
         let default = default.unwrap_or_else(|| parse_quote!(::std::default::Default::default()));
         quote! {
@@ -182,6 +193,14 @@ fn generate_field((i, field): (usize, &Field)) -> Result<TokenStream> {
     };
 
     let file_output = match attr.kind {
+        // A *required* positional-only parameter passed by name is an error,
+        // but an optional one falls through so that the name can still be
+        // picked up by a `**kwargs`-style catch-all later in the struct.
+        ParameterKind::PositionalOnly if required => {
+            quote! {
+                #field_name: args.take_positional_only(#pyname)?#middle #ending,
+            }
+        }
         ParameterKind::PositionalOnly => {
             quote! {
                 #field_name: args.take_positional()#middle #ending,
@@ -199,18 +218,37 @@ fn generate_field((i, field): (usize, &Field)) -> Result<TokenStream> {
         }
         ParameterKind::Flatten => unreachable!(),
     };
-    Ok(file_output)
+    let accepts_keywords = !matches!(attr.kind, ParameterKind::PositionalOnly);
+    Ok((file_output, accepts_keywords))
 }
 
 pub fn impl_from_args(input: DeriveInput) -> Result<TokenStream> {
-    let fields = match input.data {
-        Data::Struct(syn::DataStruct { fields, .. }) => fields
-            .iter()
-            .enumerate()
-            .map(generate_field)
-            .collect::<Result<TokenStream>>()?,
+    let (fields, num_fields, accepts_keywords) = match input.data {
+        Data::Struct(syn::DataStruct { fields, .. }) => {
+            let mut accepts_keywords = false;
+            let tokens = fields
+                .iter()
+                .enumerate()
+                .map(|field| {
+                    generate_field(field).map(|(tokens, field_accepts_keywords)| {
+                        accepts_keywords |= field_accepts_keywords;
+                        tokens
+                    })
+                })
+                .collect::<Result<TokenStream>>()?;
+            (tokens, fields.len(), accepts_keywords)
+        }
         _ => bail_span!(input, "FromArgs input must be a struct"),
     };
+    // When every parameter is positional-only, reject keywords up front with
+    // the CPython wording instead of the generic unexpected-keyword error.
+    let keyword_guard = (num_fields > 0 && !accepts_keywords).then(|| {
+        quote! {
+            if !args.kwargs.is_empty() {
+                return Err(::rustpython_vm::function::ArgumentError::NoKeywordArguments);
+            }
+        }
+    });
 
     let name = input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
@@ -220,6 +258,7 @@ pub fn impl_from_args(input: DeriveInput) -> Result<TokenStream> {
                 vm: &::rustpython_vm::VirtualMachine,
                 args: &mut ::rustpython_vm::function::FuncArgs
             ) -> ::std::result::Result<Self, ::rustpython_vm::function::ArgumentError> {
+                #keyword_guard
                 Ok(#name { #fields })
             }
         }
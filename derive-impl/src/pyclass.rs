@@ -1,7 +1,7 @@
 use super::Diagnostic;
 use crate::util::{
-    format_doc, path_eq, pyclass_ident_and_attrs, text_signature, ClassItemMeta, ContentItem,
-    ContentItemInner, ErrorVec, ItemMeta, ItemMetaInner, ItemNursery, SimpleItemMeta,
+    format_doc, path_eq, pyclass_ident_and_attrs, signature_defs, text_signature, ClassItemMeta,
+    ContentItem, ContentItemInner, ErrorVec, ItemMeta, ItemMetaInner, ItemNursery, SimpleItemMeta,
     ALL_ALLOWED_NAMES,
 };
 use proc_macro2::{Span, TokenStream};
@@ -640,6 +640,8 @@ where
                 doc = format_doc(&sig_doc, &doc);
                 quote!(.with_doc(#doc.to_owned(), ctx))
             });
+            let sig_defs = signature_defs(func.sig());
+            let sig = quote!(.with_sig(#sig_defs));
             let build_func = match self.inner.attr_name {
                 AttrName::Method => quote!(.build_method(ctx, class)),
                 AttrName::ClassMethod => quote!(.build_classmethod(ctx, class)),
@@ -656,6 +658,7 @@ where
                         ctx.names.#name_ident,
                         ctx.make_func_def(ctx.intern_str(#py_name), Self::#ident)
                             #doc
+                            #sig
                             #build_func
                         .into(),
                     );
@@ -666,6 +669,7 @@ where
                         #py_name,
                         ctx.make_func_def(ctx.intern_str(#py_name), Self::#ident)
                             #doc
+                            #sig
                             #build_func,
                         ctx,
                     );
@@ -699,9 +703,14 @@ where
         let item_meta = GetSetItemMeta::from_attr(ident.clone(), &item_attr)?;
 
         let (py_name, kind) = item_meta.getset_name()?;
-        args.context
-            .getset_items
-            .add_item(py_name, args.cfgs.to_vec(), kind, ident.clone())?;
+        let doc = args.attrs.doc();
+        args.context.getset_items.add_item(
+            py_name,
+            args.cfgs.to_vec(),
+            kind,
+            ident.clone(),
+            doc,
+        )?;
         Ok(())
     }
 }
@@ -848,6 +857,10 @@ where
         let member_kind = match item_meta.member_kind()? {
             Some(s) => match s.as_str() {
                 "bool" => MemberKind::Bool,
+                "int" => MemberKind::Int,
+                "float" => MemberKind::Float,
+                "str" => MemberKind::String,
+                "ssize_t" => MemberKind::SSizeT,
                 _ => unreachable!(),
             },
             _ => MemberKind::ObjectEx,
@@ -866,7 +879,10 @@ where
 #[derive(Default)]
 #[allow(clippy::type_complexity)]
 struct GetSetNursery {
-    map: HashMap<(String, Vec<Attribute>), (Option<Ident>, Option<Ident>, Option<Ident>)>,
+    map: HashMap<
+        (String, Vec<Attribute>),
+        (Option<Ident>, Option<Ident>, Option<Ident>, Option<String>),
+    >,
     validated: bool,
 }
 
@@ -883,12 +899,17 @@ impl GetSetNursery {
         cfgs: Vec<Attribute>,
         kind: GetSetItemKind,
         item_ident: Ident,
+        doc: Option<String>,
     ) -> Result<()> {
         assert!(!self.validated, "new item is not allowed after validation");
         if !matches!(kind, GetSetItemKind::Get) && !cfgs.is_empty() {
             bail_span!(item_ident, "Only the getter can have #[cfg]",);
         }
         let entry = self.map.entry((name.clone(), cfgs)).or_default();
+        // the getter's doc comment becomes the descriptor's __doc__
+        if matches!(kind, GetSetItemKind::Get) {
+            entry.3 = doc;
+        }
         let func = match kind {
             GetSetItemKind::Get => &mut entry.0,
             GetSetItemKind::Set => &mut entry.1,
@@ -907,7 +928,7 @@ impl GetSetNursery {
 
     fn validate(&mut self) -> Result<()> {
         let mut errors = Vec::new();
-        for ((name, _cfgs), (getter, setter, deleter)) in &self.map {
+        for ((name, _cfgs), (getter, setter, deleter, _doc)) in &self.map {
             if getter.is_none() {
                 errors.push(err_span!(
                     setter.as_ref().or(deleter.as_ref()).unwrap(),
@@ -928,7 +949,7 @@ impl ToTokens for GetSetNursery {
         let properties = self
             .map
             .iter()
-            .map(|((name, cfgs), (getter, setter, deleter))| {
+            .map(|((name, cfgs), (getter, setter, deleter, doc))| {
                 let setter = match setter {
                     Some(setter) => quote_spanned! { setter.span() => .with_set(Self::#setter)},
                     None => quote! {},
@@ -939,6 +960,10 @@ impl ToTokens for GetSetNursery {
                     }
                     None => quote! {},
                 };
+                let doc = match doc {
+                    Some(doc) => quote! { .with_doc(#doc.to_owned()) },
+                    None => quote! {},
+                };
                 quote_spanned! { getter.span() =>
                     #( #cfgs )*
                     class.set_str_attr(
@@ -946,7 +971,7 @@ impl ToTokens for GetSetNursery {
                         ::rustpython_vm::PyRef::new_ref(
                             ::rustpython_vm::builtins::PyGetSet::new(#name.into(), class)
                                 .with_get(Self::#getter)
-                                #setter #deleter,
+                                #setter #deleter #doc,
                                 ctx.types.getset_type.to_owned(), None),
                         ctx
                     );
@@ -971,6 +996,10 @@ enum MemberItemKind {
 #[derive(Eq, PartialEq, Hash)]
 enum MemberKind {
     Bool,
+    Int,
+    Float,
+    String,
+    SSizeT,
     ObjectEx,
 }
 
@@ -1027,6 +1056,18 @@ impl ToTokens for MemberNursery {
                     MemberKind::Bool => {
                         quote!(::rustpython_vm::builtins::descriptor::MemberKind::Bool)
                     }
+                    MemberKind::Int => {
+                        quote!(::rustpython_vm::builtins::descriptor::MemberKind::Int)
+                    }
+                    MemberKind::Float => {
+                        quote!(::rustpython_vm::builtins::descriptor::MemberKind::Float)
+                    }
+                    MemberKind::String => {
+                        quote!(::rustpython_vm::builtins::descriptor::MemberKind::String)
+                    }
+                    MemberKind::SSizeT => {
+                        quote!(::rustpython_vm::builtins::descriptor::MemberKind::SSizeT)
+                    }
                     MemberKind::ObjectEx => {
                         quote!(::rustpython_vm::builtins::descriptor::MemberKind::ObjectEx)
                     }
@@ -1166,7 +1207,10 @@ impl ItemMeta for SlotItemMeta {
             Some(HashMap::default())
         };
         let (Some(meta_map), None) = (meta_map, nested.next()) else {
-            bail_span!(meta_ident, "#[pyslot] must be of the form #[pyslot] or #[pyslot(slot_name)]")
+            bail_span!(
+                meta_ident,
+                "#[pyslot] must be of the form #[pyslot] or #[pyslot(slot_name)]"
+            )
         };
         Ok(Self::from_inner(ItemMetaInner {
             item_ident,
@@ -1,8 +1,8 @@
 use crate::error::Diagnostic;
 use crate::util::{
-    format_doc, iter_use_idents, pyclass_ident_and_attrs, text_signature, AttrItemMeta,
-    AttributeExt, ClassItemMeta, ContentItem, ContentItemInner, ErrorVec, ItemMeta, ItemNursery,
-    SimpleItemMeta, ALL_ALLOWED_NAMES,
+    format_doc, iter_use_idents, pyclass_ident_and_attrs, signature_defs, text_signature,
+    AttrItemMeta, AttributeExt, ClassItemMeta, ContentItem, ContentItemInner, ErrorVec, ItemMeta,
+    ItemNursery, SimpleItemMeta, ALL_ALLOWED_NAMES,
 };
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
@@ -333,9 +333,11 @@ impl ModuleItem for FunctionItem {
                 sig_doc
             };
             let doc = quote!(.with_doc(#doc.to_owned(), &vm.ctx));
+            let sig_defs = signature_defs(func.sig());
             let new_func = quote_spanned!(ident.span()=>
                 vm.ctx.make_func_def(vm.ctx.intern_str(#py_name), #ident)
                     #doc
+                    .with_sig(#sig_defs)
                     .into_function()
                     .with_module(vm.new_pyobj(#module.to_owned()))
                     .into_ref(&vm.ctx)
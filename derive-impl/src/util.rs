@@ -172,9 +172,16 @@ impl ItemMetaInner {
     pub fn _optional_str(&self, key: &str) -> Result<Option<String>> {
         let value = if let Some((_, meta)) = self.meta_map.get(key) {
             let Meta::NameValue(syn::MetaNameValue {
-                lit: syn::Lit::Str(lit), ..
-            }) = meta else {
-                bail_span!(meta, "#[{}({} = ...)] must exist as a string", self.meta_name(), key)
+                lit: syn::Lit::Str(lit),
+                ..
+            }) = meta
+            else {
+                bail_span!(
+                    meta,
+                    "#[{}({} = ...)] must exist as a string",
+                    self.meta_name(),
+                    key
+                )
             };
             Some(lit.value())
         } else {
@@ -613,3 +620,65 @@ fn func_sig(sig: &Signature) -> String {
 pub(crate) fn format_doc(sig: &str, doc: &str) -> String {
     format!("{sig}\n--\n\n{doc}")
 }
+
+// Best effort structured counterpart of `text_signature`: tokens for a
+// `&'static [ParameterDef]` literal describing the python-visible parameters.
+pub(crate) fn signature_defs(sig: &Signature) -> TokenStream {
+    let param = |name: &str, kind: &str, has_default: bool| {
+        let kind = Ident::new(kind, Span::call_site());
+        quote! {
+            ::rustpython_vm::function::ParameterDef {
+                name: #name,
+                kind: ::rustpython_vm::function::ParameterKind::#kind,
+                has_default: #has_default,
+            }
+        }
+    };
+    let defs = sig
+        .inputs
+        .iter()
+        .flat_map(|arg| {
+            use syn::FnArg::*;
+            let arg = match arg {
+                Typed(typed) => typed,
+                Receiver(_) => return vec![param("self", "PositionalOnly", false)],
+            };
+            let ty = arg.ty.as_ref();
+            let ty = quote!(#ty).to_string();
+            // strip the path and generic arguments, leaving the head of the
+            // type name, e.g. `crate :: function :: OptionalArg < T >` -> `OptionalArg`
+            let ty_head = ty
+                .split('<')
+                .next()
+                .unwrap()
+                .split_whitespace()
+                .filter(|seg| *seg != "::")
+                .last()
+                .unwrap_or_default()
+                .to_owned();
+            if ty_head == "VirtualMachine" {
+                return vec![];
+            }
+            let ident = match arg.pat.as_ref() {
+                syn::Pat::Ident(p) => p.ident.to_string(),
+                other => quote!(#other).to_string(),
+            };
+            if ident == "zelf" {
+                return vec![param("self", "PositionalOnly", false)];
+            }
+            match ty_head.as_str() {
+                "FuncArgs" => vec![
+                    param("args", "VarPositional", false),
+                    param("kwargs", "VarKeyword", false),
+                ],
+                "PosArgs" => vec![param(&ident, "VarPositional", false)],
+                "KwArgs" => vec![param(&ident, "VarKeyword", false)],
+                "OptionalArg" | "OptionalOption" => {
+                    vec![param(&ident, "PositionalOrKeyword", true)]
+                }
+                _ => vec![param(&ident, "PositionalOrKeyword", false)],
+            }
+        })
+        .collect::<Vec<_>>();
+    quote! { &[ #(#defs),* ] }
+}
@@ -3,3 +3,7 @@ assert abs(7) == 7
 assert abs(-3.21) == 3.21
 assert abs(6.25) == 6.25
 
+
+import builtins
+assert abs.__self__ is builtins
+assert abs.__reduce__() == 'abs'
@@ -70,6 +70,24 @@ def test_resizable():
 
 test_resizable()
 
+def test_resize_protection():
+    b = bytearray(b'123')
+    m = memoryview(b)
+    assert_raises(BufferError, lambda: b.__iadd__(b'45'))
+    assert_raises(BufferError, lambda: b.__init__(b'45'))
+    assert_raises(BufferError, lambda: b.__delitem__(0))
+    assert_raises(BufferError, b.clear)
+    assert_raises(BufferError, b.pop)
+    # in-place edits that keep the length are fine
+    m[0] = ord('a')
+    b[1] = ord('b')
+    m.release()
+    b += b'45'
+    b.__init__(b'reset')
+    assert b == b'reset'
+
+test_resize_protection()
+
 def test_delitem():
     a = b'abc'
     b = memoryview(a)
@@ -0,0 +1,25 @@
+def plain(a, b):
+    return a + b
+
+
+sm = staticmethod(plain)
+
+# Python 3.10: a raw staticmethod object is directly callable
+assert callable(sm)
+assert sm(1, 2) == 3
+
+# the wrapped function stays reachable
+assert sm.__func__ is plain
+assert sm.__wrapped__ is plain
+
+
+class C:
+    @staticmethod
+    def method(x):
+        return x * 2
+
+
+# calling through the class dict (unbound descriptor) works too
+assert C.__dict__['method'](3) == 6
+assert C.method(3) == 6
+assert C().method(3) == 6
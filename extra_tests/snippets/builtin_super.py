@@ -16,3 +16,41 @@ superB = super(testB)
 assert superB.__thisclass__ == testB
 assert superB.__self_class__ is None
 assert superB.__self__ is None
+
+from testutils import assert_raises
+
+
+# Zero-argument super() resolves __class__ and the first argument from the
+# calling frame, in instance methods and classmethods alike.
+class Base:
+    def who(self):
+        return 'Base'
+
+    @classmethod
+    def cwho(cls):
+        return 'Base'
+
+
+class Derived(Base):
+    def who(self):
+        return 'Derived/' + super().who()
+
+    @classmethod
+    def cwho(cls):
+        return 'Derived/' + super().cwho()
+
+    def closure_who(self):
+        # the implicit __class__ cell must survive being captured by a
+        # nested function as well
+        def inner(obj):
+            return super(__class__, obj).who()
+        return inner(self)
+
+
+d = Derived()
+assert d.who() == 'Derived/Base'
+assert Derived.cwho() == 'Derived/Base'
+assert d.closure_who() == 'Base'
+
+with assert_raises(RuntimeError):
+    super()
@@ -117,3 +117,7 @@ assert str(i.__class__.__name__) == "arrayiterator"
 a = array('B', [0])
 assert a.__contains__(0)
 assert not a.__contains__(1)
+
+# test array.typecodes
+import array as array_module
+assert array_module.typecodes == "bBuhHiIlLqQfd"
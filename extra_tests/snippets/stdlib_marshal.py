@@ -17,6 +17,8 @@ class MarshalTests(unittest.TestCase):
         self._test_marshal(-1)
         self._test_marshal(1)
         self._test_marshal(100000000)
+        self._test_marshal(2 ** 100)
+        self._test_marshal(-(2 ** 100))
 
     def test_marshal_bool(self):
         self._test_marshal(True)
@@ -65,6 +67,12 @@ class MarshalTests(unittest.TestCase):
             bytearray(b'\x01\x02'),
         )
 
+    def test_marshal_shared(self):
+        inner = (1, "hello")
+        loaded = self.dump_then_load([inner, inner])
+        self.assertEqual(loaded, [inner, inner])
+        self.assertIs(loaded[0], loaded[1])
+
     def test_roundtrip(self):
         orig = compile("1 + 1", "", 'eval')
 
@@ -92,3 +92,11 @@ if sys.platform.startswith("win"):
     # assert winver.major == winver.platform_version[0]
     # assert winver.minor == winver.platform_version[1]
     # assert winver.build == winver.platform_version[2]
+
+# sys.intern returns the canonical object for equal strings
+a = sys.intern('inter' + 'ned!')
+b = sys.intern('i' + 'nterned!')
+assert a is b
+
+# single-character ascii strings are shared
+assert 'spam'[0] is 'pasta'[2]
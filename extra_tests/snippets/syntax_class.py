@@ -163,6 +163,16 @@ assert T4.t1.__doc__ == "t1"
 cm = classmethod(lambda cls: cls)
 assert cm.__func__(int) is int
 
+# Python 3.9: classmethod chains through the wrapped object's __get__
+class WithClassProperty:
+    @classmethod
+    @property
+    def name(cls):
+        return cls.__name__
+
+assert WithClassProperty.name == "WithClassProperty"
+assert WithClassProperty().name == "WithClassProperty"
+
 assert str(super(int, 5)) == "<super: <class 'int'>, <int object>>"
 
 class T5(int):
@@ -294,4 +304,84 @@ class _(metaclass=meta): pass
 
 # check that non-callables don't fail on an absent prepare:
 with assert_raises(TypeError, _msg="'int' object not callable"):
-    class _(metaclass=20): pass
\ No newline at end of file
+    class _(metaclass=20): pass
+
+# __slots__ without '__dict__' suppresses the instance dict
+class Slotted:
+    __slots__ = ('x', 'y')
+
+s = Slotted()
+s.x = 1
+s.y = 2
+assert (s.x, s.y) == (1, 2)
+assert not hasattr(s, '__dict__')
+with assert_raises(AttributeError):
+    s.z = 3
+
+class SlottedWithDict:
+    __slots__ = ('x', '__dict__')
+
+d = SlottedWithDict()
+d.x = 1
+d.z = 3
+assert d.__dict__ == {'z': 3}
+
+# bound methods hash and compare by (identity of self, equality of func)
+class MethHolder:
+    def m(self):
+        return 1
+
+holder = MethHolder()
+assert holder.m == holder.m
+assert hash(holder.m) == hash(holder.m)
+assert holder.m != MethHolder().m
+
+# __set_name__ is called on every namespace entry when the class is built
+set_name_calls = []
+
+class NameAware:
+    def __set_name__(self, owner, name):
+        set_name_calls.append((owner, name))
+
+class Owner:
+    first = NameAware()
+    second = NameAware()
+
+assert set_name_calls == [(Owner, 'first'), (Owner, 'second')]
+
+class BadSetName:
+    def __set_name__(self, owner, name):
+        raise ValueError('nope')
+
+with assert_raises(RuntimeError):
+    class BadOwner:
+        attr = BadSetName()
+
+# __init_subclass__ receives the class statement's keyword arguments and is
+# implicitly wrapped as a classmethod
+class Registering:
+    registry = []
+
+    def __init_subclass__(cls, tag=None, **kwargs):
+        super().__init_subclass__(**kwargs)
+        cls.tag = tag
+        Registering.registry.append(cls)
+
+class Tagged(Registering, tag='spam'):
+    pass
+
+assert Tagged.tag == 'spam'
+assert Registering.registry == [Tagged]
+assert isinstance(vars(Registering)['__init_subclass__'], classmethod)
+
+# __class_getitem__ is implicitly a classmethod and native types support
+# subscription through the same hook
+class Subscriptable:
+    def __class_getitem__(cls, item):
+        return (cls, item)
+
+assert Subscriptable[int] == (Subscriptable, int)
+assert isinstance(vars(Subscriptable)['__class_getitem__'], classmethod)
+
+assert list[int].__origin__ is list
+assert dict[str, int].__args__ == (str, int)
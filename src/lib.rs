@@ -155,6 +155,12 @@ fn install_pip(_installer: &str, _scope: Scope, vm: &VirtualMachine) -> PyResult
 }
 
 fn run_rustpython(vm: &VirtualMachine, run_mode: RunMode, quiet: bool) -> PyResult<()> {
+    #[cfg(feature = "flame-it")]
+    {
+        if let Err(e) = flame_bridge::install() {
+            warn!("could not install tracing subscriber for profiling: {}", e);
+        }
+    }
     #[cfg(feature = "flame-it")]
     let main_guard = flame::start_guard("RustPython main");
 
@@ -206,6 +212,94 @@ fn run_rustpython(vm: &VirtualMachine, run_mode: RunMode, quiet: bool) -> PyResu
     Ok(())
 }
 
+/// Bridges the `tracing` spans emitted by the vm (frame runs, instruction
+/// batches, GC phases) into the `flame` call graph, so the html/text/speedscope
+/// profile outputs keep working. Other embedders can install their own
+/// subscriber instead to consume the same spans.
+#[cfg(feature = "flame-it")]
+mod flame_bridge {
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Mutex,
+        },
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    struct FlameSubscriber {
+        next_id: AtomicU64,
+        names: Mutex<HashMap<u64, String>>,
+    }
+
+    /// extracts the dynamic `name` field that `flame_guard!` spans carry
+    struct NameVisitor {
+        name: Option<String>,
+    }
+
+    impl Visit for NameVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "name" {
+                self.name = Some(format!("{value:?}"));
+            }
+        }
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "name" {
+                self.name = Some(value.to_owned());
+            }
+        }
+    }
+
+    impl Subscriber for FlameSubscriber {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &span::Attributes<'_>) -> span::Id {
+            let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            let mut visitor = NameVisitor { name: None };
+            attrs.record(&mut visitor);
+            let name = visitor
+                .name
+                .unwrap_or_else(|| attrs.metadata().name().to_owned());
+            self.names.lock().unwrap().insert(id, name);
+            span::Id::from_u64(id)
+        }
+
+        fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+        fn event(&self, _event: &Event<'_>) {}
+
+        fn enter(&self, span: &span::Id) {
+            if let Some(name) = self.names.lock().unwrap().get(&span.into_u64()).cloned() {
+                flame::start(name);
+            }
+        }
+
+        fn exit(&self, span: &span::Id) {
+            if let Some(name) = self.names.lock().unwrap().get(&span.into_u64()).cloned() {
+                flame::end(name);
+            }
+        }
+
+        fn try_close(&self, id: span::Id) -> bool {
+            self.names.lock().unwrap().remove(&id.into_u64());
+            true
+        }
+    }
+
+    pub(crate) fn install() -> Result<(), tracing::subscriber::SetGlobalDefaultError> {
+        tracing::subscriber::set_global_default(FlameSubscriber {
+            // span ids handed to tracing must be nonzero
+            next_id: AtomicU64::new(1),
+            names: Mutex::new(HashMap::new()),
+        })
+    }
+}
+
 #[cfg(feature = "flame-it")]
 fn write_profile(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
     use std::{fs, io};
@@ -34,6 +34,7 @@ mod array {
     use crate::{
         common::{
             atomic::{self, AtomicUsize},
+            borrow::{BorrowedValue, BorrowedValueMut},
             lock::{
                 PyMappedRwLockReadGuard, PyMappedRwLockWriteGuard, PyMutex, PyRwLock,
                 PyRwLockReadGuard, PyRwLockWriteGuard,
@@ -42,6 +43,7 @@ mod array {
         },
         vm::{
             atomic_func,
+            buffer::FormatSpec,
             builtins::{
                 PositionIterInternal, PyByteArray, PyBytes, PyBytesRef, PyDictRef, PyFloat, PyInt,
                 PyList, PyListRef, PyStr, PyStrRef, PyTupleRef, PyTypeRef,
@@ -52,7 +54,7 @@ mod array {
                 ArgBytesLike, ArgIntoFloat, ArgIterable, KwArgs, OptionalArg, PyComparisonValue,
             },
             protocol::{
-                BufferDescriptor, BufferMethods, BufferResizeGuard, PyBuffer, PyIterReturn,
+                BufferDescriptor, BufferInternal, BufferResizeGuard, PyBuffer, PyIterReturn,
                 PyMappingMethods, PySequenceMethods,
             },
             sequence::{OptionalRangeArgs, SequenceExt, SequenceMutExt},
@@ -71,6 +73,10 @@ mod array {
     use num_traits::ToPrimitive;
     use std::{cmp::Ordering, fmt, os::raw};
 
+    /// all the typecodes `array()` accepts, in the order CPython lists them
+    #[pyattr(name = "typecodes")]
+    const TYPECODES: &str = "bBuhHiIlLqQfd";
+
     macro_rules! def_array_enum {
         ($(($n:ident, $t:ty, $c:literal, $scode:literal)),*$(,)?) => {
             #[derive(Debug, Clone)]
@@ -1279,35 +1285,35 @@ mod array {
         fn as_buffer(zelf: &Py<Self>, _vm: &VirtualMachine) -> PyResult<PyBuffer> {
             let array = zelf.read();
             let buf = PyBuffer::new(
-                zelf.to_owned().into(),
+                zelf.to_owned(),
                 BufferDescriptor::format(
                     array.len() * array.itemsize(),
                     false,
                     array.itemsize(),
                     array.typecode_str().into(),
                 ),
-                &BUFFER_METHODS,
             );
             Ok(buf)
         }
     }
 
-    static BUFFER_METHODS: BufferMethods = BufferMethods {
-        obj_bytes: |buffer| buffer.obj_as::<PyArray>().get_bytes().into(),
-        obj_bytes_mut: |buffer| buffer.obj_as::<PyArray>().get_bytes_mut().into(),
-        release: |buffer| {
-            buffer
-                .obj_as::<PyArray>()
-                .exports
-                .fetch_sub(1, atomic::Ordering::Release);
-        },
-        retain: |buffer| {
-            buffer
-                .obj_as::<PyArray>()
-                .exports
-                .fetch_add(1, atomic::Ordering::Release);
-        },
-    };
+    impl BufferInternal for PyArray {
+        fn obj_bytes(&self) -> BorrowedValue<[u8]> {
+            self.get_bytes().into()
+        }
+
+        fn obj_bytes_mut(&self) -> BorrowedValueMut<[u8]> {
+            self.get_bytes_mut().into()
+        }
+
+        fn release(&self) {
+            self.exports.fetch_sub(1, atomic::Ordering::Release);
+        }
+
+        fn retain(&self) {
+            self.exports.fetch_add(1, atomic::Ordering::Release);
+        }
+    }
 
     impl AsMapping for PyArray {
         fn as_mapping() -> &'static PyMappingMethods {
@@ -1561,6 +1567,28 @@ mod array {
                 Self::Int64 { .. } | Self::Ieee754Double { .. } => 8,
             }
         }
+
+        /// the struct-module format equivalent of this machine format;
+        /// `None` for the unicode formats, which decode to str instead
+        fn struct_format(self) -> Option<String> {
+            let endian = |big_endian| if big_endian { '>' } else { '<' };
+            let (order, code) = match self {
+                Self::Int8 { signed } => ('<', if signed { 'b' } else { 'B' }),
+                Self::Int16 { signed, big_endian } => {
+                    (endian(big_endian), if signed { 'h' } else { 'H' })
+                }
+                Self::Int32 { signed, big_endian } => {
+                    (endian(big_endian), if signed { 'i' } else { 'I' })
+                }
+                Self::Int64 { signed, big_endian } => {
+                    (endian(big_endian), if signed { 'q' } else { 'Q' })
+                }
+                Self::Ieee754Float { big_endian } => (endian(big_endian), 'f'),
+                Self::Ieee754Double { big_endian } => (endian(big_endian), 'd'),
+                Self::Utf16 { .. } | Self::Utf32 { .. } => return None,
+            };
+            Some(format!("{order}{code}"))
+        }
     }
 
     fn check_array_type(typ: PyTypeRef, vm: &VirtualMachine) -> PyResult<PyTypeRef> {
@@ -1582,7 +1610,7 @@ mod array {
             .map_err(|_| vm.new_value_error("second argument must be a valid type code".into()))
     }
 
-    macro_rules! chunk_to_obj {
+    macro_rules! chunk_to_int {
         ($BYTE:ident, $TY:ty, $BIG_ENDIAN:ident) => {{
             let b = <[u8; ::std::mem::size_of::<$TY>()]>::try_from($BYTE).unwrap();
             if $BIG_ENDIAN {
@@ -1591,18 +1619,6 @@ mod array {
                 <$TY>::from_le_bytes(b)
             }
         }};
-        ($VM:ident, $BYTE:ident, $TY:ty, $BIG_ENDIAN:ident) => {
-            chunk_to_obj!($BYTE, $TY, $BIG_ENDIAN).to_pyobject($VM)
-        };
-        ($VM:ident, $BYTE:ident, $SIGNED_TY:ty, $UNSIGNED_TY:ty, $SIGNED:ident, $BIG_ENDIAN:ident) => {{
-            let b = <[u8; ::std::mem::size_of::<$SIGNED_TY>()]>::try_from($BYTE).unwrap();
-            match ($SIGNED, $BIG_ENDIAN) {
-                (false, false) => <$UNSIGNED_TY>::from_le_bytes(b).to_pyobject($VM),
-                (false, true) => <$UNSIGNED_TY>::from_be_bytes(b).to_pyobject($VM),
-                (true, false) => <$SIGNED_TY>::from_le_bytes(b).to_pyobject($VM),
-                (true, true) => <$SIGNED_TY>::from_be_bytes(b).to_pyobject($VM),
-            }
-        }};
     }
 
     #[pyfunction]
@@ -1626,25 +1642,8 @@ mod array {
         }
         let mut chunks = bytes.chunks(format.item_size());
         match format {
-            MachineFormatCode::Ieee754Float { big_endian } => {
-                chunks.try_for_each(|b| array.push(chunk_to_obj!(vm, b, f32, big_endian), vm))?
-            }
-            MachineFormatCode::Ieee754Double { big_endian } => {
-                chunks.try_for_each(|b| array.push(chunk_to_obj!(vm, b, f64, big_endian), vm))?
-            }
-            MachineFormatCode::Int8 { signed } => chunks
-                .try_for_each(|b| array.push(chunk_to_obj!(vm, b, i8, u8, signed, false), vm))?,
-            MachineFormatCode::Int16 { signed, big_endian } => chunks.try_for_each(|b| {
-                array.push(chunk_to_obj!(vm, b, i16, u16, signed, big_endian), vm)
-            })?,
-            MachineFormatCode::Int32 { signed, big_endian } => chunks.try_for_each(|b| {
-                array.push(chunk_to_obj!(vm, b, i32, u32, signed, big_endian), vm)
-            })?,
-            MachineFormatCode::Int64 { signed, big_endian } => chunks.try_for_each(|b| {
-                array.push(chunk_to_obj!(vm, b, i64, u64, signed, big_endian), vm)
-            })?,
             MachineFormatCode::Utf16 { big_endian } => {
-                let utf16: Vec<_> = chunks.map(|b| chunk_to_obj!(b, u16, big_endian)).collect();
+                let utf16: Vec<_> = chunks.map(|b| chunk_to_int!(b, u16, big_endian)).collect();
                 let s = String::from_utf16(&utf16).map_err(|_| {
                     vm.new_unicode_encode_error("items cannot decode as utf16".into())
                 })?;
@@ -1653,12 +1652,16 @@ mod array {
             }
             MachineFormatCode::Utf32 { big_endian } => {
                 let s: String = chunks
-                    .map(|b| chunk_to_obj!(b, u32, big_endian))
+                    .map(|b| chunk_to_int!(b, u32, big_endian))
                     .map(|ch| u32_to_char(ch).map_err(|msg| vm.new_value_error(msg)))
                     .try_collect()?;
                 let bytes = PyArray::_unicode_to_wchar_bytes(&s, array.itemsize());
                 array.frombytes_move(bytes);
             }
+            _ => {
+                let spec = FormatSpec::parse(format.struct_format().unwrap().as_bytes(), vm)?;
+                chunks.try_for_each(|b| array.push(spec.unpack_item(b, vm)?, vm))?;
+            }
         };
         PyArray::from(array).into_ref_with_type(vm, cls)
     }
@@ -0,0 +1,114 @@
+pub(crate) use _bufferpool::make_module;
+
+#[pymodule]
+mod _bufferpool {
+    use crate::common::lock::PyMutex;
+    use crate::vm::{
+        builtins::{PyByteArray, PyTypeRef},
+        function::OptionalArg,
+        types::Constructor,
+        AsObject, PyPayload, PyRef, PyResult, VirtualMachine,
+    };
+
+    const DEFAULT_MAX_POOLED: usize = 16;
+
+    /// A pool of fixed-size bytearray buffers, meant for transports that
+    /// repeatedly read into a scratch buffer (e.g. asyncio protocols with
+    /// `get_buffer`/`buffer_updated`), so a busy server doesn't have to
+    /// allocate and collect a fresh buffer for every receive call.
+    #[pyattr]
+    #[pyclass(name = "BufferPool")]
+    #[derive(Debug, PyPayload)]
+    struct BufferPool {
+        buffer_size: usize,
+        max_pooled: usize,
+        free: PyMutex<Vec<PyRef<PyByteArray>>>,
+    }
+
+    #[derive(FromArgs)]
+    struct BufferPoolNewArgs {
+        #[pyarg(any)]
+        buffer_size: usize,
+        #[pyarg(any, optional)]
+        max_pooled: OptionalArg<usize>,
+    }
+
+    impl Constructor for BufferPool {
+        type Args = BufferPoolNewArgs;
+
+        fn py_new(cls: PyTypeRef, args: Self::Args, vm: &VirtualMachine) -> PyResult {
+            if args.buffer_size == 0 {
+                return Err(vm.new_value_error("buffer_size must be positive".to_owned()));
+            }
+            BufferPool {
+                buffer_size: args.buffer_size,
+                max_pooled: args.max_pooled.unwrap_or(DEFAULT_MAX_POOLED),
+                free: PyMutex::default(),
+            }
+            .into_ref_with_type(vm, cls)
+            .map(Into::into)
+        }
+    }
+
+    #[pyclass(with(Constructor))]
+    impl BufferPool {
+        /// hand out a zero-filled bytearray of `buffer_size` bytes, reusing a
+        /// previously released buffer when one is available
+        #[pymethod]
+        fn acquire(&self, vm: &VirtualMachine) -> PyRef<PyByteArray> {
+            if let Some(buf) = self.free.lock().pop() {
+                // recycled buffers still hold whatever the previous user
+                // received into them; don't leak that to the next one
+                buf.borrow_buf_mut().fill(0);
+                return buf;
+            }
+            PyByteArray::new_ref(vec![0u8; self.buffer_size], &vm.ctx)
+        }
+
+        /// return a buffer to the pool so a later `acquire` can reuse it;
+        /// once the pool already holds `max_pooled` buffers the buffer is
+        /// simply dropped. The caller must not keep using the buffer (or
+        /// memoryviews of it) after releasing it.
+        #[pymethod]
+        fn release(&self, buf: PyRef<PyByteArray>, vm: &VirtualMachine) -> PyResult<()> {
+            if buf.borrow_buf().len() != self.buffer_size {
+                return Err(vm.new_value_error(format!(
+                    "cannot release buffer of size {} to a pool of buffer_size {}",
+                    buf.borrow_buf().len(),
+                    self.buffer_size
+                )));
+            }
+            let mut free = self.free.lock();
+            // a double release would hand the same buffer to two acquirers
+            if free.iter().any(|pooled| pooled.is(&buf)) {
+                return Err(vm.new_value_error("buffer is already in the pool".to_owned()));
+            }
+            if free.len() < self.max_pooled {
+                free.push(buf);
+            }
+            Ok(())
+        }
+
+        /// drop all pooled buffers
+        #[pymethod]
+        fn clear(&self) {
+            self.free.lock().clear();
+        }
+
+        #[pygetset]
+        fn buffer_size(&self) -> usize {
+            self.buffer_size
+        }
+
+        #[pygetset]
+        fn max_pooled(&self) -> usize {
+            self.max_pooled
+        }
+
+        /// number of buffers currently waiting in the pool
+        #[pygetset]
+        fn pooled(&self) -> usize {
+            self.free.lock().len()
+        }
+    }
+}
@@ -1,203 +1,633 @@
 pub(crate) use _contextvars::make_module;
 
+/// A persistent (immutable) hash-array-mapped-trie, the backing store of a
+/// `contextvars.Context`. Updates clone only the path from the root to the
+/// touched leaf and share every other node, so copying a whole context is a
+/// single reference-count increment.
+///
+/// Keys are identified purely by a caller-supplied 64-bit hash. The module
+/// derives those hashes injectively from a creation counter, so two distinct
+/// keys can never collide and the trie needs no collision nodes.
+mod hamt {
+    use crate::common::rc::PyRc;
+    use crate::vm::PyObjectRef;
+
+    #[cfg(feature = "gc_bacon")]
+    use crate::vm::object::gc::{Trace, TracerFn};
+
+    /// bits of the hash consumed per trie level
+    const BITS: u32 = 5;
+    const MASK: u64 = (1 << BITS) - 1;
+
+    #[derive(Debug, Clone)]
+    pub(super) struct Hamt {
+        root: PyRc<Node>,
+        len: usize,
+    }
+
+    #[derive(Debug)]
+    struct Node {
+        bitmap: u32,
+        entries: Vec<Entry>,
+    }
+
+    #[derive(Debug, Clone)]
+    enum Entry {
+        Leaf {
+            hash: u64,
+            key: PyObjectRef,
+            value: PyObjectRef,
+        },
+        Node(PyRc<Node>),
+    }
+
+    impl Hamt {
+        pub fn new() -> Self {
+            Self {
+                root: PyRc::new(Node {
+                    bitmap: 0,
+                    entries: vec![],
+                }),
+                len: 0,
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        pub fn get(&self, hash: u64) -> Option<&PyObjectRef> {
+            let mut node = &*self.root;
+            let mut shift = 0;
+            loop {
+                let bit = 1u32 << ((hash >> shift) & MASK);
+                if node.bitmap & bit == 0 {
+                    return None;
+                }
+                let idx = (node.bitmap & (bit - 1)).count_ones() as usize;
+                match &node.entries[idx] {
+                    Entry::Leaf { hash: h, value, .. } => return (*h == hash).then_some(value),
+                    Entry::Node(child) => {
+                        node = child;
+                        shift += BITS;
+                    }
+                }
+            }
+        }
+
+        /// return a new trie with `key` bound to `value`, sharing everything
+        /// off the updated path with `self`
+        pub fn set(&self, hash: u64, key: PyObjectRef, value: PyObjectRef) -> Self {
+            let (root, added) = Self::node_set(&self.root, 0, hash, key, value);
+            Self {
+                root: PyRc::new(root),
+                len: self.len + added as usize,
+            }
+        }
+
+        fn node_set(
+            node: &Node,
+            shift: u32,
+            hash: u64,
+            key: PyObjectRef,
+            value: PyObjectRef,
+        ) -> (Node, bool) {
+            debug_assert!(shift <= 60);
+            let bit = 1u32 << ((hash >> shift) & MASK);
+            let idx = (node.bitmap & (bit - 1)).count_ones() as usize;
+            let mut entries = node.entries.clone();
+            if node.bitmap & bit == 0 {
+                entries.insert(idx, Entry::Leaf { hash, key, value });
+                (
+                    Node {
+                        bitmap: node.bitmap | bit,
+                        entries,
+                    },
+                    true,
+                )
+            } else {
+                let (entry, added) = match &node.entries[idx] {
+                    Entry::Leaf {
+                        hash: h,
+                        key: k,
+                        value: v,
+                    } => {
+                        if *h == hash {
+                            (Entry::Leaf { hash, key, value }, false)
+                        } else {
+                            // both keys land in the same slot here, push them
+                            // one level down where their hashes diverge
+                            let child = Self::two_leaves(
+                                shift + BITS,
+                                (*h, k.clone(), v.clone()),
+                                (hash, key, value),
+                            );
+                            (Entry::Node(PyRc::new(child)), true)
+                        }
+                    }
+                    Entry::Node(child) => {
+                        let (child, added) = Self::node_set(child, shift + BITS, hash, key, value);
+                        (Entry::Node(PyRc::new(child)), added)
+                    }
+                };
+                entries[idx] = entry;
+                (
+                    Node {
+                        bitmap: node.bitmap,
+                        entries,
+                    },
+                    added,
+                )
+            }
+        }
+
+        fn two_leaves(
+            shift: u32,
+            a: (u64, PyObjectRef, PyObjectRef),
+            b: (u64, PyObjectRef, PyObjectRef),
+        ) -> Node {
+            debug_assert!(shift <= 60);
+            let a_bit = 1u32 << ((a.0 >> shift) & MASK);
+            let b_bit = 1u32 << ((b.0 >> shift) & MASK);
+            let leaf = |(hash, key, value)| Entry::Leaf { hash, key, value };
+            if a_bit == b_bit {
+                Node {
+                    bitmap: a_bit,
+                    entries: vec![Entry::Node(PyRc::new(Self::two_leaves(shift + BITS, a, b)))],
+                }
+            } else {
+                let entries = if a_bit < b_bit {
+                    vec![leaf(a), leaf(b)]
+                } else {
+                    vec![leaf(b), leaf(a)]
+                };
+                Node {
+                    bitmap: a_bit | b_bit,
+                    entries,
+                }
+            }
+        }
+
+        /// return a new trie without `key`, or `None` when it wasn't bound
+        pub fn remove(&self, hash: u64) -> Option<Self> {
+            let root = Self::node_remove(&self.root, 0, hash)?;
+            Some(Self {
+                root: PyRc::new(root),
+                len: self.len - 1,
+            })
+        }
+
+        fn node_remove(node: &Node, shift: u32, hash: u64) -> Option<Node> {
+            let bit = 1u32 << ((hash >> shift) & MASK);
+            if node.bitmap & bit == 0 {
+                return None;
+            }
+            let idx = (node.bitmap & (bit - 1)).count_ones() as usize;
+            let mut bitmap = node.bitmap;
+            let mut entries = node.entries.clone();
+            match &node.entries[idx] {
+                Entry::Leaf { hash: h, .. } => {
+                    if *h != hash {
+                        return None;
+                    }
+                    entries.remove(idx);
+                    bitmap &= !bit;
+                }
+                Entry::Node(child) => {
+                    let child = Self::node_remove(child, shift + BITS, hash)?;
+                    if child.entries.is_empty() {
+                        entries.remove(idx);
+                        bitmap &= !bit;
+                    } else {
+                        entries[idx] = Entry::Node(PyRc::new(child));
+                    }
+                }
+            }
+            Some(Node { bitmap, entries })
+        }
+
+        pub fn for_each(&self, f: &mut impl FnMut(&PyObjectRef, &PyObjectRef)) {
+            Self::node_for_each(&self.root, f)
+        }
+
+        fn node_for_each(node: &Node, f: &mut impl FnMut(&PyObjectRef, &PyObjectRef)) {
+            for entry in &node.entries {
+                match entry {
+                    Entry::Leaf { key, value, .. } => f(key, value),
+                    Entry::Node(child) => Self::node_for_each(child, f),
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "gc_bacon")]
+    unsafe impl Trace for Hamt {
+        fn trace(&self, tracer_fn: &mut TracerFn) {
+            // a node shared with another context is owned by more strong
+            // references than this trie holds; skipping it only under-counts,
+            // which the collector treats as "keep", never as "free"
+            if PyRc::strong_count(&self.root) == 1 {
+                self.root.trace(tracer_fn);
+            }
+        }
+    }
+
+    #[cfg(feature = "gc_bacon")]
+    unsafe impl Trace for Node {
+        fn trace(&self, tracer_fn: &mut TracerFn) {
+            for entry in &self.entries {
+                match entry {
+                    Entry::Leaf { key, value, .. } => {
+                        key.trace(tracer_fn);
+                        value.trace(tracer_fn);
+                    }
+                    Entry::Node(child) => {
+                        if PyRc::strong_count(child) == 1 {
+                            child.trace(tracer_fn);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[pymodule]
 mod _contextvars {
+    use super::hamt::Hamt;
+    use crate::common::lock::PyMutex;
+    #[cfg(feature = "gc_bacon")]
+    use crate::vm::object::gc::{Trace, TracerFn};
     use crate::vm::{
-        builtins::{PyFunction, PyStrRef, PyTypeRef},
-        function::{ArgCallable, FuncArgs, OptionalArg},
-        types::Initializer,
-        PyObjectRef, PyPayload, PyRef, PyResult, VirtualMachine,
+        builtins::{PyGenericAlias, PyStrRef, PyTypeRef},
+        function::{FuncArgs, OptionalArg},
+        protocol::PyIter,
+        types::Constructor,
+        AsObject, Context, Py, PyObjectRef, PyPayload, PyRef, PyResult, VirtualMachine,
+    };
+    use std::{
+        cell::RefCell,
+        sync::atomic::{AtomicBool, AtomicU64, Ordering},
     };
 
+    // each thread runs in its own context chain; entering a context (or
+    // setting a var with none entered yet) swaps the thread's current one
+    thread_local! {
+        static CURRENT_CONTEXT: RefCell<Option<PyRef<PyContext>>> = RefCell::default();
+    }
+
+    fn current_context(vm: &VirtualMachine) -> PyRef<PyContext> {
+        CURRENT_CONTEXT.with(|ctx| {
+            ctx.borrow_mut()
+                .get_or_insert_with(|| PyContext::empty().into_ref(&vm.ctx))
+                .clone()
+        })
+    }
+
+    #[pyfunction]
+    fn copy_context(vm: &VirtualMachine) -> PyContext {
+        current_context(vm).copy()
+    }
+
     #[pyattr]
-    #[pyclass(name = "Context")]
+    #[pyclass(name = "Context", trace)]
     #[derive(Debug, PyPayload)]
-    struct PyContext {} // not to confuse with vm::Context
+    struct PyContext {
+        // not to confuse with vm::Context
+        vars: PyMutex<Hamt>,
+        entered: AtomicBool,
+    }
+
+    #[cfg(feature = "gc_bacon")]
+    unsafe impl Trace for PyContext {
+        fn trace(&self, tracer_fn: &mut TracerFn) {
+            self.vars.trace(tracer_fn);
+        }
+    }
 
-    #[pyclass(with(Initializer))]
+    impl PyContext {
+        fn empty() -> Self {
+            Self::from_hamt(Hamt::new())
+        }
+
+        fn from_hamt(vars: Hamt) -> Self {
+            Self {
+                vars: PyMutex::new(vars),
+                entered: AtomicBool::new(false),
+            }
+        }
+
+        fn keys_vec(&self) -> Vec<PyObjectRef> {
+            let mut keys = vec![];
+            self.vars
+                .lock()
+                .for_each(&mut |key, _| keys.push(key.clone()));
+            keys
+        }
+    }
+
+    impl Constructor for PyContext {
+        type Args = ();
+
+        fn py_new(cls: PyTypeRef, _args: Self::Args, vm: &VirtualMachine) -> PyResult {
+            PyContext::empty()
+                .into_ref_with_type(vm, cls)
+                .map(Into::into)
+        }
+    }
+
+    #[pyclass(with(Constructor))]
     impl PyContext {
         #[pymethod]
         fn run(
-            &self,
-            _callable: ArgCallable,
-            _args: FuncArgs,
-            _vm: &VirtualMachine,
-        ) -> PyResult<PyFunction> {
-            unimplemented!("Context.run is currently under construction")
+            zelf: PyRef<Self>,
+            callable: PyObjectRef,
+            args: FuncArgs,
+            vm: &VirtualMachine,
+        ) -> PyResult {
+            if zelf.entered.swap(true, Ordering::Acquire) {
+                return Err(vm.new_runtime_error(format!(
+                    "cannot enter context: {} is already entered",
+                    zelf.as_object().repr(vm)?
+                )));
+            }
+            let prev = CURRENT_CONTEXT.with(|ctx| ctx.borrow_mut().replace(zelf.clone()));
+            let result = callable.call(args, vm);
+            CURRENT_CONTEXT.with(|ctx| *ctx.borrow_mut() = prev);
+            zelf.entered.store(false, Ordering::Release);
+            result
         }
 
         #[pymethod]
-        fn copy(&self, _vm: &VirtualMachine) -> PyResult<Self> {
-            unimplemented!("Context.copy is currently under construction")
+        fn copy(&self) -> Self {
+            Self::from_hamt(self.vars.lock().clone())
         }
 
         #[pymethod(magic)]
-        fn getitem(&self, _var: PyObjectRef) -> PyResult<PyObjectRef> {
-            unimplemented!("Context.__getitem__ is currently under construction")
+        fn getitem(&self, var: PyRef<ContextVar>, vm: &VirtualMachine) -> PyResult<PyObjectRef> {
+            self.vars.lock().get(var.hash).cloned().ok_or_else(|| {
+                vm.new_exception(vm.ctx.exceptions.key_error.to_owned(), vec![var.into()])
+            })
         }
 
         #[pymethod(magic)]
-        fn contains(&self, _var: PyObjectRef) -> PyResult<bool> {
-            unimplemented!("Context.__contains__ is currently under construction")
+        fn contains(&self, var: PyRef<ContextVar>) -> bool {
+            self.vars.lock().get(var.hash).is_some()
         }
 
         #[pymethod(magic)]
         fn len(&self) -> usize {
-            unimplemented!("Context.__len__ is currently under construction")
+            self.vars.lock().len()
         }
 
         #[pymethod(magic)]
-        fn iter(&self) -> PyResult {
-            unimplemented!("Context.__iter__ is currently under construction")
+        fn iter(&self, vm: &VirtualMachine) -> PyResult<PyIter> {
+            let keys: PyObjectRef = vm.ctx.new_list(self.keys_vec()).into();
+            keys.get_iter(vm)
         }
 
         #[pymethod]
         fn get(
             &self,
-            _key: PyObjectRef,
-            _default: OptionalArg<PyObjectRef>,
+            key: PyRef<ContextVar>,
+            default: OptionalArg<PyObjectRef>,
+            vm: &VirtualMachine,
         ) -> PyResult<PyObjectRef> {
-            unimplemented!("Context.get is currently under construction")
+            Ok(self
+                .vars
+                .lock()
+                .get(key.hash)
+                .cloned()
+                .or_else(|| default.into_option())
+                .unwrap_or_else(|| vm.ctx.none()))
         }
 
         #[pymethod]
-        fn keys(_zelf: PyRef<Self>, _vm: &VirtualMachine) -> Vec<PyObjectRef> {
-            unimplemented!("Context.keys is currently under construction")
+        fn keys(&self) -> Vec<PyObjectRef> {
+            self.keys_vec()
         }
 
         #[pymethod]
-        fn values(_zelf: PyRef<Self>, _vm: &VirtualMachine) -> Vec<PyObjectRef> {
-            unimplemented!("Context.values is currently under construction")
+        fn values(&self) -> Vec<PyObjectRef> {
+            let mut values = vec![];
+            self.vars
+                .lock()
+                .for_each(&mut |_, value| values.push(value.clone()));
+            values
         }
     }
 
-    impl Initializer for PyContext {
-        type Args = FuncArgs;
+    /// hashes are handed out from a counter through an invertible mixer, so
+    /// every var gets a unique, well-spread 64-bit hash
+    static NEXT_HASH: AtomicU64 = AtomicU64::new(0);
 
-        fn init(_obj: PyRef<Self>, _args: Self::Args, _vm: &VirtualMachine) -> PyResult<()> {
-            unimplemented!("Context.__init__ is currently under construction")
-        }
+    fn splitmix64(mut x: u64) -> u64 {
+        x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        x ^ (x >> 31)
     }
 
     #[pyattr]
-    #[pyclass(name)]
+    #[pyclass(name, trace)]
     #[derive(Debug, PyPayload)]
     struct ContextVar {
-        #[allow(dead_code)] // TODO: RUSTPYTHON
-        name: String,
-        #[allow(dead_code)] // TODO: RUSTPYTHON
+        name: PyStrRef,
         default: Option<PyObjectRef>,
+        hash: u64,
+    }
+
+    #[cfg(feature = "gc_bacon")]
+    unsafe impl Trace for ContextVar {
+        fn trace(&self, tracer_fn: &mut TracerFn) {
+            self.default.trace(tracer_fn);
+        }
     }
 
     #[derive(FromArgs)]
     struct ContextVarOptions {
         #[pyarg(positional)]
-        #[allow(dead_code)] // TODO: RUSTPYTHON
         name: PyStrRef,
         #[pyarg(any, optional)]
-        #[allow(dead_code)] // TODO: RUSTPYTHON
         default: OptionalArg<PyObjectRef>,
     }
 
-    #[pyclass(with(Initializer))]
+    impl Constructor for ContextVar {
+        type Args = ContextVarOptions;
+
+        fn py_new(cls: PyTypeRef, args: Self::Args, vm: &VirtualMachine) -> PyResult {
+            ContextVar {
+                name: args.name,
+                default: args.default.into_option(),
+                hash: splitmix64(NEXT_HASH.fetch_add(1, Ordering::Relaxed)),
+            }
+            .into_ref_with_type(vm, cls)
+            .map(Into::into)
+        }
+    }
+
+    #[pyclass(with(Constructor))]
     impl ContextVar {
         #[pygetset]
-        fn name(&self) -> String {
+        fn name(&self) -> PyStrRef {
             self.name.clone()
         }
 
         #[pymethod]
         fn get(
-            &self,
-            _default: OptionalArg<PyObjectRef>,
-            _vm: &VirtualMachine,
+            zelf: PyRef<Self>,
+            default: OptionalArg<PyObjectRef>,
+            vm: &VirtualMachine,
         ) -> PyResult<PyObjectRef> {
-            unimplemented!("ContextVar.get() is currently under construction")
+            let ctx = current_context(vm);
+            let value = ctx.vars.lock().get(zelf.hash).cloned();
+            if let Some(value) = value {
+                return Ok(value);
+            }
+            if let OptionalArg::Present(default) = default {
+                return Ok(default);
+            }
+            if let Some(default) = &zelf.default {
+                return Ok(default.clone());
+            }
+            Err(vm.new_exception(vm.ctx.exceptions.lookup_error.to_owned(), vec![zelf.into()]))
         }
 
         #[pymethod]
-        fn set(&self, _value: PyObjectRef, _vm: &VirtualMachine) -> PyResult<()> {
-            unimplemented!("ContextVar.set() is currently under construction")
+        fn set(
+            zelf: PyRef<Self>,
+            value: PyObjectRef,
+            vm: &VirtualMachine,
+        ) -> PyResult<ContextToken> {
+            let ctx = current_context(vm);
+            let old_value = {
+                let mut vars = ctx.vars.lock();
+                let old = vars.get(zelf.hash).cloned();
+                *vars = vars.set(zelf.hash, zelf.as_object().to_owned(), value);
+                old
+            };
+            Ok(ContextToken {
+                ctx,
+                var: zelf,
+                old_value,
+                used: AtomicBool::new(false),
+            })
         }
 
         #[pymethod]
         fn reset(
-            _zelf: PyRef<Self>,
-            _token: PyRef<ContextToken>,
-            _vm: &VirtualMachine,
+            zelf: PyRef<Self>,
+            token: PyRef<ContextToken>,
+            vm: &VirtualMachine,
         ) -> PyResult<()> {
-            unimplemented!("ContextVar.reset() is currently under construction")
+            if token.used.load(Ordering::Acquire) {
+                return Err(vm.new_runtime_error(format!(
+                    "{} has already been used once",
+                    token.as_object().repr(vm)?
+                )));
+            }
+            if !token.var.is(&zelf) {
+                return Err(vm.new_value_error(format!(
+                    "{} was created by a different ContextVar",
+                    token.as_object().repr(vm)?
+                )));
+            }
+            let ctx = current_context(vm);
+            if !token.ctx.is(&ctx) {
+                return Err(vm.new_value_error(format!(
+                    "{} was created in a different Context",
+                    token.as_object().repr(vm)?
+                )));
+            }
+
+            let mut vars = ctx.vars.lock();
+            match &token.old_value {
+                Some(old) => *vars = vars.set(zelf.hash, zelf.as_object().to_owned(), old.clone()),
+                None => {
+                    if let Some(without) = vars.remove(zelf.hash) {
+                        *vars = without;
+                    }
+                }
+            }
+            drop(vars);
+            token.used.store(true, Ordering::Release);
+            Ok(())
         }
 
         #[pyclassmethod(magic)]
-        fn class_getitem(_cls: PyTypeRef, _key: PyStrRef, _vm: &VirtualMachine) -> PyResult<()> {
-            unimplemented!("ContextVar.__class_getitem__() is currently under construction")
+        fn class_getitem(cls: PyTypeRef, args: PyObjectRef, vm: &VirtualMachine) -> PyGenericAlias {
+            PyGenericAlias::new(cls, args, vm)
         }
 
         #[pymethod(magic)]
-        fn repr(_zelf: PyRef<Self>, _vm: &VirtualMachine) -> String {
-            unimplemented!("<ContextVar name={{}} default={{}} at {{}}")
-            // format!(
-            //     "<ContextVar name={} default={:?} at {:#x}>",
-            //     zelf.name.as_str(),
-            //     zelf.default.map_or("", |x| PyStr::from(*x).as_str()),
-            //     zelf.get_id()
-            // )
-        }
-    }
-
-    impl Initializer for ContextVar {
-        type Args = ContextVarOptions;
-
-        fn init(_obj: PyRef<Self>, _args: Self::Args, _vm: &VirtualMachine) -> PyResult<()> {
-            unimplemented!("ContextVar.__init__() is currently under construction")
+        fn repr(zelf: PyRef<Self>, vm: &VirtualMachine) -> PyResult<String> {
+            let default = match &zelf.default {
+                Some(default) => format!(" default={}", default.repr(vm)?),
+                None => String::new(),
+            };
+            Ok(format!(
+                "<ContextVar name={}{} at {:#x}>",
+                zelf.name.as_object().repr(vm)?,
+                default,
+                zelf.get_id()
+            ))
         }
     }
 
     #[pyattr]
-    #[pyclass(name = "Token")]
+    #[pyclass(name = "Token", trace)]
     #[derive(Debug, PyPayload)]
-    struct ContextToken {}
+    struct ContextToken {
+        ctx: PyRef<PyContext>,
+        var: PyRef<ContextVar>,
+        /// the value the var had when the token was handed out, `None` when
+        /// it wasn't bound at all (surfaced to python as `Token.MISSING`)
+        old_value: Option<PyObjectRef>,
+        used: AtomicBool,
+    }
 
-    #[derive(FromArgs)]
-    struct ContextTokenOptions {
-        #[pyarg(positional)]
-        #[allow(dead_code)] // TODO: RUSTPYTHON
-        context: PyObjectRef,
-        #[pyarg(positional)]
-        #[allow(dead_code)] // TODO: RUSTPYTHON
-        var: PyObjectRef,
-        #[pyarg(positional)]
-        #[allow(dead_code)] // TODO: RUSTPYTHON
-        old_value: PyObjectRef,
+    #[cfg(feature = "gc_bacon")]
+    unsafe impl Trace for ContextToken {
+        fn trace(&self, tracer_fn: &mut TracerFn) {
+            self.ctx.trace(tracer_fn);
+            self.var.trace(tracer_fn);
+            self.old_value.trace(tracer_fn);
+        }
     }
 
-    #[pyclass(with(Initializer))]
+    // no Constructor: tokens are only created by ContextVar.set()
+    #[pyclass]
     impl ContextToken {
-        #[pygetset]
-        fn var(&self, _vm: &VirtualMachine) -> PyObjectRef {
-            unimplemented!("Token.var() is currently under construction")
+        #[pyattr(name = "MISSING")]
+        fn missing(ctx: &Context) -> PyObjectRef {
+            ctx.new_base_object(ctx.types.object_type.to_owned(), None)
         }
 
         #[pygetset]
-        fn old_value(&self, _vm: &VirtualMachine) -> PyObjectRef {
-            unimplemented!("Token.old_value() is currently under construction")
+        fn var(&self) -> PyRef<ContextVar> {
+            self.var.clone()
         }
 
-        #[pymethod(magic)]
-        fn repr(_zelf: PyRef<Self>, _vm: &VirtualMachine) -> String {
-            unimplemented!("<Token {{}}var={{}} at {{}}>")
+        #[pygetset]
+        fn old_value(zelf: &Py<Self>, vm: &VirtualMachine) -> PyResult<PyObjectRef> {
+            match &zelf.old_value {
+                Some(value) => Ok(value.clone()),
+                None => zelf.class().as_object().get_attr("MISSING", vm),
+            }
         }
-    }
-
-    impl Initializer for ContextToken {
-        type Args = ContextTokenOptions;
 
-        fn init(_obj: PyRef<Self>, _args: Self::Args, _vm: &VirtualMachine) -> PyResult<()> {
-            unimplemented!("Token.__init__() is currently under construction")
+        #[pymethod(magic)]
+        fn repr(zelf: PyRef<Self>, vm: &VirtualMachine) -> PyResult<String> {
+            Ok(format!(
+                "<Token {}var={} at {:#x}>",
+                if zelf.used.load(Ordering::Acquire) {
+                    "used "
+                } else {
+                    ""
+                },
+                zelf.var.as_object().repr(vm)?,
+                zelf.get_id()
+            ))
         }
     }
-
-    #[pyfunction]
-    fn copy_context() {}
 }
@@ -3,6 +3,9 @@ pub(crate) use decl::make_module;
 #[pymodule(name = "faulthandler")]
 mod decl {
     use crate::vm::{frame::Frame, function::OptionalArg, stdlib::sys::PyStderr, VirtualMachine};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static ENABLED: AtomicBool = AtomicBool::new(false);
 
     fn dump_frame(frame: &Frame, vm: &VirtualMachine) {
         let stderr = PyStderr(vm);
@@ -15,6 +18,21 @@ mod decl {
         )
     }
 
+    /// Like `dump_frame`, but straight to the process stderr: for contexts
+    /// that must not run python code, like the crash handler or the watchdog
+    /// thread.
+    fn dump_frame_to_stderr(frame: &Frame) {
+        let lineno = if frame.lasti() > 0 {
+            frame.current_location().row()
+        } else {
+            0
+        };
+        eprintln!(
+            "  File \"{}\", line {} in {}",
+            frame.code.source_path, lineno, frame.code.obj_name
+        );
+    }
+
     #[pyfunction]
     fn dump_traceback(
         _file: OptionalArg<i64>,
@@ -29,6 +47,90 @@ mod decl {
         }
     }
 
+    /// Emit `bytes` with a raw `write(2)` to the process stderr. The only
+    /// output primitive the crash handler may use: `eprintln!` takes std's
+    /// stderr lock and runs the formatting machinery, neither of which is
+    /// async-signal-safe.
+    #[cfg(unix)]
+    fn write_raw(bytes: &[u8]) {
+        unsafe {
+            libc::write(2, bytes.as_ptr() as *const libc::c_void, bytes.len());
+        }
+    }
+
+    /// `n` in decimal via [`write_raw`], formatted on the stack: `format!`
+    /// would allocate, and malloc is not async-signal-safe either.
+    #[cfg(unix)]
+    fn write_raw_number(mut n: usize) {
+        let mut buf = [0u8; 20];
+        let mut i = buf.len();
+        loop {
+            i -= 1;
+            buf[i] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        write_raw(&buf[i..]);
+    }
+
+    /// The crash-handler counterpart of `dump_frame_to_stderr`, built out of
+    /// raw writes only.
+    #[cfg(unix)]
+    fn dump_frame_raw(frame: &Frame) {
+        let lineno = if frame.lasti() > 0 {
+            frame.current_location().row()
+        } else {
+            0
+        };
+        write_raw(b"  File \"");
+        write_raw(frame.code.source_path.as_str().as_bytes());
+        write_raw(b"\", line ");
+        write_raw_number(lineno);
+        write_raw(b" in ");
+        write_raw(frame.code.obj_name.as_str().as_bytes());
+        write_raw(b"\n");
+    }
+
+    /// Dump the frame stack of every vm entered on the faulting thread, then
+    /// re-raise the signal with its default disposition so the process still
+    /// dies the way it would have without the handler. Everything here sticks
+    /// to static strings and raw `write(2)` calls.
+    #[cfg(unix)]
+    extern "C" fn crash_handler(signum: libc::c_int) {
+        let msg: &[u8] = match signum {
+            libc::SIGSEGV => b"Fatal Python error: Segmentation fault\n",
+            libc::SIGABRT => b"Fatal Python error: Aborted\n",
+            _ => b"Fatal Python error: Fatal signal\n",
+        };
+        write_raw(msg);
+        write_raw(b"Stack (most recent call first):\n");
+        crate::vm::vm::thread::for_each_vm_on_thread(|vm| {
+            // the fault may have struck while the frame stack was being
+            // updated; a dump that skips it is better than a second crash
+            if let Ok(frames) = vm.frames.try_borrow() {
+                for frame in frames.iter() {
+                    dump_frame_raw(frame);
+                }
+            }
+        });
+        unsafe {
+            install_handler(signum, libc::SIG_DFL);
+            libc::raise(signum);
+        }
+    }
+
+    /// Install `handler` for `signum` with `sigaction`, whose semantics are
+    /// defined across platforms where `signal`'s are not.
+    #[cfg(unix)]
+    unsafe fn install_handler(signum: libc::c_int, handler: libc::sighandler_t) {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = handler;
+        libc::sigemptyset(&mut action.sa_mask);
+        libc::sigaction(signum, &action, std::ptr::null_mut());
+    }
+
     #[derive(FromArgs)]
     #[allow(unused)]
     struct EnableArgs {
@@ -40,7 +142,100 @@ mod decl {
 
     #[pyfunction]
     fn enable(_args: EnableArgs) {
-        // TODO
+        if ENABLED.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        #[cfg(unix)]
+        unsafe {
+            install_handler(libc::SIGSEGV, crash_handler as libc::sighandler_t);
+            install_handler(libc::SIGABRT, crash_handler as libc::sighandler_t);
+        }
+    }
+
+    #[pyfunction]
+    fn disable() {
+        if ENABLED.swap(false, Ordering::AcqRel) {
+            #[cfg(unix)]
+            unsafe {
+                install_handler(libc::SIGSEGV, libc::SIG_DFL);
+                install_handler(libc::SIGABRT, libc::SIG_DFL);
+            }
+        }
+    }
+
+    #[pyfunction]
+    fn is_enabled() -> bool {
+        ENABLED.load(Ordering::Acquire)
+    }
+
+    #[cfg(feature = "threading")]
+    static WATCHDOG: std::sync::Mutex<Option<std::sync::mpsc::Sender<()>>> =
+        std::sync::Mutex::new(None);
+
+    #[cfg(feature = "threading")]
+    #[derive(FromArgs)]
+    struct DumpTracebackLaterArgs {
+        #[pyarg(positional)]
+        timeout: f64,
+        #[pyarg(any, default = "false")]
+        repeat: bool,
+        #[pyarg(any, default)]
+        #[allow(unused)]
+        file: Option<i64>,
+        #[pyarg(any, default = "false")]
+        exit: bool,
+    }
+
+    /// Arm a watchdog thread that dumps the calling vm's frame stack to
+    /// stderr after `timeout` seconds. The stack is pinned when the watchdog
+    /// is armed: frames still running show the line they are currently at,
+    /// frames that returned in the meantime show where they ended.
+    #[cfg(feature = "threading")]
+    #[pyfunction]
+    fn dump_traceback_later(
+        args: DumpTracebackLaterArgs,
+        vm: &VirtualMachine,
+    ) -> crate::vm::PyResult<()> {
+        use std::sync::mpsc::RecvTimeoutError;
+
+        if !args.timeout.is_finite() || args.timeout <= 0.0 {
+            return Err(vm.new_value_error("timeout must be greater than 0".to_owned()));
+        }
+        let timeout = std::time::Duration::from_secs_f64(args.timeout);
+        let frames: Vec<crate::vm::frame::FrameRef> = vm.frames.borrow().clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+        // dropping any previous sender cancels its watchdog
+        *WATCHDOG.lock().unwrap() = Some(tx);
+        let (repeat, exit) = (args.repeat, args.exit);
+        std::thread::Builder::new()
+            .name("faulthandler watchdog".to_owned())
+            .spawn(move || loop {
+                match rx.recv_timeout(timeout) {
+                    // cancelled, or replaced by a newer watchdog
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                    Err(RecvTimeoutError::Timeout) => {}
+                }
+                eprintln!("Timeout ({:.1} seconds)!", timeout.as_secs_f64());
+                eprintln!("Stack (most recent call first):");
+                for frame in frames.iter() {
+                    dump_frame_to_stderr(frame);
+                }
+                if exit {
+                    std::process::exit(1);
+                }
+                if !repeat {
+                    break;
+                }
+            })
+            .map_err(|err| vm.new_os_error(format!("can't start watchdog thread: {err}")))?;
+        Ok(())
+    }
+
+    #[cfg(feature = "threading")]
+    #[pyfunction]
+    fn cancel_dump_traceback_later() {
+        // dropping the sender wakes the watchdog, which exits on disconnect
+        *WATCHDOG.lock().unwrap() = None;
     }
 
     #[derive(FromArgs)]
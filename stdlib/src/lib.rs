@@ -8,6 +8,7 @@ extern crate rustpython_derive;
 pub mod array;
 mod binascii;
 mod bisect;
+mod bufferpool;
 mod cmath;
 mod contextvars;
 mod csv;
@@ -18,7 +19,7 @@ mod json;
 #[cfg(not(any(target_os = "ios", target_os = "android", target_arch = "wasm32")))]
 mod locale;
 mod math;
-#[cfg(unix)]
+#[cfg(any(unix, windows))]
 mod mmap;
 mod pyexpat;
 mod pystruct;
@@ -95,6 +96,7 @@ pub fn get_module_inits() -> impl Iterator<Item = (Cow<'static, str>, StdlibInit
             "array" => array::make_module,
             "binascii" => binascii::make_module,
             "_bisect" => bisect::make_module,
+            "_bufferpool" => bufferpool::make_module,
             "cmath" => cmath::make_module,
             "_contextvars" => contextvars::make_module,
             "_csv" => csv::make_module,
@@ -135,11 +137,14 @@ pub fn get_module_inits() -> impl Iterator<Item = (Cow<'static, str>, StdlibInit
         {
             "_bz2" => bz2::make_module,
         }
+        #[cfg(any(unix, windows))]
+        {
+            "mmap" => mmap::make_module,
+        }
         // Unix-only
         #[cfg(unix)]
         {
             "_posixsubprocess" => posixsubprocess::make_module,
-            "mmap" => mmap::make_module,
         }
         #[cfg(all(unix, not(target_os = "redox")))]
         {
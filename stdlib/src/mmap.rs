@@ -5,6 +5,7 @@ pub(crate) use mmap::make_module;
 mod mmap {
     use crate::common::{
         borrow::{BorrowedValue, BorrowedValueMut},
+        crt_fd::{Fd, Offset},
         lock::{MapImmutable, PyMutex, PyMutexGuard},
     };
     use crate::vm::{
@@ -13,7 +14,7 @@ mod mmap {
         byte::{bytes_from_object, value_from_object},
         function::{ArgBytesLike, FuncArgs, OptionalArg},
         protocol::{
-            BufferDescriptor, BufferMethods, PyBuffer, PyMappingMethods, PySequenceMethods,
+            BufferDescriptor, BufferInternal, PyBuffer, PyMappingMethods, PySequenceMethods,
         },
         sliceable::{SaturatedSlice, SequenceIndex, SequenceIndexOp},
         types::{AsBuffer, AsMapping, AsSequence, Constructor, Representable},
@@ -21,15 +22,34 @@ mod mmap {
         TryFromBorrowedObject, VirtualMachine,
     };
     use crossbeam_utils::atomic::AtomicCell;
-    use memmap2::{Advice, Mmap, MmapMut, MmapOptions};
-    use nix::unistd;
+    #[cfg(unix)]
+    use memmap2::Advice;
+    use memmap2::{Mmap, MmapMut, MmapOptions};
     use num_traits::Signed;
     use std::fs::File;
     use std::io::Write;
+    use std::mem::ManuallyDrop;
     use std::ops::{Deref, DerefMut};
     #[cfg(unix)]
-    use std::os::unix::io::{FromRawFd, IntoRawFd, RawFd};
+    use std::os::unix::io::{FromRawFd, RawFd};
+    #[cfg(windows)]
+    type RawFd = libc::c_int;
 
+    /// Borrow the C runtime fd as a `File` without taking ownership of it, so
+    /// dropping the result never closes the caller's fd.
+    fn borrow_fd_as_file(fd: RawFd) -> std::io::Result<ManuallyDrop<File>> {
+        #[cfg(unix)]
+        let file = unsafe { File::from_raw_fd(fd) };
+        #[cfg(windows)]
+        let file = {
+            use std::os::windows::io::FromRawHandle;
+            let handle = Fd(fd).to_raw_handle()?;
+            unsafe { File::from_raw_handle(handle) }
+        };
+        Ok(ManuallyDrop::new(file))
+    }
+
+    #[cfg(unix)]
     fn advice_try_from_i32(vm: &VirtualMachine, i: i32) -> PyResult<Advice> {
         Ok(match i {
             libc::MADV_NORMAL => Advice::Normal,
@@ -85,6 +105,7 @@ mod mmap {
         }
     }
 
+    #[cfg(unix)]
     #[pyattr]
     use libc::{
         MADV_DONTNEED, MADV_NORMAL, MADV_RANDOM, MADV_SEQUENTIAL, MADV_WILLNEED, MAP_ANON,
@@ -128,13 +149,11 @@ mod mmap {
     #[pyattr]
     const ACCESS_COPY: u32 = AccessMode::Copy as u32;
 
-    #[cfg(all(unix, not(target_arch = "wasm32")))]
     #[pyattr(name = "PAGESIZE", once)]
     fn page_size(_vm: &VirtualMachine) -> usize {
         page_size::get()
     }
 
-    #[cfg(all(unix, not(target_arch = "wasm32")))]
     #[pyattr(name = "ALLOCATIONGRANULARITY", once)]
     fn granularity(_vm: &VirtualMachine) -> usize {
         page_size::get_granularity()
@@ -158,7 +177,7 @@ mod mmap {
         closed: AtomicCell<bool>,
         mmap: PyMutex<Option<MmapObj>>,
         fd: RawFd,
-        offset: libc::off_t,
+        offset: Offset,
         size: AtomicCell<usize>,
         pos: AtomicCell<usize>, // relative to offset
         exports: AtomicCell<usize>,
@@ -171,14 +190,16 @@ mod mmap {
         fileno: RawFd,
         #[pyarg(any)]
         length: isize,
+        #[cfg(unix)]
         #[pyarg(any, default = "MAP_SHARED")]
         flags: libc::c_int,
+        #[cfg(unix)]
         #[pyarg(any, default = "PROT_WRITE|PROT_READ")]
         prot: libc::c_int,
         #[pyarg(any, default = "AccessMode::Default")]
         access: AccessMode,
         #[pyarg(any, default = "0")]
-        offset: libc::off_t,
+        offset: Offset,
     }
 
     #[derive(FromArgs)]
@@ -224,7 +245,7 @@ mod mmap {
         end: Option<isize>,
     }
 
-    #[cfg(not(target_os = "redox"))]
+    #[cfg(all(unix, not(target_os = "redox")))]
     #[derive(FromArgs)]
     pub struct AdviseOptions {
         #[pyarg(positional)]
@@ -235,7 +256,7 @@ mod mmap {
         length: Option<PyIntRef>,
     }
 
-    #[cfg(not(target_os = "redox"))]
+    #[cfg(all(unix, not(target_os = "redox")))]
     impl AdviseOptions {
         fn values(self, len: usize, vm: &VirtualMachine) -> PyResult<(libc::c_int, usize, usize)> {
             let start = self
@@ -274,20 +295,14 @@ mod mmap {
     impl Constructor for PyMmap {
         type Args = MmapNewArgs;
 
-        // TODO: Windows is not supported right now.
-        #[cfg(unix)]
-        fn py_new(
-            cls: PyTypeRef,
-            MmapNewArgs {
-                fileno: mut fd,
-                length,
-                flags,
-                prot,
-                access,
-                offset,
-            }: Self::Args,
-            vm: &VirtualMachine,
-        ) -> PyResult {
+        fn py_new(cls: PyTypeRef, args: Self::Args, vm: &VirtualMachine) -> PyResult {
+            let fd = args.fileno;
+            let length = args.length;
+            let access = args.access;
+            let offset = args.offset;
+            #[cfg(unix)]
+            let (flags, prot) = (args.flags, args.prot);
+
             let map_size = length;
             if map_size < 0 {
                 return Err(
@@ -302,6 +317,7 @@ mod mmap {
                 );
             }
 
+            #[cfg(unix)]
             if (access != AccessMode::Default)
                 && ((flags != MAP_SHARED) || (prot != (PROT_WRITE | PROT_READ)))
             {
@@ -311,6 +327,7 @@ mod mmap {
             }
 
             // TODO: memmap2 doesn't support mapping with pro and flags right now
+            #[cfg(unix)]
             let (_flags, _prot, access) = match access {
                 AccessMode::Read => (MAP_SHARED, PROT_READ, access),
                 AccessMode::Write => (MAP_SHARED, PROT_READ | PROT_WRITE, access),
@@ -326,16 +343,21 @@ mod mmap {
                     (flags, prot, access)
                 }
             };
+            // windows has no prot/flags, so ACCESS_DEFAULT simply means a
+            // writable mapping
+            #[cfg(windows)]
+            let access = if access == AccessMode::Default {
+                AccessMode::Write
+            } else {
+                access
+            };
 
             if fd != -1 {
-                let file = unsafe { File::from_raw_fd(fd) };
+                let file = borrow_fd_as_file(fd).map_err(|e| vm.new_os_error(e.to_string()))?;
                 let metadata = file
                     .metadata()
                     .map_err(|e| vm.new_os_error(e.to_string()))?;
-                let file_len: libc::off_t = metadata.len().try_into().expect("file size overflow");
-                // File::from_raw_fd will consume the fd, so we
-                // have to  get it again.
-                fd = file.into_raw_fd();
+                let file_len: Offset = metadata.len().try_into().expect("file size overflow");
                 if map_size == 0 {
                     if file_len == 0 {
                         return Err(vm.new_value_error("cannot mmap an empty file".to_owned()));
@@ -350,7 +372,7 @@ mod mmap {
                     map_size = (file_len - offset)
                         .try_into()
                         .map_err(|_| vm.new_value_error("mmap length is too large".to_owned()))?;
-                } else if offset > file_len || file_len - offset < map_size as libc::off_t {
+                } else if offset > file_len || file_len - offset < map_size as Offset {
                     return Err(
                         vm.new_value_error("mmap length is greater than file size".to_owned())
                     );
@@ -370,17 +392,19 @@ mod mmap {
                     ),
                 )
             } else {
-                let new_fd = unistd::dup(fd).map_err(|e| vm.new_os_error(e.to_string()))?;
+                let new_fd = Fd(fd).dup().map_err(|e| vm.new_os_error(e.to_string()))?.0;
+                let file = borrow_fd_as_file(new_fd).map_err(|e| vm.new_os_error(e.to_string()))?;
                 let mmap = match access {
                     AccessMode::Default | AccessMode::Write => MmapObj::Write(
-                        unsafe { mmap_opt.map_mut(fd) }
+                        unsafe { mmap_opt.map_mut(&*file) }
                             .map_err(|e| vm.new_os_error(e.to_string()))?,
                     ),
                     AccessMode::Read => MmapObj::Read(
-                        unsafe { mmap_opt.map(fd) }.map_err(|e| vm.new_os_error(e.to_string()))?,
+                        unsafe { mmap_opt.map(&*file) }
+                            .map_err(|e| vm.new_os_error(e.to_string()))?,
                     ),
                     AccessMode::Copy => MmapObj::Write(
-                        unsafe { mmap_opt.map_copy(fd) }
+                        unsafe { mmap_opt.map_copy(&*file) }
                             .map_err(|e| vm.new_os_error(e.to_string()))?,
                     ),
                 };
@@ -402,24 +426,27 @@ mod mmap {
         }
     }
 
-    static BUFFER_METHODS: BufferMethods = BufferMethods {
-        obj_bytes: |buffer| buffer.obj_as::<PyMmap>().as_bytes(),
-        obj_bytes_mut: |buffer| buffer.obj_as::<PyMmap>().as_bytes_mut(),
-        release: |buffer| {
-            buffer.obj_as::<PyMmap>().exports.fetch_sub(1);
-        },
-        retain: |buffer| {
-            buffer.obj_as::<PyMmap>().exports.fetch_add(1);
-        },
-    };
+    impl BufferInternal for PyMmap {
+        fn obj_bytes(&self) -> BorrowedValue<[u8]> {
+            PyMmap::as_bytes(self)
+        }
+
+        fn obj_bytes_mut(&self) -> BorrowedValueMut<[u8]> {
+            PyMmap::as_bytes_mut(self)
+        }
+
+        fn release(&self) {
+            self.exports.fetch_sub(1);
+        }
+
+        fn retain(&self) {
+            self.exports.fetch_add(1);
+        }
+    }
 
     impl AsBuffer for PyMmap {
         fn as_buffer(zelf: &Py<Self>, _vm: &VirtualMachine) -> PyResult<PyBuffer> {
-            let buf = PyBuffer::new(
-                zelf.to_owned().into(),
-                BufferDescriptor::simple(zelf.len(), true),
-                &BUFFER_METHODS,
-            );
+            let buf = PyBuffer::new(zelf.to_owned(), BufferDescriptor::simple(zelf.len(), true));
 
             Ok(buf)
         }
@@ -538,8 +565,6 @@ mod mmap {
             Ok(m)
         }
 
-        /// TODO: impl resize
-        #[allow(dead_code)]
         fn check_resizeable(&self, vm: &VirtualMachine) -> PyResult<()> {
             if self.exports.load() > 0 {
                 return Err(vm.new_buffer_error(
@@ -650,7 +675,7 @@ mod mmap {
             Ok(())
         }
 
-        #[cfg(not(target_os = "redox"))]
+        #[cfg(all(unix, not(target_os = "redox")))]
         #[allow(unused_assignments)]
         #[pymethod]
         fn madvise(&self, options: AdviseOptions, vm: &VirtualMachine) -> PyResult<()> {
@@ -801,11 +826,61 @@ mod mmap {
             Ok(result)
         }
 
-        // TODO: supports resize
         #[pymethod]
-        fn resize(&self, _newsize: PyIntRef, vm: &VirtualMachine) -> PyResult<()> {
+        fn resize(&self, newsize: PyIntRef, vm: &VirtualMachine) -> PyResult<()> {
             self.check_resizeable(vm)?;
-            Err(vm.new_system_error("mmap: resizing not available--no mremap()".to_owned()))
+            let newsize = newsize
+                .try_to_primitive::<isize>(vm)
+                .ok()
+                .filter(|s| *s > 0)
+                .ok_or_else(|| vm.new_value_error("new size out of range".to_owned()))?
+                as usize;
+
+            let mut mmap = self.check_valid(vm)?;
+            // the old view has to be gone before the file (or the anonymous
+            // memory) can change size under it; if remapping fails below, the
+            // map is left closed, the same as after close()
+            let old = mmap.take().unwrap();
+            let new_mmap = if self.fd == -1 {
+                let old = match old {
+                    MmapObj::Write(mmap) => mmap,
+                    MmapObj::Read(_) => unreachable!("anonymous maps are always writable"),
+                };
+                let mut new = MmapOptions::new()
+                    .len(newsize)
+                    .map_anon()
+                    .map_err(|e| vm.new_os_error(e.to_string()))?;
+                let keep = newsize.min(old.len());
+                new[..keep].copy_from_slice(&old[..keep]);
+                MmapObj::Write(new)
+            } else {
+                drop(old);
+                let file_end = Offset::try_from(newsize)
+                    .ok()
+                    .and_then(|n| n.checked_add(self.offset))
+                    .ok_or_else(|| vm.new_value_error("new size out of range".to_owned()))?;
+                Fd(self.fd)
+                    .ftruncate(file_end)
+                    .map_err(|e| vm.new_os_error(e.to_string()))?;
+                let file =
+                    borrow_fd_as_file(self.fd).map_err(|e| vm.new_os_error(e.to_string()))?;
+                let mut mmap_opt = MmapOptions::new();
+                let mmap_opt = mmap_opt
+                    .offset(self.offset.try_into().unwrap())
+                    .len(newsize);
+                MmapObj::Write(
+                    unsafe { mmap_opt.map_mut(&*file) }
+                        .map_err(|e| vm.new_os_error(e.to_string()))?,
+                )
+            };
+            *mmap = Some(new_mmap);
+            self.size.store(newsize);
+            // the cursor can't point past the end of the new mapping
+            if self.pos() > newsize {
+                self.pos.store(newsize);
+            }
+
+            Ok(())
         }
 
         #[pymethod]
@@ -849,8 +924,7 @@ mod mmap {
 
         #[pymethod]
         fn size(&self, vm: &VirtualMachine) -> PyResult<PyIntRef> {
-            let new_fd = unistd::dup(self.fd).map_err(|e| vm.new_os_error(e.to_string()))?;
-            let file = unsafe { File::from_raw_fd(new_fd) };
+            let file = borrow_fd_as_file(self.fd).map_err(|e| vm.new_os_error(e.to_string()))?;
             let file_len = match file.metadata() {
                 Ok(m) => m.len(),
                 Err(e) => return Err(vm.new_os_error(e.to_string())),
@@ -3,6 +3,7 @@ pub(crate) use resource::make_module;
 #[pymodule]
 mod resource {
     use crate::vm::{
+        builtins::PyTypeRef,
         convert::{ToPyException, ToPyObject},
         stdlib::os,
         types::PyStructSequence,
@@ -10,6 +11,11 @@ mod resource {
     };
     use std::{io, mem};
 
+    #[pyattr]
+    fn error(vm: &VirtualMachine) -> PyTypeRef {
+        vm.ctx.exceptions.os_error.to_owned()
+    }
+
     cfg_if::cfg_if! {
         if #[cfg(any(target_os = "linux", target_os = "android"))] {
             use libc::RLIM_NLIMITS;
@@ -90,7 +96,7 @@ mod resource {
             let tv = |tv: libc::timeval| tv.tv_sec as f64 + (tv.tv_usec as f64 / 1_000_000.0);
             Rusage {
                 ru_utime: tv(rusage.ru_utime),
-                ru_stime: tv(rusage.ru_utime),
+                ru_stime: tv(rusage.ru_stime),
                 ru_maxrss: rusage.ru_maxrss,
                 ru_ixrss: rusage.ru_ixrss,
                 ru_idrss: rusage.ru_idrss,
@@ -128,6 +134,11 @@ mod resource {
         })
     }
 
+    #[pyfunction]
+    fn getpagesize() -> libc::c_long {
+        unsafe { libc::sysconf(libc::_SC_PAGESIZE) }
+    }
+
     struct Limits(libc::rlimit);
     impl<'a> TryFromBorrowedObject<'a> for Limits {
         fn try_from_borrowed_object(vm: &VirtualMachine, obj: &'a PyObject) -> PyResult<Self> {
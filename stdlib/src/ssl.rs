@@ -36,8 +36,8 @@ mod _ssl {
             convert::{ToPyException, ToPyObject},
             exceptions,
             function::{
-                ArgBytesLike, ArgCallable, ArgMemoryBuffer, ArgStrOrBytesLike, Either, FsPath,
-                OptionalArg,
+                ArgBytesLike, ArgCallable, ArgMemoryBuffer, ArgPath, ArgStrOrBytesLike, Either,
+                FsPath, OptionalArg,
             },
             types::Constructor,
             utils::ToCString,
@@ -1310,9 +1310,8 @@ mod _ssl {
     }
 
     #[pyfunction]
-    fn _test_decode_cert(path: FsPath, vm: &VirtualMachine) -> PyResult {
-        let path = path.to_path_buf(vm)?;
-        let pem = std::fs::read(path).map_err(|e| e.to_pyexception(vm))?;
+    fn _test_decode_cert(path: ArgPath, vm: &VirtualMachine) -> PyResult {
+        let pem = std::fs::read(path.as_path()).map_err(|e| e.to_pyexception(vm))?;
         let x509 = X509::from_pem(&pem).map_err(|e| convert_openssl_error(vm, e))?;
         cert_to_py(vm, &x509, false)
     }
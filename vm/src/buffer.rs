@@ -437,6 +437,30 @@ impl FormatSpec {
         Ok(PyTuple::new_ref(items, &vm.ctx))
     }
 
+    /// unpack a single item: the scalar itself for a one-value format, a
+    /// tuple otherwise; this is the typed item access used by memoryview
+    /// and array
+    pub fn unpack_item(&self, data: &[u8], vm: &VirtualMachine) -> PyResult<PyObjectRef> {
+        self.unpack(data, vm).map(|x| {
+            if x.len() == 1 {
+                x.fast_getitem(0)
+            } else {
+                x.into()
+            }
+        })
+    }
+
+    /// pack a single item into `buffer`, the inverse of
+    /// [`unpack_item`](Self::unpack_item)
+    pub fn pack_item(
+        &self,
+        arg: PyObjectRef,
+        buffer: &mut [u8],
+        vm: &VirtualMachine,
+    ) -> PyResult<()> {
+        self.pack_into(buffer, vec![arg], vm)
+    }
+
     #[inline]
     pub fn size(&self) -> usize {
         self.size
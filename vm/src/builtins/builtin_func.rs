@@ -1,8 +1,8 @@
-use super::{type_, PyClassMethod, PyStaticMethod, PyStr, PyStrInterned, PyStrRef, PyType};
+use super::{type_, PyStaticMethod, PyStr, PyStrInterned, PyStrRef, PyType};
 use crate::{
-    builtins::PyBoundMethod,
+    builtins::{PyBoundMethod, PyModule},
     class::PyClassImpl,
-    function::{FuncArgs, IntoPyNativeFunc, PyNativeFunc},
+    function::{FuncArgs, IntoPyNativeFunc, ParameterDef, PyNativeFunc},
     types::{Callable, Constructor, GetDescriptor, Representable, Unconstructible},
     AsObject, Context, Py, PyObjectRef, PyPayload, PyRef, PyResult, VirtualMachine,
 };
@@ -12,6 +12,7 @@ pub struct PyNativeFuncDef {
     pub func: PyNativeFunc,
     pub name: &'static PyStrInterned,
     pub doc: Option<PyStrRef>,
+    pub sig: Option<&'static [ParameterDef]>,
 }
 
 impl PyNativeFuncDef {
@@ -20,6 +21,7 @@ impl PyNativeFuncDef {
             func,
             name,
             doc: None,
+            sig: None,
         }
     }
 
@@ -28,6 +30,11 @@ impl PyNativeFuncDef {
         self
     }
 
+    pub fn with_sig(mut self, sig: &'static [ParameterDef]) -> Self {
+        self.sig = Some(sig);
+        self
+    }
+
     pub fn into_function(self) -> PyBuiltinFunction {
         self.into()
     }
@@ -45,10 +52,12 @@ impl PyNativeFuncDef {
         self,
         ctx: &Context,
         class: &'static Py<PyType>,
-    ) -> PyRef<PyClassMethod> {
-        // TODO: classmethod_descriptor
-        let callable = self.build_method(ctx, class).into();
-        PyClassMethod::new_ref(callable, ctx)
+    ) -> PyRef<PyBuiltinClassMethod> {
+        PyRef::new_ref(
+            PyBuiltinClassMethod { value: self, class },
+            ctx.types.classmethod_descriptor_type.to_owned(),
+            None,
+        )
     }
     pub fn build_staticmethod(
         self,
@@ -134,16 +143,32 @@ impl PyBuiltinFunction {
     }
     #[pygetset(name = "__self__")]
     fn get_self(&self, vm: &VirtualMachine) -> PyObjectRef {
-        vm.ctx.none()
+        // a module-level builtin behaves as if bound to its module, like a
+        // PyCFunction created with a module object in CPython
+        self.module
+            .as_ref()
+            .and_then(|module| {
+                let sys_modules = vm.sys_module.get_attr("modules", vm).ok()?;
+                sys_modules.get_item(&**module, vm).ok()
+            })
+            .unwrap_or_else(|| vm.ctx.none())
     }
     #[pymethod(magic)]
-    fn reduce(&self) -> PyStrRef {
-        // TODO: return (getattr, (self.object, self.name)) if this is a method
-        self.name()
+    fn reduce(&self, vm: &VirtualMachine) -> PyResult {
+        // match CPython's meth_reduce: a function bound to a module (or to
+        // nothing) pickles by name, anything else round-trips through getattr
+        let zelf = self.get_self(vm);
+        if vm.is_none(&zelf) || zelf.payload_is::<PyModule>() {
+            Ok(self.name().into())
+        } else {
+            let getattr = vm.builtins.get_attr("getattr", vm)?;
+            let target = vm.ctx.new_tuple(vec![zelf, self.name().into()]);
+            Ok(vm.ctx.new_tuple(vec![getattr, target.into()]).into())
+        }
     }
     #[pymethod(magic)]
-    fn reduce_ex(&self, _ver: PyObjectRef) -> PyStrRef {
-        self.name()
+    fn reduce_ex(&self, _ver: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        self.reduce(vm)
     }
     #[pygetset(magic)]
     fn text_signature(&self) -> Option<String> {
@@ -152,6 +177,17 @@ impl PyBuiltinFunction {
             type_::get_text_signature_from_internal_doc(self.value.name.as_str(), doc.as_str())?;
         Some(signature.to_owned())
     }
+    #[pygetset(magic)]
+    fn signature(&self, vm: &VirtualMachine) -> PyResult {
+        let sig = self.value.sig.ok_or_else(|| {
+            // inspect falls back to __text_signature__ on AttributeError
+            vm.new_attribute_error(format!(
+                "builtin function {} has no signature metadata",
+                self.value.name
+            ))
+        })?;
+        build_signature(sig, vm)
+    }
 }
 
 impl Representable for PyBuiltinFunction {
@@ -253,6 +289,16 @@ impl PyBuiltinMethod {
                 .map(|signature| signature.to_string())
         })
     }
+    #[pygetset(magic)]
+    fn signature(&self, vm: &VirtualMachine) -> PyResult {
+        let sig = self.value.sig.ok_or_else(|| {
+            vm.new_attribute_error(format!(
+                "method {} has no signature metadata",
+                self.value.name
+            ))
+        })?;
+        build_signature(sig, vm)
+    }
     #[pymethod(magic)]
     fn reduce(
         &self,
@@ -277,7 +323,123 @@ impl Representable for PyBuiltinMethod {
 
 impl Unconstructible for PyBuiltinMethod {}
 
+// `PyBuiltinClassMethod` is `PyMethodDescrObject` with `METH_CLASS` set,
+// i.e. CPython's `classmethod_descriptor` in
+// https://github.com/python/cpython/blob/main/Objects/descrobject.c
+#[pyclass(module = false, name = "classmethod_descriptor")]
+pub struct PyBuiltinClassMethod {
+    value: PyNativeFuncDef,
+    class: &'static Py<PyType>,
+}
+
+impl PyPayload for PyBuiltinClassMethod {
+    fn class(ctx: &Context) -> &'static Py<PyType> {
+        ctx.types.classmethod_descriptor_type
+    }
+}
+
+impl fmt::Debug for PyBuiltinClassMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "classmethod descriptor for '{}'", self.value.name)
+    }
+}
+
+impl GetDescriptor for PyBuiltinClassMethod {
+    fn descr_get(
+        zelf: PyObjectRef,
+        obj: Option<PyObjectRef>,
+        cls: Option<PyObjectRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult {
+        let (zelf, obj) = Self::_unwrap(&zelf, obj, vm)?;
+        // bind the class, not the instance; an instance access falls back
+        // to its class
+        let cls = cls.unwrap_or_else(|| obj.class().to_owned().into());
+        Ok(PyBoundMethod::new_ref(cls, zelf.to_owned().into(), &vm.ctx).into())
+    }
+}
+
+impl Callable for PyBuiltinClassMethod {
+    type Args = FuncArgs;
+    #[inline]
+    fn call(zelf: &Py<Self>, args: FuncArgs, vm: &VirtualMachine) -> PyResult {
+        (zelf.value.func)(vm, args)
+    }
+}
+
+#[pyclass(with(GetDescriptor, Callable, Constructor, Representable))]
+impl PyBuiltinClassMethod {
+    #[pygetset(magic)]
+    fn name(&self) -> PyStrRef {
+        self.value.name.to_owned()
+    }
+    #[pygetset(magic)]
+    fn qualname(&self) -> String {
+        format!("{}.{}", self.class.name(), &self.value.name)
+    }
+    #[pygetset(magic)]
+    fn doc(&self) -> Option<PyStrRef> {
+        self.value.doc.clone()
+    }
+    #[pygetset(name = "__objclass__")]
+    fn objclass(&self) -> PyObjectRef {
+        self.class.to_owned().into()
+    }
+    #[pygetset(magic)]
+    fn text_signature(&self) -> Option<String> {
+        self.value.doc.as_ref().and_then(|doc| {
+            type_::get_text_signature_from_internal_doc(self.value.name.as_str(), doc.as_str())
+                .map(|signature| signature.to_string())
+        })
+    }
+    #[pymethod(magic)]
+    fn reduce(
+        &self,
+        vm: &VirtualMachine,
+    ) -> (Option<PyObjectRef>, (Option<PyObjectRef>, PyStrRef)) {
+        let builtins_getattr = vm.builtins.get_attr("getattr", vm).ok();
+        let classname = vm.builtins.get_attr(&self.class.__name__(vm), vm).ok();
+        (builtins_getattr, (classname, self.value.name.to_owned()))
+    }
+}
+
+impl Representable for PyBuiltinClassMethod {
+    #[inline]
+    fn repr_str(zelf: &Py<Self>, _vm: &VirtualMachine) -> PyResult<String> {
+        Ok(format!(
+            "<classmethod '{}' of '{}' objects>",
+            &zelf.value.name,
+            zelf.class.name()
+        ))
+    }
+}
+
+impl Unconstructible for PyBuiltinClassMethod {}
+
+/// build an `inspect.Signature` from the structured metadata the macros
+/// attach to a function definition
+fn build_signature(sig: &'static [ParameterDef], vm: &VirtualMachine) -> PyResult {
+    let inspect = vm.import("inspect", None, 0)?;
+    let parameter_cls = inspect.get_attr("Parameter", vm)?;
+    let params = sig
+        .iter()
+        .map(|param| {
+            let kind = parameter_cls.get_attr(param.kind.as_inspect_name(), vm)?;
+            let mut args = FuncArgs::from(vec![vm.ctx.new_str(param.name).into(), kind]);
+            if param.has_default {
+                // only the optionality survives on the Rust side; None is the
+                // closest representable default value
+                args.kwargs.insert("default".into(), vm.ctx.none());
+            }
+            parameter_cls.call(args, vm)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let signature_cls = inspect.get_attr("Signature", vm)?;
+    signature_cls.call((params,), vm)
+}
+
 pub fn init(context: &Context) {
     PyBuiltinFunction::extend_class(context, context.types.builtin_function_or_method_type);
     PyBuiltinMethod::extend_class(context, context.types.method_descriptor_type);
+    PyBuiltinClassMethod::extend_class(context, context.types.classmethod_descriptor_type);
 }
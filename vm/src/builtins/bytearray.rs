@@ -14,6 +14,7 @@ use crate::{
     class::PyClassImpl,
     common::{
         atomic::{AtomicUsize, Ordering},
+        borrow::{BorrowedValue, BorrowedValueMut},
         lock::{
             PyMappedRwLockReadGuard, PyMappedRwLockWriteGuard, PyMutex, PyRwLock,
             PyRwLockReadGuard, PyRwLockWriteGuard,
@@ -25,7 +26,7 @@ use crate::{
         PyComparisonValue,
     },
     protocol::{
-        BufferDescriptor, BufferMethods, BufferResizeGuard, PyBuffer, PyIterReturn,
+        BufferDescriptor, BufferInternal, BufferResizeGuard, PyBuffer, PyIterReturn,
         PyMappingMethods, PyNumberMethods, PySequenceMethods,
     },
     sliceable::{SequenceIndex, SliceableSequenceMutOp, SliceableSequenceOp},
@@ -195,10 +196,6 @@ impl PyByteArray {
     fn inner(&self) -> PyRwLockReadGuard<'_, PyBytesInner> {
         self.inner.read()
     }
-    #[inline]
-    fn inner_mut(&self) -> PyRwLockWriteGuard<'_, PyBytesInner> {
-        self.inner.write()
-    }
 
     #[pymethod(magic)]
     fn alloc(&self) -> usize {
@@ -711,7 +708,9 @@ impl Initializer for PyByteArray {
     fn init(zelf: PyRef<Self>, options: Self::Args, vm: &VirtualMachine) -> PyResult<()> {
         // First unpack bytearray and *then* get a lock to set it.
         let mut inner = options.get_bytearray_inner(vm)?;
-        std::mem::swap(&mut *zelf.inner_mut(), &mut inner);
+        // re-initializing replaces the backing storage, which must not happen
+        // while a buffer export is alive
+        std::mem::swap(&mut *zelf.try_resizable(vm)?, &mut inner);
         Ok(())
     }
 }
@@ -730,34 +729,29 @@ impl Comparable for PyByteArray {
     }
 }
 
-static BUFFER_METHODS: BufferMethods = BufferMethods {
-    obj_bytes: |buffer| buffer.obj_as::<PyByteArray>().borrow_buf().into(),
-    obj_bytes_mut: |buffer| {
-        PyMappedRwLockWriteGuard::map(buffer.obj_as::<PyByteArray>().borrow_buf_mut(), |x| {
-            x.as_mut_slice()
-        })
-        .into()
-    },
-    release: |buffer| {
-        buffer
-            .obj_as::<PyByteArray>()
-            .exports
-            .fetch_sub(1, Ordering::Release);
-    },
-    retain: |buffer| {
-        buffer
-            .obj_as::<PyByteArray>()
-            .exports
-            .fetch_add(1, Ordering::Release);
-    },
-};
+impl BufferInternal for PyByteArray {
+    fn obj_bytes(&self) -> BorrowedValue<[u8]> {
+        self.borrow_buf().into()
+    }
+
+    fn obj_bytes_mut(&self) -> BorrowedValueMut<[u8]> {
+        PyMappedRwLockWriteGuard::map(self.borrow_buf_mut(), |x| x.as_mut_slice()).into()
+    }
+
+    fn release(&self) {
+        self.exports.fetch_sub(1, Ordering::Release);
+    }
+
+    fn retain(&self) {
+        self.exports.fetch_add(1, Ordering::Release);
+    }
+}
 
 impl AsBuffer for PyByteArray {
     fn as_buffer(zelf: &Py<Self>, _vm: &VirtualMachine) -> PyResult<PyBuffer> {
         Ok(PyBuffer::new(
-            zelf.to_owned().into(),
+            zelf.to_owned(),
             BufferDescriptor::simple(zelf.len(), false),
-            &BUFFER_METHODS,
         ))
     }
 }
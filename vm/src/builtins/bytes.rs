@@ -9,14 +9,14 @@ use crate::{
         ByteInnerSplitOptions, ByteInnerTranslateOptions, DecodeArgs, PyBytesInner,
     },
     class::PyClassImpl,
-    common::{hash::PyHash, lock::PyMutex},
+    common::{borrow::BorrowedValue, hash::PyHash, lock::PyMutex},
     convert::{ToPyObject, ToPyResult},
     function::{
         ArgBytesLike, ArgIndex, ArgIterable, Either, OptionalArg, OptionalOption, PyComparisonValue,
     },
     protocol::{
-        BufferDescriptor, BufferMethods, PyBuffer, PyIterReturn, PyMappingMethods, PyNumberMethods,
-        PySequenceMethods,
+        BufferDescriptor, BufferInternal, PyBuffer, PyIterReturn, PyMappingMethods,
+        PyNumberMethods, PySequenceMethods,
     },
     sliceable::{SequenceIndex, SliceableSequenceOp},
     types::{
@@ -548,20 +548,15 @@ impl PyRef<PyBytes> {
     }
 }
 
-static BUFFER_METHODS: BufferMethods = BufferMethods {
-    obj_bytes: |buffer| buffer.obj_as::<PyBytes>().as_bytes().into(),
-    obj_bytes_mut: |_| panic!(),
-    release: |_| {},
-    retain: |_| {},
-};
+impl BufferInternal for PyBytes {
+    fn obj_bytes(&self) -> BorrowedValue<[u8]> {
+        self.as_bytes().into()
+    }
+}
 
 impl AsBuffer for PyBytes {
     fn as_buffer(zelf: &Py<Self>, _vm: &VirtualMachine) -> PyResult<PyBuffer> {
-        let buf = PyBuffer::new(
-            zelf.to_owned().into(),
-            BufferDescriptor::simple(zelf.len(), true),
-            &BUFFER_METHODS,
-        );
+        let buf = PyBuffer::new(zelf.to_owned(), BufferDescriptor::simple(zelf.len(), true));
         Ok(buf)
     }
 }
@@ -55,10 +55,13 @@ impl GetDescriptor for PyClassMethod {
     ) -> PyResult {
         let (zelf, _obj) = Self::_unwrap(&zelf, obj, vm)?;
         let cls = cls.unwrap_or_else(|| _obj.class().to_owned().into());
-        let call_descr_get: PyResult<PyObjectRef> = zelf.callable.lock().get_attr("__get__", vm);
-        match call_descr_get {
-            Err(_) => Ok(PyBoundMethod::new_ref(cls, zelf.callable.lock().clone(), &vm.ctx).into()),
-            Ok(call_descr_get) => call_descr_get.call((cls.clone(), cls), vm),
+        let callable = zelf.callable.lock().clone();
+        // Python 3.9 chaining: if the wrapped object is itself a descriptor
+        // (e.g. `classmethod(property(...))`), delegate to its `__get__` with
+        // the class in both positions instead of binding it ourselves.
+        match vm.call_get_descriptor_specific(&callable, Some(cls.clone()), Some(cls.clone())) {
+            Some(result) => result,
+            None => Ok(PyBoundMethod::new_ref(cls, callable, &vm.ctx).into()),
         }
     }
 }
@@ -7,6 +7,7 @@ use crate::{
     builtins::PyStrInterned,
     bytecode::{self, AsBag, BorrowedConstant, CodeFlags, Constant, ConstantBag},
     class::{PyClassImpl, StaticType},
+    common::lock::PyMutex,
     convert::ToPyObject,
     function::{FuncArgs, OptionalArg},
     types::Representable,
@@ -186,9 +187,101 @@ impl<B: AsRef<[u8]>> IntoCodeObject for bytecode::frozen_lib::FrozenCodeObject<B
     }
 }
 
+/// Operand-type specialization a hot `BinaryOperation`/`Subscript`
+/// instruction can be quickened to, see [`InlineCache::Adaptive`]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OpSpec {
+    /// both operands are exactly `int`
+    IntInt,
+    /// both operands are exactly `float`
+    FloatFloat,
+    /// `list[int]`
+    ListInt,
+    /// `dict[str]`
+    DictStr,
+}
+
+impl OpSpec {
+    /// classify the operands of a `BinaryOperation`; `None` means there is no
+    /// specialized variant for these types. Only exact types qualify, since a
+    /// subclass may override the operator.
+    pub(crate) fn of_binop(a: &PyObject, b: &PyObject, vm: &VirtualMachine) -> Option<Self> {
+        let types = &vm.ctx.types;
+        if a.class().is(types.int_type) && b.class().is(types.int_type) {
+            Some(Self::IntInt)
+        } else if a.class().is(types.float_type) && b.class().is(types.float_type) {
+            Some(Self::FloatFloat)
+        } else {
+            None
+        }
+    }
+
+    /// classify the operands of a `Subscript`, analogous to [`Self::of_binop`]
+    pub(crate) fn of_subscript(a: &PyObject, b: &PyObject, vm: &VirtualMachine) -> Option<Self> {
+        let types = &vm.ctx.types;
+        if a.class().is(types.list_type) && b.class().is(types.int_type) {
+            Some(Self::ListInt)
+        } else if a.class().is(types.dict_type) && b.class().is(types.str_type) {
+            Some(Self::DictStr)
+        } else {
+            None
+        }
+    }
+}
+
+/// executions with stable operand types before an adaptive instruction
+/// switches to its specialized fast path
+pub(crate) const SPECIALIZE_THRESHOLD: u8 = 8;
+
+/// Inline cache slot for an instruction, filled in by the fast paths in
+/// `frame.rs`. Each instruction only ever uses the variant matching its
+/// opcode, so one slot per instruction suffices.
+#[derive(Default)]
+pub(crate) enum InlineCache {
+    #[default]
+    Empty,
+    /// `LoadAttr`/`StoreAttr`: the result of the MRO walk on the receiver's
+    /// type, only meaningful while the type's version tag (see
+    /// `PyType::version_tag`) still equals `version` (0 is never a valid tag)
+    Attr {
+        version: u32,
+        cls_attr: Option<PyObjectRef>,
+    },
+    /// `LoadGlobal`: the value the name resolved to, only meaningful while
+    /// both the globals and the builtins dict still carry these version tags
+    /// (see `dictdatatype::Dict::version`)
+    Global {
+        globals_version: u64,
+        builtins_version: u64,
+        value: PyObjectRef,
+    },
+    /// `SetupWith`/`BeforeAsyncWith`: the enter/exit (or aenter/aexit)
+    /// descriptors from the MRO walk on the context manager's type, only
+    /// meaningful while the type's version tag still equals `version`
+    With {
+        version: u32,
+        enter: Option<PyObjectRef>,
+        exit: Option<PyObjectRef>,
+    },
+    /// `BinaryOperation`/`Subscript`: PEP 659-style adaptive specialization
+    /// state. The instruction warms up while consecutive executions keep
+    /// observing operand types matching `spec`; once `counter` reaches
+    /// [`SPECIALIZE_THRESHOLD`] the handler switches to the specialized fast
+    /// path, and a later type mismatch deoptimizes back into warmup for the
+    /// newly observed types
+    Adaptive {
+        spec: OpSpec,
+        counter: u8,
+        specialized: bool,
+    },
+}
+
 #[pyclass(module = false, name = "code")]
 pub struct PyCode {
     pub code: CodeObject,
+    /// inline cache slots, parallel to `code.instructions`; see the
+    /// `*_cached` helpers on `ExecutingFrame` in `frame.rs`
+    pub(crate) inline_caches: Box<[PyMutex<InlineCache>]>,
 }
 
 impl Deref for PyCode {
@@ -200,7 +293,24 @@ impl Deref for PyCode {
 
 impl PyCode {
     pub fn new(code: CodeObject) -> PyCode {
-        PyCode { code }
+        let inline_caches = std::iter::repeat_with(Default::default)
+            .take(code.instructions.len())
+            .collect();
+        PyCode {
+            code,
+            inline_caches,
+        }
+    }
+
+    /// Name of the opcode at `index`, without its argument.
+    pub(crate) fn instruction_name(&self, index: usize) -> Option<String> {
+        let op = self.code.instructions.get(index)?.op;
+        let name = format!("{op:?}");
+        // the variant name is everything up to the first payload
+        let end = name
+            .find(|c: char| matches!(c, '(' | ' ' | '{'))
+            .unwrap_or(name.len());
+        Some(name[..end].to_owned())
     }
 }
 
@@ -330,6 +440,27 @@ impl PyCode {
         vm.ctx.new_tuple(names)
     }
 
+    /// Name of the opcode at instruction offset `index`, without its
+    /// argument; together with `_instruction_location` this is enough for
+    /// tools to walk a code object instruction by instruction.
+    #[pymethod]
+    fn _instruction_name(&self, index: usize, vm: &VirtualMachine) -> PyResult<String> {
+        self.instruction_name(index)
+            .ok_or_else(|| vm.new_index_error("instruction index out of range".to_owned()))
+    }
+
+    /// `(lineno, col)` of the source span that produced the instruction at
+    /// offset `index`, from the `locations` table
+    #[pymethod]
+    fn _instruction_location(&self, index: usize, vm: &VirtualMachine) -> PyResult<(usize, usize)> {
+        let loc = self
+            .code
+            .locations
+            .get(index)
+            .ok_or_else(|| vm.new_index_error("instruction index out of range".to_owned()))?;
+        Ok((loc.row(), loc.column()))
+    }
+
     #[pymethod]
     pub fn replace(&self, args: ReplaceArgs, vm: &VirtualMachine) -> PyResult<PyCode> {
         let posonlyarg_count = match args.co_posonlyargcount {
@@ -388,33 +519,32 @@ impl PyCode {
             OptionalArg::Missing => self.code.varnames.iter().map(|s| s.to_object()).collect(),
         };
 
-        Ok(PyCode {
-            code: CodeObject {
-                flags: CodeFlags::from_bits_truncate(flags),
-                posonlyarg_count,
-                arg_count,
-                kwonlyarg_count,
-                source_path: source_path.as_object().as_interned_str(vm).unwrap(),
-                first_line_number,
-                obj_name: obj_name.as_object().as_interned_str(vm).unwrap(),
-
-                max_stackdepth: self.code.max_stackdepth,
-                instructions: self.code.instructions.clone(),
-                locations: self.code.locations.clone(),
-                constants: constants.into_iter().map(Literal).collect(),
-                names: names
-                    .into_iter()
-                    .map(|o| o.as_interned_str(vm).unwrap())
-                    .collect(),
-                varnames: varnames
-                    .into_iter()
-                    .map(|o| o.as_interned_str(vm).unwrap())
-                    .collect(),
-                cellvars: self.code.cellvars.clone(),
-                freevars: self.code.freevars.clone(),
-                cell2arg: self.code.cell2arg.clone(),
-            },
-        })
+        Ok(PyCode::new(CodeObject {
+            flags: CodeFlags::from_bits_truncate(flags),
+            posonlyarg_count,
+            arg_count,
+            kwonlyarg_count,
+            source_path: source_path.as_object().as_interned_str(vm).unwrap(),
+            first_line_number,
+            obj_name: obj_name.as_object().as_interned_str(vm).unwrap(),
+
+            max_stackdepth: self.code.max_stackdepth,
+            max_blockdepth: self.code.max_blockdepth,
+            instructions: self.code.instructions.clone(),
+            locations: self.code.locations.clone(),
+            constants: constants.into_iter().map(Literal).collect(),
+            names: names
+                .into_iter()
+                .map(|o| o.as_interned_str(vm).unwrap())
+                .collect(),
+            varnames: varnames
+                .into_iter()
+                .map(|o| o.as_interned_str(vm).unwrap())
+                .collect(),
+            cellvars: self.code.cellvars.clone(),
+            freevars: self.code.freevars.clone(),
+            cell2arg: self.code.cell2arg.clone(),
+        }))
     }
 }
 
@@ -14,10 +14,17 @@ pub struct DescrObject {
     pub qualname: PyRwLock<Option<String>>,
 }
 
+// members of the `T_*` kinds from CPython's structmember.h that make sense
+// for object-slot storage; the discriminants match CPython's values
 #[derive(Debug)]
 pub enum MemberKind {
+    Int = 1,
+    Float = 3,
+    // `String` members are read-only, as in CPython
+    String = 5,
     Bool = 14,
     ObjectEx = 16,
+    SSizeT = 19,
 }
 
 pub type MemberSetterFunc = Option<fn(&VirtualMachine, PyObjectRef, PySetterValue) -> PyResult<()>>;
@@ -140,6 +147,13 @@ fn get_slot_from_object(
     vm: &VirtualMachine,
 ) -> PyResult {
     let slot = match member.kind {
+        MemberKind::Int | MemberKind::SSizeT => obj
+            .get_slot(offset)
+            .unwrap_or_else(|| vm.ctx.new_int(0).into()),
+        MemberKind::Float => obj
+            .get_slot(offset)
+            .unwrap_or_else(|| vm.ctx.new_float(0.0).into()),
+        MemberKind::String => obj.get_slot(offset).unwrap_or_else(|| vm.ctx.none()),
         MemberKind::Bool => obj
             .get_slot(offset)
             .unwrap_or_else(|| vm.ctx.new_bool(false).into()),
@@ -163,6 +177,29 @@ fn set_slot_at_object(
     vm: &VirtualMachine,
 ) -> PyResult<()> {
     match member.kind {
+        MemberKind::Int | MemberKind::SSizeT => match value {
+            PySetterValue::Assign(v) => {
+                if !v.class().fast_issubclass(vm.ctx.types.int_type) {
+                    return Err(vm.new_type_error("attribute value type must be int".to_owned()));
+                }
+                obj.set_slot(offset, Some(v));
+            }
+            PySetterValue::Delete => {
+                return Err(vm.new_type_error("can't delete numeric/char attribute".to_owned()));
+            }
+        },
+        MemberKind::Float => match value {
+            PySetterValue::Assign(v) => {
+                let v = v.try_float(vm)?;
+                obj.set_slot(offset, Some(v.into()));
+            }
+            PySetterValue::Delete => {
+                return Err(vm.new_type_error("can't delete numeric/char attribute".to_owned()));
+            }
+        },
+        MemberKind::String => {
+            return Err(vm.new_attribute_error("readonly attribute".to_owned()));
+        }
         MemberKind::Bool => {
             match value {
                 PySetterValue::Assign(v) => {
@@ -58,12 +58,25 @@ impl PyDict {
         PyRef::new_ref(Self::default(), ctx.types.dict_type.to_owned(), None)
     }
 
+    /// see `dictdatatype::Dict::with_capacity`
+    pub fn new_ref_with_capacity(ctx: &Context, capacity: usize) -> PyRef<Self> {
+        let dict = Self {
+            entries: DictContentType::with_capacity(capacity),
+        };
+        PyRef::new_ref(dict, ctx.types.dict_type.to_owned(), None)
+    }
+
     /// escape hatch to access the underlying data structure directly. prefer adding a method on
     /// PyDict instead of using this
     pub(crate) fn _as_dict_inner(&self) -> &DictContentType {
         &self.entries
     }
 
+    /// version tag of the current content, see `dictdatatype::Dict::version`
+    pub(crate) fn version(&self) -> u64 {
+        self.entries.version()
+    }
+
     // Used in update and ior.
     pub(crate) fn merge_object(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult<()> {
         let casted: Result<PyRefExact<PyDict>, _> = other.downcast_exact(vm);
@@ -100,13 +113,23 @@ impl PyDict {
         Ok(())
     }
 
+    /// Bulk-insert `(key, value)` pairs, hashing each key once and taking
+    /// the underlying table's lock once for the whole batch, see
+    /// `dictdatatype::Dict::extend_from_iter`.
+    pub(crate) fn extend_from_iter(
+        &self,
+        vm: &VirtualMachine,
+        iter: impl IntoIterator<Item = (PyObjectRef, PyObjectRef)>,
+    ) -> PyResult<()> {
+        self.entries.extend_from_iter(vm, iter)
+    }
+
     fn merge_dict(&self, dict_other: PyDictRef, vm: &VirtualMachine) -> PyResult<()> {
-        let dict = &self.entries;
         let dict_size = &dict_other.size();
-        for (key, value) in &dict_other {
-            dict.insert(vm, &*key, value)?;
-        }
-        if dict_other.entries.has_changed_size(dict_size) {
+        self.extend_from_iter(vm, &dict_other)?;
+        if dict_other.entries.has_changed_size(dict_size)
+            || dict_other.entries.has_changed_keys(dict_size)
+        {
             return Err(vm.new_runtime_error("dict mutated during update".to_owned()));
         }
         Ok(())
@@ -198,6 +221,12 @@ impl PyDict {
     pub fn size(&self) -> dictdatatype::DictSize {
         self.entries.size()
     }
+
+    /// Shed the memory a dict that shrank is still holding on to, for
+    /// embedders keeping many long-lived dicts around.
+    pub fn shrink_to_fit(&self) {
+        self.entries.shrink_to_fit()
+    }
 }
 
 // Python dict methods:
@@ -387,7 +416,7 @@ impl PyDict {
 #[pyclass]
 impl Py<PyDict> {
     #[pymethod(magic)]
-    #[cfg_attr(feature = "flame-it", flame("PyDictRef"))]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     fn getitem(&self, key: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.inner_getitem(&*key, vm)
     }
@@ -518,7 +547,7 @@ impl Representable for PyDict {
     fn repr(zelf: &Py<Self>, vm: &VirtualMachine) -> PyResult<PyStrRef> {
         let s = if let Some(_guard) = ReprGuard::enter(vm, zelf.as_object()) {
             let mut str_parts = Vec::with_capacity(zelf.len());
-            for (key, value) in zelf {
+            for (key, value) in zelf.entries.iter_snapshot() {
                 let key_repr = &key.repr(vm)?;
                 let value_repr = value.repr(vm)?;
                 str_parts.push(format!("{key_repr}: {value_repr}"));
@@ -861,6 +890,8 @@ macro_rules! dict_view {
                 let internal = self.internal.lock();
                 let entries = match &internal.status {
                     IterStatus::Active(dict) => dict
+                        .entries
+                        .iter_snapshot()
                         .into_iter()
                         .map(|(key, value)| ($result_fn)(vm, key, value))
                         .collect::<Vec<_>>(),
@@ -883,6 +914,12 @@ macro_rules! dict_view {
                             "dictionary changed size during iteration".to_owned(),
                         ));
                     }
+                    if dict.entries.has_changed_keys(&zelf.size) {
+                        internal.status = IterStatus::Exhausted;
+                        return Err(vm.new_runtime_error(
+                            "dictionary keys changed during iteration".to_owned(),
+                        ));
+                    }
                     match dict.entries.next_entry(internal.position) {
                         Some((position, key, value)) => {
                             internal.position = position;
@@ -932,6 +969,8 @@ macro_rules! dict_view {
                 // TODO: entries must be reversed too
                 let entries = match &internal.status {
                     IterStatus::Active(dict) => dict
+                        .entries
+                        .iter_snapshot()
                         .into_iter()
                         .map(|(key, value)| ($result_fn)(vm, key, value))
                         .collect::<Vec<_>>(),
@@ -961,6 +1000,12 @@ macro_rules! dict_view {
                             "dictionary changed size during iteration".to_owned(),
                         ));
                     }
+                    if dict.entries.has_changed_keys(&zelf.size) {
+                        internal.status = IterStatus::Exhausted;
+                        return Err(vm.new_runtime_error(
+                            "dictionary keys changed during iteration".to_owned(),
+                        ));
+                    }
                     match dict.entries.prev_entry(internal.position) {
                         Some((position, key, value)) => {
                             if internal.position == position {
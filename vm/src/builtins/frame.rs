@@ -33,11 +33,6 @@ impl Representable for Frame {
 
 #[pyclass(with(Constructor, Py))]
 impl Frame {
-    #[pymethod]
-    fn clear(&self) {
-        // TODO
-    }
-
     #[pygetset]
     fn f_globals(&self) -> PyDictRef {
         self.globals.clone()
@@ -63,6 +58,16 @@ impl Frame {
         self.current_location().row()
     }
 
+    /// `(offset, opname, lineno, col)` of the instruction this frame is
+    /// executing right now, or None for a frame that has not started
+    #[pymethod]
+    fn current_instruction(&self) -> Option<(usize, String, usize, usize)> {
+        let index = (self.lasti() as usize).checked_sub(1)?;
+        let opname = self.code.instruction_name(index)?;
+        let loc = self.code.locations[index];
+        Some((index, opname, loc.row(), loc.column()))
+    }
+
     #[pygetset]
     fn f_trace(&self) -> PyObjectRef {
         let boxed = self.trace.lock();
@@ -107,10 +112,55 @@ impl Frame {
             }
         }
     }
+
+    #[pymember(type = "bool")]
+    fn f_trace_opcodes(vm: &VirtualMachine, zelf: PyObjectRef) -> PyResult {
+        let zelf: FrameRef = zelf.downcast().unwrap_or_else(|_| unreachable!());
+
+        let boxed = zelf.trace_opcodes.lock();
+        Ok(vm.ctx.new_bool(*boxed).into())
+    }
+
+    #[pymember(type = "bool", setter)]
+    fn set_f_trace_opcodes(
+        vm: &VirtualMachine,
+        zelf: PyObjectRef,
+        value: PySetterValue,
+    ) -> PyResult<()> {
+        match value {
+            PySetterValue::Assign(value) => {
+                let zelf: FrameRef = zelf.downcast().unwrap_or_else(|_| unreachable!());
+
+                let value: PyIntRef = value.downcast().map_err(|_| {
+                    vm.new_type_error("attribute value type must be bool".to_owned())
+                })?;
+
+                let mut trace_opcodes = zelf.trace_opcodes.lock();
+                *trace_opcodes = !value.as_bigint().is_zero();
+
+                Ok(())
+            }
+            PySetterValue::Delete => {
+                Err(vm.new_type_error("can't delete numeric/char attribute".to_owned()))
+            }
+        }
+    }
 }
 
 #[pyclass]
 impl Py<Frame> {
+    #[pymethod]
+    fn clear(&self, vm: &VirtualMachine) -> PyResult<()> {
+        // frames suspended in the middle of a call sit on the VM frame stack
+        // with their state unlocked, so `try_clear` alone wouldn't catch them
+        let executing = vm.frames.borrow().iter().any(|f| f.is(self.as_object()));
+        if !executing && self.try_clear() {
+            Ok(())
+        } else {
+            Err(vm.new_runtime_error("cannot clear an executing frame".to_owned()))
+        }
+    }
+
     #[pygetset]
     pub fn f_back(&self, vm: &VirtualMachine) -> Option<PyRef<Frame>> {
         // TODO: actually store f_back inside Frame struct
@@ -3,7 +3,7 @@ mod jitfunc;
 
 use super::{
     tuple::PyTupleTyped, PyAsyncGen, PyCode, PyCoroutine, PyDictRef, PyGenerator, PyStr, PyStrRef,
-    PyTupleRef, PyType, PyTypeRef,
+    PyTuple, PyTupleRef, PyType, PyTypeRef,
 };
 #[cfg(feature = "jit")]
 use crate::common::lock::OnceCell;
@@ -13,11 +13,12 @@ use crate::function::ArgMapping;
 use crate::{
     bytecode,
     class::PyClassImpl,
-    frame::Frame,
+    common::hash,
+    frame::{Frame, FrameRef},
     function::{FuncArgs, OptionalArg, PyComparisonValue, PySetterValue},
-    scope::Scope,
     types::{
-        Callable, Comparable, Constructor, GetAttr, GetDescriptor, PyComparisonOp, Representable,
+        Callable, Comparable, Constructor, GetAttr, GetDescriptor, Hashable, PyComparisonOp,
+        Representable,
     },
     AsObject, Context, Py, PyObject, PyObjectRef, PyPayload, PyRef, PyResult, VirtualMachine,
 };
@@ -153,7 +154,7 @@ impl PyFunction {
                 }
                 *slot = Some(value);
             } else if let Some(kwargs) = kwargs.as_ref() {
-                kwargs.set_item(&name, value, vm)?;
+                kwargs.set_item(&*name, value, vm)?;
             } else if argpos(0..code.posonlyarg_count as usize, &name).is_some() {
                 posonly_passed_as_kwarg.push(name);
             } else {
@@ -283,6 +284,60 @@ impl PyFunction {
         Ok(())
     }
 
+    /// Construct a bare frame for a call to this function, without binding
+    /// any arguments into its locals yet.
+    fn new_frame(&self, locals: Option<ArgMapping>, vm: &VirtualMachine) -> FrameRef {
+        let code = &self.code;
+
+        let locals = if code.flags.contains(bytecode::CodeFlags::NEW_LOCALS) {
+            // materialized by the frame on demand; most calls never need it
+            None
+        } else if let Some(locals) = locals {
+            Some(locals)
+        } else {
+            Some(ArgMapping::from_dict_exact(self.globals.clone()))
+        };
+
+        Frame::new(
+            code.clone(),
+            locals,
+            self.globals.clone(),
+            vm.builtins.dict(),
+            self.closure.as_ref().map_or(&[], |c| c.as_slice()),
+            vm,
+        )
+        .into_ref(&vm.ctx)
+    }
+
+    /// Set up the frame for a call to this function, binding `func_args` into
+    /// its locals, without running it.
+    pub(crate) fn create_frame(
+        &self,
+        func_args: FuncArgs,
+        locals: Option<ArgMapping>,
+        vm: &VirtualMachine,
+    ) -> PyResult<FrameRef> {
+        let frame = self.new_frame(locals, vm);
+        self.fill_locals_from_args(&frame, func_args, vm)?;
+        Ok(frame)
+    }
+
+    /// Whether a call to this function may run directly on the VM frame stack
+    /// (see [`ExecutionResult::Call`]): an ordinary function whose frame runs
+    /// to completion in one go, with no jitted fast path to prefer.
+    ///
+    /// [`ExecutionResult::Call`]: crate::frame::ExecutionResult::Call
+    pub(crate) fn is_stackless_callable(&self) -> bool {
+        #[cfg(feature = "jit")]
+        if self.jitted_code.get().is_some() {
+            return false;
+        }
+        !self
+            .code
+            .flags
+            .intersects(bytecode::CodeFlags::IS_GENERATOR | bytecode::CodeFlags::IS_COROUTINE)
+    }
+
     pub fn invoke_with_locals(
         &self,
         func_args: FuncArgs,
@@ -303,29 +358,14 @@ impl PyFunction {
             }
         }
 
-        let code = &self.code;
-
-        let locals = if self.code.flags.contains(bytecode::CodeFlags::NEW_LOCALS) {
-            ArgMapping::from_dict_exact(vm.ctx.new_dict())
-        } else if let Some(locals) = locals {
-            locals
-        } else {
-            ArgMapping::from_dict_exact(self.globals.clone())
-        };
-
-        // Construct frame:
-        let frame = Frame::new(
-            code.clone(),
-            Scope::new(Some(locals), self.globals.clone()),
-            vm.builtins.dict(),
-            self.closure.as_ref().map_or(&[], |c| c.as_slice()),
-            vm,
-        )
-        .into_ref(&vm.ctx);
-
-        self.fill_locals_from_args(&frame, func_args, vm)?;
+        let frame = self.create_frame(func_args, locals, vm)?;
+        self.run_or_wrap_frame(frame, vm)
+    }
 
-        // If we have a generator, create a new generator
+    /// Run a frame set up for a call to this function, or, if we have a
+    /// generator-like function, wrap the frame into the matching object.
+    fn run_or_wrap_frame(&self, frame: FrameRef, vm: &VirtualMachine) -> PyResult {
+        let code = &self.code;
         let is_gen = code.flags.contains(bytecode::CodeFlags::IS_GENERATOR);
         let is_coro = code.flags.contains(bytecode::CodeFlags::IS_COROUTINE);
         match (is_gen, is_coro) {
@@ -336,6 +376,37 @@ impl PyFunction {
         }
     }
 
+    /// The vectorcall fast path: a plain positional call where every
+    /// parameter is filled by exactly one argument can copy the arguments
+    /// straight into the fastlocals, with no `FuncArgs` boxing and none of
+    /// the general binding logic. Returns `None` when the call needs
+    /// defaults, cells, `*args`/`**kwargs` packing or keyword binding, and
+    /// has to take the slow path after all.
+    fn vectorcall_positional(&self, args: &[PyObjectRef], vm: &VirtualMachine) -> Option<PyResult> {
+        #[cfg(feature = "jit")]
+        if self.jitted_code.get().is_some() {
+            return None;
+        }
+        let code = &self.code;
+        if args.len() != code.arg_count as usize
+            || code.kwonlyarg_count != 0
+            || code.cell2arg.is_some()
+            || code
+                .flags
+                .intersects(bytecode::CodeFlags::HAS_VARARGS | bytecode::CodeFlags::HAS_VARKEYWORDS)
+        {
+            return None;
+        }
+        let frame = self.new_frame(None, vm);
+        {
+            let mut fastlocals = frame.fastlocals.lock();
+            for (local, arg) in fastlocals.iter_mut().zip(args) {
+                *local = Some(arg.clone());
+            }
+        }
+        Some(self.run_or_wrap_frame(frame, vm))
+    }
+
     #[inline(always)]
     pub fn invoke(&self, func_args: FuncArgs, vm: &VirtualMachine) -> PyResult {
         self.invoke_with_locals(func_args, None, vm)
@@ -353,6 +424,24 @@ impl PyPayload for PyFunction {
     flags(HAS_DICT, METHOD_DESCR)
 )]
 impl PyFunction {
+    #[pyslot]
+    fn vectorcall(
+        zelf: &PyObject,
+        args: &[PyObjectRef],
+        kwnames: Option<&Py<PyTuple>>,
+        vm: &VirtualMachine,
+    ) -> PyResult {
+        let zelf = zelf
+            .downcast_ref::<Self>()
+            .expect("unexpected payload for vectorcall");
+        if kwnames.is_none() {
+            if let Some(result) = zelf.vectorcall_positional(args, vm) {
+                return result;
+            }
+        }
+        zelf.invoke(FuncArgs::from_vectorcall(args, kwnames, vm), vm)
+    }
+
     #[pygetset(magic)]
     fn code(&self) -> PyRef<PyCode> {
         self.code.clone()
@@ -499,17 +588,28 @@ impl Comparable for PyBoundMethod {
         zelf: &Py<Self>,
         other: &PyObject,
         op: PyComparisonOp,
-        _vm: &VirtualMachine,
+        vm: &VirtualMachine,
     ) -> PyResult<PyComparisonValue> {
         op.eq_only(|| {
             let other = class_or_notimplemented!(Self, other);
+            // as of Python 3.8, __self__ compares by identity and __func__ by
+            // equality
             Ok(PyComparisonValue::Implemented(
-                zelf.function.is(&other.function) && zelf.object.is(&other.object),
+                zelf.object.is(&other.object) && vm.bool_eq(&zelf.function, &other.function)?,
             ))
         })
     }
 }
 
+impl Hashable for PyBoundMethod {
+    #[inline]
+    fn hash(zelf: &Py<Self>, vm: &VirtualMachine) -> PyResult<hash::PyHash> {
+        // combines the identity hash of __self__ with the hash of __func__,
+        // consistent with the comparison above
+        Ok((zelf.object.get_id() as hash::PyHash) ^ zelf.function.hash(vm)?)
+    }
+}
+
 impl GetAttr for PyBoundMethod {
     fn getattro(zelf: &Py<Self>, name: &Py<PyStr>, vm: &VirtualMachine) -> PyResult {
         let class_attr = vm
@@ -559,7 +659,10 @@ impl PyBoundMethod {
     }
 }
 
-#[pyclass(with(Callable, Comparable, GetAttr, Constructor), flags(HAS_DICT))]
+#[pyclass(
+    with(Callable, Comparable, Hashable, GetAttr, Constructor),
+    flags(HAS_DICT)
+)]
 impl PyBoundMethod {
     #[pymethod(magic)]
     fn repr(&self, vm: &VirtualMachine) -> PyResult<String> {
@@ -1,7 +1,7 @@
 /*! Python `attribute` descriptor class. (PyGetSet)
 
 */
-use super::PyType;
+use super::{PyStr, PyType};
 use crate::{
     class::PyClassImpl,
     function::{IntoPyGetterFunc, IntoPySetterFunc, PyGetterFunc, PySetterFunc, PySetterValue},
@@ -15,7 +15,7 @@ pub struct PyGetSet {
     class: &'static Py<PyType>,
     getter: Option<PyGetterFunc>,
     setter: Option<PySetterFunc>,
-    // doc: Option<String>,
+    doc: Option<String>,
 }
 
 impl std::fmt::Debug for PyGetSet {
@@ -74,6 +74,7 @@ impl PyGetSet {
             class,
             getter: None,
             setter: None,
+            doc: None,
         }
     }
 
@@ -92,6 +93,11 @@ impl PyGetSet {
         self.setter = Some(setter.into_setter());
         self
     }
+
+    pub fn with_doc(mut self, doc: String) -> Self {
+        self.doc = Some(doc);
+        self
+    }
 }
 
 #[pyclass(with(GetDescriptor, Constructor))]
@@ -136,8 +142,31 @@ impl PyGetSet {
     }
 
     #[pygetset(magic)]
-    fn qualname(&self) -> String {
-        format!("{}.{}", self.class.slot_name(), self.name.clone())
+    fn qualname(&self, vm: &VirtualMachine) -> PyResult<Option<String>> {
+        Ok(
+            if let Some(qualname) =
+                vm.get_attribute_opt(self.class.to_owned().into(), "__qualname__")?
+            {
+                let str = qualname.downcast::<PyStr>().map_err(|_| {
+                    vm.new_type_error(
+                        "<descriptor>.__objclass__.__qualname__ is not a unicode object".to_owned(),
+                    )
+                })?;
+                Some(format!("{}.{}", str, self.name))
+            } else {
+                None
+            },
+        )
+    }
+
+    #[pygetset(magic)]
+    fn doc(&self) -> Option<String> {
+        self.doc.clone()
+    }
+
+    #[pygetset(name = "__objclass__")]
+    fn objclass(&self) -> PyObjectRef {
+        self.class.to_owned().into()
     }
 }
 impl Unconstructible for PyGetSet {}
@@ -8,7 +8,7 @@ use crate::common::lock::{
 use crate::{
     class::PyClassImpl,
     convert::ToPyObject,
-    function::{ArgSize, FuncArgs, OptionalArg, PyComparisonValue},
+    function::{ArgCallable, ArgSize, FuncArgs, OptionalArg, PyComparisonValue},
     iter::PyExactSizeIterator,
     protocol::{PyIterReturn, PyMappingMethods, PySequenceMethods},
     recursion::ReprGuard,
@@ -20,7 +20,7 @@ use crate::{
     },
     utils::collection_repr,
     vm::VirtualMachine,
-    AsObject, Context, Py, PyObject, PyObjectRef, PyPayload, PyRef, PyResult,
+    AsObject, Context, Py, PyObject, PyObjectRef, PyPayload, PyRef, PyResult, TryFromObject,
 };
 use std::{fmt, ops::DerefMut};
 
@@ -519,10 +519,14 @@ fn do_sort(
     };
     let cmp = |a: &PyObjectRef, b: &PyObjectRef| a.rich_compare_bool(b, op, vm);
 
-    if let Some(ref key_func) = key_func {
+    if let Some(key_func) = key_func {
+        let key_func = ArgCallable::try_from_object(vm, key_func)?;
         let mut items = values
             .iter()
-            .map(|x| Ok((x.clone(), key_func.call((x.clone(),), vm)?)))
+            .map(|x| {
+                let key = key_func.invoke_fast(std::slice::from_ref(x), vm)?;
+                Ok((x.clone(), key))
+            })
             .collect::<Result<Vec<_>, _>>()?;
         timsort::try_sort_by_gt(&mut items, |a, b| cmp(&a.1, &b.1))?;
         *values = items.into_iter().map(|(val, _)| val).collect();
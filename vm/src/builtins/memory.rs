@@ -16,7 +16,7 @@ use crate::{
     function::Either,
     function::{FuncArgs, OptionalArg, PyComparisonValue},
     protocol::{
-        BufferDescriptor, BufferMethods, PyBuffer, PyIterReturn, PyMappingMethods,
+        BufferDescriptor, BufferInternal, BufferOrder, PyBuffer, PyIterReturn, PyMappingMethods,
         PySequenceMethods, VecBuffer,
     },
     sliceable::SequenceIndexOp,
@@ -30,7 +30,10 @@ use crate::{
 use crossbeam_utils::atomic::AtomicCell;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
-use std::{cmp::Ordering, fmt::Debug, mem::ManuallyDrop, ops::Range};
+use std::{cmp::Ordering, fmt::Debug, ops::Range};
+
+/// maximum number of dimensions, matching CPython's `PyBUF_MAX_NDIM`
+const MAX_NDIM: usize = 64;
 
 #[pytrace]
 #[derive(FromArgs)]
@@ -41,8 +44,7 @@ pub struct PyMemoryViewNewArgs {
 #[pyclass(module = false, name = "memoryview")]
 #[derive(Debug)]
 pub struct PyMemoryView {
-    // avoid double release when memoryview had released the buffer before drop
-    buffer: ManuallyDrop<PyBuffer>,
+    buffer: PyBuffer,
     // the released memoryview does not mean the buffer is destroyed
     // because the possible another memeoryview is viewing from it
     released: AtomicCell<bool>,
@@ -95,7 +97,7 @@ impl PyMemoryView {
         let desc = buffer.desc.clone();
 
         Ok(PyMemoryView {
-            buffer: ManuallyDrop::new(buffer),
+            buffer,
             released: AtomicCell::new(false),
             start: 0,
             format_spec,
@@ -121,16 +123,14 @@ impl PyMemoryView {
 
     /// this should be the only way to create a memoryview from another memoryview
     pub fn new_view(&self) -> Self {
-        let zelf = PyMemoryView {
+        PyMemoryView {
             buffer: self.buffer.clone(),
             released: AtomicCell::new(false),
             start: self.start,
             format_spec: self.format_spec.clone(),
             desc: self.desc.clone(),
             hash: OnceCell::new(),
-        };
-        zelf.buffer.retain();
-        zelf
+        }
     }
 
     fn try_not_released(&self, vm: &VirtualMachine) -> PyResult<()> {
@@ -151,9 +151,11 @@ impl PyMemoryView {
         let index = i
             .wrapped_at(shape)
             .ok_or_else(|| vm.new_index_error("index out of range".to_owned()))?;
-        let index = index as isize * stride + suboffset;
-        let pos = (index + self.start as isize) as usize;
-        self.unpack_single(pos, vm)
+        let mut pos = index as isize * stride + self.start as isize;
+        if suboffset >= 0 {
+            pos = BufferDescriptor::deref_suboffset(&self.buffer.obj_bytes(), pos, suboffset);
+        }
+        self.unpack_single(pos as usize, vm)
     }
 
     fn getitem_by_slice(&self, slice: &PySlice, vm: &VirtualMachine) -> PyResult {
@@ -167,7 +169,8 @@ impl PyMemoryView {
     fn getitem_by_multi_idx(&self, indexes: &[isize], vm: &VirtualMachine) -> PyResult {
         let pos = self.pos_from_multi_index(indexes, vm)?;
         let bytes = self.buffer.obj_bytes();
-        format_unpack(&self.format_spec, &bytes[pos..pos + self.desc.itemsize], vm)
+        self.format_spec
+            .unpack_item(&bytes[pos..pos + self.desc.itemsize], vm)
     }
 
     fn setitem_by_idx(&self, i: isize, value: PyObjectRef, vm: &VirtualMachine) -> PyResult<()> {
@@ -178,9 +181,11 @@ impl PyMemoryView {
         let index = i
             .wrapped_at(shape)
             .ok_or_else(|| vm.new_index_error("index out of range".to_owned()))?;
-        let index = index as isize * stride + suboffset;
-        let pos = (index + self.start as isize) as usize;
-        self.pack_single(pos, value, vm)
+        let mut pos = index as isize * stride + self.start as isize;
+        if suboffset >= 0 {
+            pos = BufferDescriptor::deref_suboffset(&self.buffer.obj_bytes(), pos, suboffset);
+        }
+        self.pack_single(pos as usize, value, vm)
     }
 
     fn setitem_by_multi_idx(
@@ -195,29 +200,20 @@ impl PyMemoryView {
 
     fn pack_single(&self, pos: usize, value: PyObjectRef, vm: &VirtualMachine) -> PyResult<()> {
         let mut bytes = self.buffer.obj_bytes_mut();
-        // TODO: Optimize
-        let data = self.format_spec.pack(vec![value], vm).map_err(|_| {
-            vm.new_type_error(format!(
-                "memoryview: invalid type for format '{}'",
-                &self.desc.format
-            ))
-        })?;
-        bytes[pos..pos + self.desc.itemsize].copy_from_slice(&data);
-        Ok(())
+        self.format_spec
+            .pack_item(value, &mut bytes[pos..pos + self.desc.itemsize], vm)
+            .map_err(|_| {
+                vm.new_type_error(format!(
+                    "memoryview: invalid type for format '{}'",
+                    &self.desc.format
+                ))
+            })
     }
 
     fn unpack_single(&self, pos: usize, vm: &VirtualMachine) -> PyResult {
         let bytes = self.buffer.obj_bytes();
-        // TODO: Optimize
         self.format_spec
-            .unpack(&bytes[pos..pos + self.desc.itemsize], vm)
-            .map(|x| {
-                if x.len() == 1 {
-                    x.fast_getitem(0)
-                } else {
-                    x.into()
-                }
-            })
+            .unpack_item(&bytes[pos..pos + self.desc.itemsize], vm)
     }
 
     fn pos_from_multi_index(&self, indexes: &[isize], vm: &VirtualMachine) -> PyResult<usize> {
@@ -235,9 +231,11 @@ impl PyMemoryView {
             Ordering::Equal => (),
         }
 
-        let pos = self.desc.position(indexes, vm)?;
-        let pos = (pos + self.start as isize) as usize;
-        Ok(pos)
+        let bytes = self.buffer.obj_bytes();
+        let pos = self
+            .desc
+            .position(&bytes, self.start as isize, indexes, vm)?;
+        Ok(pos as usize)
     }
 
     fn init_len(&mut self) {
@@ -251,7 +249,7 @@ impl PyMemoryView {
 
         let mut is_adjusted = false;
         for (_, _, suboffset) in self.desc.dim_desc.iter_mut().rev() {
-            if *suboffset != 0 {
+            if *suboffset >= 0 {
                 *suboffset += stride * range.start as isize;
                 is_adjusted = true;
                 break;
@@ -272,7 +270,7 @@ impl PyMemoryView {
 
         let mut is_adjusted_suboffset = false;
         for (_, _, suboffset) in self.desc.dim_desc.iter_mut().rev() {
-            if *suboffset != 0 {
+            if *suboffset >= 0 {
                 *suboffset += stride * range.start as isize;
                 is_adjusted_suboffset = true;
                 break;
@@ -304,10 +302,14 @@ impl PyMemoryView {
         if dim + 1 == self.desc.ndim() {
             let mut v = Vec::with_capacity(shape);
             for _ in 0..shape {
-                let pos = index + suboffset;
-                let pos = (pos + self.start as isize) as usize;
-                let obj =
-                    format_unpack(&self.format_spec, &bytes[pos..pos + self.desc.itemsize], vm)?;
+                let pos = if suboffset >= 0 {
+                    BufferDescriptor::deref_suboffset(bytes, index, suboffset)
+                } else {
+                    index
+                } as usize;
+                let obj = self
+                    .format_spec
+                    .unpack_item(&bytes[pos..pos + self.desc.itemsize], vm)?;
                 v.push(obj);
                 index += stride;
             }
@@ -316,7 +318,12 @@ impl PyMemoryView {
 
         let mut v = Vec::with_capacity(shape);
         for _ in 0..shape {
-            let obj = self._to_list(bytes, index + suboffset, dim + 1, vm)?.into();
+            let base = if suboffset >= 0 {
+                BufferDescriptor::deref_suboffset(bytes, index, suboffset)
+            } else {
+                index
+            };
+            let obj = self._to_list(bytes, base, dim + 1, vm)?.into();
             v.push(obj);
             index += stride;
         }
@@ -352,8 +359,8 @@ impl PyMemoryView {
         let b_format_spec = &Self::parse_format(&other.desc.format, vm)?;
 
         if zelf.desc.ndim() == 0 {
-            let a_val = format_unpack(a_format_spec, &zelf.buffer.obj_bytes()[..a_itemsize], vm)?;
-            let b_val = format_unpack(b_format_spec, &other.obj_bytes()[..b_itemsize], vm)?;
+            let a_val = a_format_spec.unpack_item(&zelf.buffer.obj_bytes()[..a_itemsize], vm)?;
+            let b_val = b_format_spec.unpack_item(&other.obj_bytes()[..b_itemsize], vm)?;
             return vm.bool_eq(&a_val, &b_val);
         }
 
@@ -361,36 +368,37 @@ impl PyMemoryView {
         let mut ret = Ok(true);
         let a_bytes = zelf.buffer.obj_bytes();
         let b_bytes = other.obj_bytes();
-        zelf.desc.zip_eq(&other.desc, false, |a_range, b_range| {
-            let a_range = (a_range.start + zelf.start as isize) as usize
-                ..(a_range.end + zelf.start as isize) as usize;
-            let b_range = b_range.start as usize..b_range.end as usize;
-            let a_val = match format_unpack(a_format_spec, &a_bytes[a_range], vm) {
-                Ok(val) => val,
-                Err(e) => {
-                    ret = Err(e);
-                    return true;
-                }
-            };
-            let b_val = match format_unpack(b_format_spec, &b_bytes[b_range], vm) {
-                Ok(val) => val,
-                Err(e) => {
-                    ret = Err(e);
-                    return true;
+        zelf.desc
+            .zip_eq(&other.desc, BufferOrder::C, false, |a_range, b_range| {
+                let a_range = (a_range.start + zelf.start as isize) as usize
+                    ..(a_range.end + zelf.start as isize) as usize;
+                let b_range = b_range.start as usize..b_range.end as usize;
+                let a_val = match a_format_spec.unpack_item(&a_bytes[a_range], vm) {
+                    Ok(val) => val,
+                    Err(e) => {
+                        ret = Err(e);
+                        return true;
+                    }
+                };
+                let b_val = match b_format_spec.unpack_item(&b_bytes[b_range], vm) {
+                    Ok(val) => val,
+                    Err(e) => {
+                        ret = Err(e);
+                        return true;
+                    }
+                };
+                ret = vm.bool_eq(&a_val, &b_val);
+                if let Ok(b) = ret {
+                    !b
+                } else {
+                    true
                 }
-            };
-            ret = vm.bool_eq(&a_val, &b_val);
-            if let Ok(b) = ret {
-                !b
-            } else {
-                true
-            }
-        });
+            });
         ret
     }
 
     fn obj_bytes(&self) -> BorrowedValue<[u8]> {
-        if self.desc.is_contiguous() {
+        if self.desc.is_contiguous(BufferOrder::C) {
             BorrowedValue::map(self.buffer.obj_bytes(), |x| {
                 &x[self.start..self.start + self.desc.len]
             })
@@ -400,7 +408,7 @@ impl PyMemoryView {
     }
 
     fn obj_bytes_mut(&self) -> BorrowedValueMut<[u8]> {
-        if self.desc.is_contiguous() {
+        if self.desc.is_contiguous(BufferOrder::C) {
             BorrowedValueMut::map(self.buffer.obj_bytes_mut(), |x| {
                 &mut x[self.start..self.start + self.desc.len]
             })
@@ -410,7 +418,7 @@ impl PyMemoryView {
     }
 
     fn as_contiguous(&self) -> Option<BorrowedValue<[u8]>> {
-        self.desc.is_contiguous().then(|| {
+        self.desc.is_contiguous(BufferOrder::C).then(|| {
             BorrowedValue::map(self.buffer.obj_bytes(), |x| {
                 &x[self.start..self.start + self.desc.len]
             })
@@ -418,7 +426,7 @@ impl PyMemoryView {
     }
 
     fn _as_contiguous_mut(&self) -> Option<BorrowedValueMut<[u8]>> {
-        self.desc.is_contiguous().then(|| {
+        self.desc.is_contiguous(BufferOrder::C).then(|| {
             BorrowedValueMut::map(self.buffer.obj_bytes_mut(), |x| {
                 &mut x[self.start..self.start + self.desc.len]
             })
@@ -431,11 +439,10 @@ impl PyMemoryView {
         } else {
             buf.reserve(self.desc.len);
             let bytes = &*self.buffer.obj_bytes();
-            self.desc.for_each_segment(true, |range| {
-                let start = (range.start + self.start as isize) as usize;
-                let end = (range.end + self.start as isize) as usize;
-                buf.extend_from_slice(&bytes[start..end]);
-            })
+            self.desc
+                .for_each_segment(bytes, self.start as isize, BufferOrder::C, true, |range| {
+                    buf.extend_from_slice(&bytes[range.start as usize..range.end as usize]);
+                })
         }
     }
 
@@ -467,10 +474,10 @@ impl PyMemoryView {
 
         let mut dim_desc = self.desc.dim_desc.clone();
         dim_desc.last_mut().unwrap().1 = self.desc.itemsize as isize;
-        dim_desc.last_mut().unwrap().2 = 0;
+        dim_desc.last_mut().unwrap().2 = -1;
         for i in (0..dim_desc.len() - 1).rev() {
             dim_desc[i].1 = dim_desc[i + 1].1 * dim_desc[i + 1].0 as isize;
-            dim_desc[i].2 = 0;
+            dim_desc[i].2 = -1;
         }
 
         let desc = BufferDescriptor {
@@ -523,21 +530,22 @@ impl Py<PyMemoryView> {
             PyBuffer::try_from_object(vm, src)?
         };
 
-        if !is_equiv_structure(&src.desc, &dest.desc) {
-            return Err(vm.new_value_error(
+        let src_desc = dest.desc.broadcast(&src.desc).ok_or_else(|| {
+            vm.new_value_error(
                 "memoryview assignment: lvalue and rvalue have different structures".to_owned(),
-            ));
-        }
+            )
+        })?;
 
         let mut bytes_mut = dest.buffer.obj_bytes_mut();
         let src_bytes = src.obj_bytes();
-        dest.desc.zip_eq(&src.desc, true, |a_range, b_range| {
-            let a_range = (a_range.start + dest.start as isize) as usize
-                ..(a_range.end + dest.start as isize) as usize;
-            let b_range = b_range.start as usize..b_range.end as usize;
-            bytes_mut[a_range].copy_from_slice(&src_bytes[b_range]);
-            false
-        });
+        dest.desc
+            .zip_eq(&src_desc, BufferOrder::C, true, |a_range, b_range| {
+                let a_range = (a_range.start + dest.start as isize) as usize
+                    ..(a_range.end + dest.start as isize) as usize;
+                let b_range = b_range.start as usize..b_range.end as usize;
+                bytes_mut[a_range].copy_from_slice(&src_bytes[b_range]);
+                false
+            });
 
         Ok(())
     }
@@ -614,6 +622,9 @@ impl PyMemoryView {
     #[pygetset]
     fn suboffsets(&self, vm: &VirtualMachine) -> PyResult<PyTupleRef> {
         self.try_not_released(vm)?;
+        if !self.desc.has_suboffsets() {
+            return Ok(vm.ctx.empty_tuple.clone());
+        }
         Ok(vm.ctx.new_tuple(
             self.desc
                 .dim_desc
@@ -631,19 +642,21 @@ impl PyMemoryView {
 
     #[pygetset]
     fn contiguous(&self, vm: &VirtualMachine) -> PyResult<bool> {
-        self.try_not_released(vm).map(|_| self.desc.is_contiguous())
+        self.try_not_released(vm).map(|_| {
+            self.desc.is_contiguous(BufferOrder::C) || self.desc.is_contiguous(BufferOrder::F)
+        })
     }
 
     #[pygetset]
     fn c_contiguous(&self, vm: &VirtualMachine) -> PyResult<bool> {
-        self.try_not_released(vm).map(|_| self.desc.is_contiguous())
+        self.try_not_released(vm)
+            .map(|_| self.desc.is_contiguous(BufferOrder::C))
     }
 
     #[pygetset]
     fn f_contiguous(&self, vm: &VirtualMachine) -> PyResult<bool> {
-        // TODO: fortain order
         self.try_not_released(vm)
-            .map(|_| self.desc.ndim() <= 1 && self.desc.is_contiguous())
+            .map(|_| self.desc.is_contiguous(BufferOrder::F))
     }
 
     #[pymethod(magic)]
@@ -711,13 +724,11 @@ impl PyMemoryView {
         self.try_not_released(vm)?;
         let bytes = self.buffer.obj_bytes();
         if self.desc.ndim() == 0 {
-            return Ok(vm.ctx.new_list(vec![format_unpack(
-                &self.format_spec,
-                &bytes[..self.desc.itemsize],
-                vm,
-            )?]));
+            return Ok(vm.ctx.new_list(vec![self
+                .format_spec
+                .unpack_item(&bytes[..self.desc.itemsize], vm)?]));
         }
-        self._to_list(&bytes, 0, 0, vm)
+        self._to_list(&bytes, self.start as isize, 0, vm)
     }
 
     #[pymethod]
@@ -758,7 +769,7 @@ impl PyMemoryView {
                 readonly: self.desc.readonly,
                 itemsize,
                 format: format.to_string().into(),
-                dim_desc: vec![(self.desc.len / itemsize, itemsize as isize, 0)],
+                dim_desc: vec![(self.desc.len / itemsize, itemsize as isize, -1)],
             },
             hash: OnceCell::new(),
         })
@@ -767,7 +778,7 @@ impl PyMemoryView {
     #[pymethod]
     fn cast(&self, args: CastArgs, vm: &VirtualMachine) -> PyResult<PyRef<Self>> {
         self.try_not_released(vm)?;
-        if !self.desc.is_contiguous() {
+        if !self.desc.is_contiguous(BufferOrder::C) {
             return Err(vm.new_type_error(
                 "memoryview: casts are restricted to C-contiguous views".to_owned(),
             ));
@@ -798,18 +809,25 @@ impl PyMemoryView {
             };
 
             let shape_ndim = shape.len();
-            // TODO: MAX_NDIM
-            if self.desc.ndim() != 1 && shape_ndim != 1 {
-                return Err(
-                    vm.new_type_error("memoryview: cast must be 1D -> ND or ND -> 1D".to_owned())
-                );
+            if shape_ndim > MAX_NDIM {
+                return Err(vm.new_value_error(format!(
+                    "memoryview: number of dimensions must not exceed {MAX_NDIM}"
+                )));
             }
 
+            // the view is C-contiguous (checked above), so any source shape can
+            // be flattened and reinterpreted with the requested one
             let mut other = self.cast_to_1d(format, vm)?;
             let itemsize = other.desc.itemsize;
 
             // 0 ndim is single item
             if shape_ndim == 0 {
+                if other.desc.len != itemsize {
+                    return Err(vm.new_type_error(format!(
+                        "memoryview: cannot cast view of length {} to 0-dim",
+                        other.desc.len
+                    )));
+                }
                 other.desc.dim_desc = vec![];
                 other.desc.len = itemsize;
                 return Ok(other.into_ref(&vm.ctx));
@@ -827,7 +845,7 @@ impl PyMemoryView {
                     ));
                 }
                 product_shape *= x;
-                dim_descriptor.push((x, 0, 0));
+                dim_descriptor.push((x, 0, -1));
             }
 
             dim_descriptor.last_mut().unwrap().1 = itemsize as isize;
@@ -942,33 +960,30 @@ impl TryFromObject for SubscriptNeedle {
     }
 }
 
-static BUFFER_METHODS: BufferMethods = BufferMethods {
-    obj_bytes: |buffer| buffer.obj_as::<PyMemoryView>().obj_bytes(),
-    obj_bytes_mut: |buffer| buffer.obj_as::<PyMemoryView>().obj_bytes_mut(),
-    release: |buffer| buffer.obj_as::<PyMemoryView>().buffer.release(),
-    retain: |buffer| buffer.obj_as::<PyMemoryView>().buffer.retain(),
-};
+impl BufferInternal for PyMemoryView {
+    fn obj_bytes(&self) -> BorrowedValue<[u8]> {
+        PyMemoryView::obj_bytes(self)
+    }
+
+    fn obj_bytes_mut(&self) -> BorrowedValueMut<[u8]> {
+        PyMemoryView::obj_bytes_mut(self)
+    }
+
+    fn release(&self) {
+        self.buffer.internal().release()
+    }
+
+    fn retain(&self) {
+        self.buffer.internal().retain()
+    }
+}
 
 impl AsBuffer for PyMemoryView {
     fn as_buffer(zelf: &Py<Self>, vm: &VirtualMachine) -> PyResult<PyBuffer> {
         if zelf.released.load() {
             Err(vm.new_value_error("operation forbidden on released memoryview object".to_owned()))
         } else {
-            Ok(PyBuffer::new(
-                zelf.to_owned().into(),
-                zelf.desc.clone(),
-                &BUFFER_METHODS,
-            ))
-        }
-    }
-}
-
-impl Drop for PyMemoryView {
-    fn drop(&mut self) {
-        if self.released.load() {
-            unsafe { self.buffer.drop_without_release() };
-        } else {
-            unsafe { ManuallyDrop::drop(&mut self.buffer) };
+            Ok(PyBuffer::new(zelf.to_owned(), zelf.desc.clone()))
         }
     }
 }
@@ -1074,20 +1089,6 @@ pub(crate) fn init(ctx: &Context) {
     PyMemoryViewIterator::extend_class(ctx, ctx.types.memoryviewiterator_type);
 }
 
-fn format_unpack(
-    format_spec: &FormatSpec,
-    bytes: &[u8],
-    vm: &VirtualMachine,
-) -> PyResult<PyObjectRef> {
-    format_spec.unpack(bytes, vm).map(|x| {
-        if x.len() == 1 {
-            x.fast_getitem(0)
-        } else {
-            x.into()
-        }
-    })
-}
-
 fn is_equiv_shape(a: &BufferDescriptor, b: &BufferDescriptor) -> bool {
     if a.ndim() != b.ndim() {
         return false;
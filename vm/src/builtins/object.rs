@@ -173,7 +173,7 @@ impl PyBaseObject {
     }
 
     #[pyslot]
-    fn slot_setattro(
+    pub(crate) fn slot_setattro(
         obj: &PyObject,
         attr_name: &Py<PyStr>,
         value: PySetterValue,
@@ -427,10 +427,13 @@ impl PyStr {
 
     fn _getitem(&self, needle: &PyObject, vm: &VirtualMachine) -> PyResult {
         match SequenceIndex::try_from_borrowed_object(vm, needle, "str")? {
-            SequenceIndex::Int(i) => self.getitem_by_index(vm, i).map(|x| x.to_string()),
-            SequenceIndex::Slice(slice) => self.getitem_by_slice(vm, slice),
+            SequenceIndex::Int(i) => self
+                .getitem_by_index(vm, i)
+                .map(|c| vm.ctx.new_char(c).into()),
+            SequenceIndex::Slice(slice) => self
+                .getitem_by_slice(vm, slice)
+                .map(|x| self.new_substr(x).into_ref(&vm.ctx).into()),
         }
-        .map(|x| self.new_substr(x).into_ref(&vm.ctx).into())
     }
 
     #[pymethod(magic)]
@@ -1426,7 +1429,7 @@ impl ToPyObject for String {
 
 impl ToPyObject for char {
     fn to_pyobject(self, vm: &VirtualMachine) -> PyObjectRef {
-        vm.ctx.new_str(self.to_string()).into()
+        vm.ctx.new_char(self).into()
     }
 }
 
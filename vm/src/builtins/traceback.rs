@@ -1,7 +1,9 @@
 use rustpython_common::lock::PyMutex;
 
 use super::PyType;
-use crate::{class::PyClassImpl, frame::FrameRef, Context, Py, PyPayload, PyRef};
+use crate::{
+    bytecode::Location, class::PyClassImpl, frame::FrameRef, Context, Py, PyPayload, PyRef,
+};
 
 #[pyclass(module = false, name = "traceback", trace)]
 #[derive(Debug)]
@@ -9,7 +11,8 @@ pub struct PyTraceback {
     pub next: PyMutex<Option<PyTracebackRef>>,
     pub frame: FrameRef,
     pub lasti: u32,
-    pub lineno: usize,
+    /// source span of the instruction this entry points at (PEP 657)
+    pub location: Location,
 }
 
 #[cfg(feature = "gc_bacon")]
@@ -29,12 +32,12 @@ impl PyPayload for PyTraceback {
 
 #[pyclass]
 impl PyTraceback {
-    pub fn new(next: Option<PyRef<Self>>, frame: FrameRef, lasti: u32, lineno: usize) -> Self {
+    pub fn new(next: Option<PyRef<Self>>, frame: FrameRef, lasti: u32, location: Location) -> Self {
         PyTraceback {
             next: PyMutex::new(next),
             frame,
             lasti,
-            lineno,
+            location,
         }
     }
 
@@ -50,7 +53,7 @@ impl PyTraceback {
 
     #[pygetset]
     fn tb_lineno(&self) -> usize {
-        self.lineno
+        self.location.row()
     }
 
     #[pygetset]
@@ -81,7 +84,7 @@ impl serde::Serialize for PyTraceback {
 
         let mut struc = s.serialize_struct("PyTraceback", 3)?;
         struc.serialize_field("name", self.frame.code.obj_name.as_str())?;
-        struc.serialize_field("lineno", &self.lineno)?;
+        struc.serialize_field("lineno", &self.location.row())?;
         struc.serialize_field("filename", self.frame.code.source_path.as_str())?;
         struc.end()
     }
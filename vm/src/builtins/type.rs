@@ -217,7 +217,8 @@ impl PyType {
 
         let weakref_type = super::PyWeak::static_type();
         for base in &new_type.bases {
-            base.subclasses.write().push(
+            Self::attach_subclass(
+                base,
                 new_type
                     .as_object()
                     .downgrade_with_weakref_typ_opt(None, weakref_type.to_owned())
@@ -231,15 +232,31 @@ impl PyType {
     pub fn new_static(
         base: PyRef<Self>,
         attrs: PyAttributes,
+        slots: PyTypeSlots,
+        metaclass: PyRef<Self>,
+    ) -> Result<PyRef<Self>, String> {
+        Self::new_static_with_bases(vec![base], attrs, slots, metaclass)
+    }
+
+    /// Like [`Self::new_static`], but linearising an explicit list of bases;
+    /// needed for the few builtin types with multiple inheritance, such as
+    /// `ExceptionGroup(BaseExceptionGroup, Exception)`.
+    pub fn new_static_with_bases(
+        bases: Vec<PyRef<Self>>,
+        attrs: PyAttributes,
         mut slots: PyTypeSlots,
         metaclass: PyRef<Self>,
     ) -> Result<PyRef<Self>, String> {
+        let base = bases[0].clone();
         if base.slots.flags.has_feature(PyTypeFlags::HAS_DICT) {
             slots.flags |= PyTypeFlags::HAS_DICT
         }
 
-        let bases = vec![base.clone()];
-        let mro = base.iter_mro().map(|x| x.to_owned()).collect();
+        let mros = bases
+            .iter()
+            .map(|x| x.iter_mro().map(|x| x.to_owned()).collect())
+            .collect();
+        let mro = linearise_mro(mros)?;
 
         let new_type = PyRef::new_ref(
             PyType {
@@ -257,7 +274,8 @@ impl PyType {
 
         let weakref_type = super::PyWeak::static_type();
         for base in &new_type.bases {
-            base.subclasses.write().push(
+            Self::attach_subclass(
+                base,
                 new_type
                     .as_object()
                     .downgrade_with_weakref_typ_opt(None, weakref_type.to_owned())
@@ -268,6 +286,52 @@ impl PyType {
         Ok(new_type)
     }
 
+    /// register a new subclass in `base.__subclasses__`, pruning weakrefs whose
+    /// referent is already collected so that defining many short-lived classes
+    /// doesn't accumulate dead entries until the next `__subclasses__` call
+    fn attach_subclass(base: &Py<PyType>, subclass: PyRef<PyWeak>) {
+        let mut subclasses = base.subclasses.write();
+        // amortize the O(n) prune by only doing it when the list has doubled,
+        // so repeated class creation stays O(1) per class
+        if subclasses.len() >= 8 && subclasses.len().is_power_of_two() {
+            subclasses.retain(|x| x.upgrade().is_some());
+        }
+        subclasses.push(subclass);
+    }
+
+    /// Tag identifying the current state of this type for attribute lookup
+    /// purposes, lazily assigned from a global counter. As long as two loads
+    /// return the same non-zero value, MRO lookups on this type are guaranteed
+    /// to produce the same result, so caches can key on it; 0 means the result
+    /// must not be cached. Modeled after CPython's `tp_version_tag`.
+    pub fn version_tag(&self) -> u32 {
+        let tag = self.slots.version_tag.load();
+        if tag != 0 {
+            tag
+        } else {
+            let tag = next_version_tag();
+            if tag != 0 {
+                self.slots.version_tag.store(tag);
+            }
+            tag
+        }
+    }
+
+    /// Retire the version tag of this type and of every (transitive) subclass,
+    /// invalidating all attribute caches guarded by them. Must be called after
+    /// every mutation of `attributes`; tags are never reused, so caches still
+    /// holding a retired tag simply miss.
+    pub(crate) fn invalidate_version_tags(&self) {
+        self.slots.version_tag.store(0);
+        for subclass in self.subclasses.read().iter() {
+            if let Some(subclass) = subclass.upgrade() {
+                if let Ok(subclass) = subclass.downcast::<Self>() {
+                    subclass.invalidate_version_tags();
+                }
+            }
+        }
+    }
+
     pub(crate) fn init_slots(&self, ctx: &Context) {
         #[allow(clippy::mutable_key_type)]
         let mut slot_name_set = std::collections::HashSet::new();
@@ -322,6 +386,7 @@ impl PyType {
 
     pub fn set_attr(&self, attr_name: &'static PyStrInterned, value: PyObjectRef) {
         self.attributes.write().insert(attr_name, value);
+        self.invalidate_version_tags();
     }
 
     /// This is the internal get_attr implementation for fast lookup on a class.
@@ -547,6 +612,7 @@ impl PyType {
         let __annotations__ = identifier!(vm, __annotations__);
         if let Some(value) = value {
             self.attributes.write().insert(__annotations__, value);
+            self.invalidate_version_tags();
         } else {
             self.attributes
                 .read()
@@ -723,24 +789,6 @@ impl PyType {
             .entry(identifier!(vm, __qualname__))
             .or_insert_with(|| vm.ctx.new_str(name.as_str()).into());
 
-        // All *classes* should have a dict. Exceptions are *instances* of
-        // classes that define __slots__ and instances of built-in classes
-        // (with exceptions, e.g function)
-        let __dict__ = identifier!(vm, __dict__);
-        attributes.entry(__dict__).or_insert_with(|| {
-            vm.ctx
-                .new_getset(
-                    "__dict__",
-                    vm.ctx.types.object_type,
-                    subtype_get_dict,
-                    subtype_set_dict,
-                )
-                .into()
-        });
-
-        // TODO: Flags is currently initialized with HAS_DICT. Should be
-        // updated when __slots__ are supported (toggling the flag off if
-        // a class has __slots__ defined).
         let heaptype_slots: Option<PyTupleTyped<PyStrRef>> =
             if let Some(x) = attributes.get(identifier!(vm, __slots__)) {
                 Some(if x.to_owned().class().is(vm.ctx.types.str_type) {
@@ -763,11 +811,51 @@ impl PyType {
                 None
             };
 
+        // '__dict__' and '__weakref__' may be named in __slots__ to request the
+        // corresponding behavior; neither gets a member slot of its own
+        let member_slots: Vec<PyStrRef> = heaptype_slots
+            .as_ref()
+            .map(|slots| {
+                slots
+                    .as_slice()
+                    .iter()
+                    .filter(|name| name.as_str() != "__dict__" && name.as_str() != "__weakref__")
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let slots_request_dict = heaptype_slots.as_ref().map_or(false, |slots| {
+            slots
+                .as_slice()
+                .iter()
+                .any(|name| name.as_str() == "__dict__")
+        });
+
+        // Instances get a dict unless the class defines __slots__ without
+        // '__dict__' in it (bases with a dict still force one, see
+        // `new_heap_inner`). Classes themselves always have a dict.
+        let instances_have_dict = heaptype_slots.is_none() || slots_request_dict;
+        if instances_have_dict {
+            let __dict__ = identifier!(vm, __dict__);
+            attributes.entry(__dict__).or_insert_with(|| {
+                vm.ctx
+                    .new_getset(
+                        "__dict__",
+                        vm.ctx.types.object_type,
+                        subtype_get_dict,
+                        subtype_set_dict,
+                    )
+                    .into()
+            });
+        }
+
         let base_member_count = base.slots.member_count;
-        let member_count: usize =
-            base.slots.member_count + heaptype_slots.as_ref().map(|x| x.len()).unwrap_or(0);
+        let member_count: usize = base.slots.member_count + member_slots.len();
 
-        let flags = PyTypeFlags::heap_type_flags() | PyTypeFlags::HAS_DICT;
+        let mut flags = PyTypeFlags::heap_type_flags();
+        if instances_have_dict {
+            flags |= PyTypeFlags::HAS_DICT;
+        }
         let (slots, heaptype_ext) = unsafe {
             // # Safety
             // `slots.name` live long enough because `heaptype_ext` is alive.
@@ -795,9 +883,9 @@ impl PyType {
         )
         .map_err(|e| vm.new_type_error(e))?;
 
-        if let Some(ref slots) = heaptype_slots {
+        if !member_slots.is_empty() {
             let mut offset = base_member_count;
-            for member in slots.as_slice() {
+            for member in &member_slots {
                 let member_def = MemberDef {
                     name: member.to_string(),
                     kind: MemberKind::ObjectEx,
@@ -964,6 +1052,19 @@ impl Py<PyType> {
 }
 
 const SIGNATURE_END_MARKER: &str = ")\n--\n\n";
+/// hand out the next type version tag. 0 is reserved to mean "no tag"; should
+/// the counter ever be exhausted it keeps returning 0, which simply disables
+/// the attribute caches instead of wrapping around and reusing tags.
+fn next_version_tag() -> u32 {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    static NEXT_VERSION_TAG: AtomicU32 = AtomicU32::new(1);
+    NEXT_VERSION_TAG
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |tag| {
+            tag.checked_add(1)
+        })
+        .unwrap_or(0)
+}
+
 fn get_signature(doc: &str) -> Option<&str> {
     doc.find(SIGNATURE_END_MARKER).map(|index| &doc[..=index])
 }
@@ -1066,6 +1167,7 @@ impl SetAttr for PyType {
                 ));
             }
         }
+        zelf.invalidate_version_tags();
         if attr_name.as_str().starts_with("__") && attr_name.as_str().ends_with("__") {
             if assign {
                 zelf.update_slot::<true>(attr_name, &vm.ctx);
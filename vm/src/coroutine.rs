@@ -20,6 +20,11 @@ impl ExecutionResult {
                 };
                 PyIterReturn::StopIteration(arg)
             }
+            // calls made from generator frames stay on the recursive path,
+            // and their safepoints never honor suspension requests
+            ExecutionResult::Call(_) | ExecutionResult::Suspended => {
+                unreachable!("generator frames never suspend in a call or at a safepoint")
+            }
         }
     }
 }
@@ -60,8 +65,18 @@ impl Coro {
 
     fn maybe_close(&self, res: &PyResult<ExecutionResult>) {
         match res {
-            Ok(ExecutionResult::Return(_)) | Err(_) => self.closed.store(true),
+            Ok(ExecutionResult::Return(_)) | Err(_) => {
+                // `running` is still set when `res` is the "already executing"
+                // error of a reentrant send/throw; the original run is not
+                // done and its own result decides whether the generator closes
+                if !self.running.load() {
+                    self.closed.store(true);
+                }
+            }
             Ok(ExecutionResult::Yield(_)) => {}
+            Ok(ExecutionResult::Call(_)) | Ok(ExecutionResult::Suspended) => {
+                unreachable!("generator frames never suspend in a call or at a safepoint")
+            }
         }
     }
 
@@ -158,7 +173,11 @@ impl Coro {
                 vm.ctx.none(),
             )
         });
-        self.closed.store(true);
+        // a reentrant close fails with "already executing" and must not mark
+        // the still-running generator closed
+        if !self.running.load() {
+            self.closed.store(true);
+        }
         match result {
             Ok(ExecutionResult::Yield(_)) => {
                 Err(vm.new_runtime_error(format!("{} ignored GeneratorExit", gen_name(gen, vm))))
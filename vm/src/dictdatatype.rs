@@ -8,10 +8,11 @@ use crate::common::{
     lock::{PyRwLock, PyRwLockReadGuard, PyRwLockWriteGuard},
 };
 use crate::{
-    builtins::{PyInt, PyStr, PyStrInterned, PyStrRef},
+    builtins::{PyBytes, PyInt, PyStr, PyStrInterned, PyStrRef},
     convert::ToPyObject,
     AsObject, Py, PyExact, PyObject, PyObjectRef, PyRefExact, PyResult, VirtualMachine,
 };
+use num_bigint::BigInt;
 use num_traits::ToPrimitive;
 use std::{fmt, mem::size_of, ops::ControlFlow};
 
@@ -68,14 +69,165 @@ impl IndexEntry {
     }
 }
 
+/// Storage for one slot of the index table, at one of four widths. The top
+/// two values of each width are reserved for the sentinels; everything below
+/// them is an entry index.
+trait IndexSlot: Copy + Eq {
+    const FREE: Self;
+    const DUMMY: Self;
+    fn from_entry(entry: IndexEntry) -> Self;
+    fn to_entry(self) -> IndexEntry;
+}
+
+macro_rules! impl_index_slot {
+    ($($ty:ty),*) => {$(
+        impl IndexSlot for $ty {
+            const FREE: Self = <$ty>::MAX;
+            const DUMMY: Self = <$ty>::MAX - 1;
+
+            fn from_entry(entry: IndexEntry) -> Self {
+                if entry == IndexEntry::FREE {
+                    Self::FREE
+                } else if entry == IndexEntry::DUMMY {
+                    Self::DUMMY
+                } else {
+                    debug_assert!((entry.0 as u64) < u64::from(Self::DUMMY));
+                    entry.0 as $ty
+                }
+            }
+
+            fn to_entry(self) -> IndexEntry {
+                if self == Self::FREE {
+                    IndexEntry::FREE
+                } else if self == Self::DUMMY {
+                    IndexEntry::DUMMY
+                } else {
+                    IndexEntry(self as i64)
+                }
+            }
+        }
+    )*};
+}
+impl_index_slot!(u8, u16, u32, u64);
+
+/// How many entries a dict holds before it grows a probe table. Linear scans
+/// over a handful of entries beat hashing, and the many tiny dicts made for
+/// kwargs, annotations and small namespaces never allocate a table at all.
+const INLINE_CAPACITY: usize = 8;
+
+/// The index table: a power-of-two probe table mapping hash slots to entry
+/// indices, stored at the narrowest width the table size allows, like
+/// CPython's `dk_indices`. A small dict pays one byte per slot instead of
+/// eight; the probing sequence (see [`GenIndexes`]) is unaffected.
+///
+/// Dicts start out `Inline` with no table at all: entries are scanned
+/// linearly and an entry's slot is simply its position, until the
+/// [`INLINE_CAPACITY`]th push switches to the hashed layout for good.
+#[derive(Clone)]
+enum Indices {
+    Inline,
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+    U64(Vec<u64>),
+}
+
+macro_rules! with_slots {
+    ($indices:expr, $slots:ident => $body:expr, inline => $inline:expr) => {
+        match $indices {
+            Indices::Inline => $inline,
+            Indices::U8($slots) => $body,
+            Indices::U16($slots) => $body,
+            Indices::U32($slots) => $body,
+            Indices::U64($slots) => $body,
+        }
+    };
+}
+
+impl Indices {
+    /// A table of `size` slots, all free. Entry indices stay well below
+    /// twice the table size (compaction bounds the tombstones), so each
+    /// width is chosen with headroom for that and for the two sentinels.
+    fn new(size: usize) -> Self {
+        if size <= 1 << 6 {
+            Indices::U8(vec![<u8 as IndexSlot>::FREE; size])
+        } else if size <= 1 << 14 {
+            Indices::U16(vec![<u16 as IndexSlot>::FREE; size])
+        } else if size <= 1 << 30 {
+            Indices::U32(vec![<u32 as IndexSlot>::FREE; size])
+        } else {
+            Indices::U64(vec![<u64 as IndexSlot>::FREE; size])
+        }
+    }
+
+    fn len(&self) -> usize {
+        with_slots!(self, slots => slots.len(), inline => 0)
+    }
+
+    /// # Safety
+    /// `index` must be within the table
+    unsafe fn get_unchecked(&self, index: usize) -> IndexEntry {
+        with_slots!(
+            self,
+            slots => slots.get_unchecked(index).to_entry(),
+            inline => unreachable!("inline dicts are probed by scanning the entries")
+        )
+    }
+
+    /// # Safety
+    /// `index` must be within the table
+    unsafe fn set_unchecked(&mut self, index: usize, entry: IndexEntry) {
+        with_slots!(
+            self,
+            slots => *slots.get_unchecked_mut(index) = IndexSlot::from_entry(entry),
+            // an inline entry's slot is its position; nothing to update
+            inline => {}
+        )
+    }
+
+    fn set(&mut self, index: usize, entry: IndexEntry) {
+        with_slots!(
+            self,
+            slots => slots[index] = IndexSlot::from_entry(entry),
+            // an inline entry's slot is its position; nothing to update
+            inline => {}
+        )
+    }
+
+    /// heap bytes used by the table
+    fn sizeof(&self) -> usize {
+        with_slots!(self, slots => std::mem::size_of_val(&slots[..]), inline => 0)
+    }
+}
+
 #[derive(Clone)]
 struct DictInner<T> {
     used: usize,
     filled: usize,
-    indices: Vec<IndexEntry>,
+    version: u64,
+    /// bumped whenever the key set changes (insert of a new key, deletion,
+    /// clear) but not on value overwrites, so iterators can tell harmless
+    /// mutation from the kind that moves entries under their feet
+    generation: u64,
+    /// whether every key is an exact `str`: comparing such keys can't run
+    /// Python code, so lookups may use plain string equality under the lock.
+    /// Inserting any other kind of key retires the fast path for good.
+    all_str_keys: bool,
+    indices: Indices,
     entries: Vec<Option<DictEntry<T>>>,
 }
 
+/// Dict version tags are handed out from a single global counter, so a given
+/// value identifies one content state of one dict: two dicts never share a
+/// version unless one was cloned from the other while holding exactly that
+/// content. Caches keying on a version (see `LoadGlobal` in `frame.rs`) are
+/// therefore sound without also checking dict identity; tags are never reused.
+fn next_dict_version() -> u64 {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static NEXT_DICT_VERSION: AtomicU64 = AtomicU64::new(1);
+    NEXT_DICT_VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
 #[cfg(feature = "gc_bacon")]
 unsafe impl<T: crate::object::gc::Trace> crate::object::gc::Trace for DictInner<T> {
     fn trace(&self, tracer_fn: &mut crate::object::gc::TracerFn) {
@@ -105,13 +257,40 @@ impl<T> Default for Dict<T> {
             inner: PyRwLock::new(DictInner {
                 used: 0,
                 filled: 0,
-                indices: vec![IndexEntry::FREE; 8],
+                version: next_dict_version(),
+                generation: 0,
+                all_str_keys: true,
+                indices: Indices::Inline,
                 entries: Vec::new(),
             }),
         }
     }
 }
 
+impl<T> Dict<T> {
+    /// A dict presized for `capacity` entries, so displays and literals of
+    /// known size never resize while being filled.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let indices = if capacity <= INLINE_CAPACITY {
+            Indices::Inline
+        } else {
+            // the load factor `should_resize` maintains
+            Indices::new((capacity * 2).next_power_of_two())
+        };
+        Self {
+            inner: PyRwLock::new(DictInner {
+                used: 0,
+                filled: 0,
+                version: next_dict_version(),
+                generation: 0,
+                all_str_keys: true,
+                indices,
+                entries: Vec::with_capacity(capacity),
+            }),
+        }
+    }
+}
+
 #[derive(Clone)]
 struct DictEntry<T> {
     hash: HashValue,
@@ -127,6 +306,7 @@ pub struct DictSize {
     pub entries_size: usize,
     pub used: usize,
     filled: usize,
+    generation: u64,
 }
 
 struct GenIndexes {
@@ -164,7 +344,7 @@ impl<T> DictInner<T> {
             }
             i
         };
-        self.indices = vec![IndexEntry::FREE; new_size];
+        self.indices = Indices::new(new_size);
         let mask = (new_size - 1) as i64;
         for (entry_idx, entry) in self.entries.iter_mut().enumerate() {
             if let Some(entry) = entry {
@@ -175,9 +355,11 @@ impl<T> DictInner<T> {
                         // Safety: index is always valid here
                         // index_index is generated by idxs
                         // entry_idx is saved one
-                        let idx = self.indices.get_unchecked_mut(index_index);
-                        if *idx == IndexEntry::FREE {
-                            *idx = IndexEntry::from_index_unchecked(entry_idx);
+                        if self.indices.get_unchecked(index_index) == IndexEntry::FREE {
+                            self.indices.set_unchecked(
+                                index_index,
+                                IndexEntry::from_index_unchecked(entry_idx),
+                            );
                             entry.index = index_index;
                             break;
                         }
@@ -190,28 +372,65 @@ impl<T> DictInner<T> {
         self.filled = self.used;
     }
 
+    /// Rebuild `entries` without the `None` tombstones deletions leave
+    /// behind, re-deriving the indices for the compacted order. Without this
+    /// a dict cycling through keys — an LRU cache, say — grows its entry
+    /// table without bound and slows every iteration down.
+    fn compact(&mut self) {
+        self.entries.retain(Option::is_some);
+        self.resize(self.indices.len());
+    }
+
+    /// compact once tombstones outnumber the live entries, but leave small
+    /// tables alone: rebuilding those costs more than scanning them
+    fn maybe_compact(&mut self) {
+        if self.entries.len() > 16 && self.entries.len() > self.used * 2 {
+            self.compact();
+        }
+    }
+
     fn unchecked_push(
         &mut self,
+        vm: &VirtualMachine,
         index: IndexIndex,
         hash_value: HashValue,
         key: PyObjectRef,
         value: T,
         index_entry: IndexEntry,
     ) {
+        if self.all_str_keys && str_exact(&key, vm).is_none() {
+            self.all_str_keys = false;
+        }
+        let entry_index = self.entries.len();
+        let inline = matches!(self.indices, Indices::Inline);
+        // an inline entry's slot is its position in `entries`
+        let index = if inline { entry_index } else { index };
         let entry = DictEntry {
             hash: hash_value,
             key,
             value,
             index,
         };
-        let entry_index = self.entries.len();
+        if inline && self.entries.capacity() == 0 {
+            // a tiny dict makes its one allocation here
+            self.entries.reserve_exact(INLINE_CAPACITY);
+        }
         self.entries.push(Some(entry));
-        self.indices[index] = unsafe {
+        self.used += 1;
+        self.generation += 1;
+        if inline {
+            self.filled = self.used;
+            if self.entries.len() > INLINE_CAPACITY {
+                self.resize(INLINE_CAPACITY * 2)
+            }
+            return;
+        }
+        let index_value = unsafe {
             // SAFETY: entry_index is self.entries.len(). it never can
             // grow to `usize-2` because hash tables cannot full its index
             IndexEntry::from_index_unchecked(entry_index)
         };
-        self.used += 1;
+        self.indices.set(index, index_value);
         if let IndexEntry::FREE = index_entry {
             self.filled += 1;
             if let Some(new_size) = self.should_resize() {
@@ -220,12 +439,154 @@ impl<T> DictInner<T> {
         }
     }
 
+    /// Insert a pair while the write lock is already held, returning any
+    /// overwritten value. Gives the pair back as `Err` instead when matching
+    /// it against an existing key would have to run Python code, which must
+    /// not happen under the lock.
+    fn push_in_lock(
+        &mut self,
+        vm: &VirtualMachine,
+        hash: HashValue,
+        key: PyObjectRef,
+        value: T,
+    ) -> Result<Option<T>, (PyObjectRef, T)> {
+        if matches!(self.indices, Indices::Inline) {
+            return self.push_in_lock_inline(vm, hash, key, value);
+        }
+        let mut idxs = GenIndexes::new(hash, (self.indices.len() - 1) as i64);
+        let mut free_slot = None;
+        let (entry_index, index_index) = loop {
+            let index_index = idxs.next();
+            let index_entry = unsafe {
+                // Safety: index_index is generated
+                self.indices.get_unchecked(index_index)
+            };
+            match index_entry {
+                IndexEntry::DUMMY => {
+                    if free_slot.is_none() {
+                        free_slot = Some(index_index);
+                    }
+                }
+                IndexEntry::FREE => {
+                    break match free_slot {
+                        Some(free) => (IndexEntry::DUMMY, free),
+                        None => (IndexEntry::FREE, index_index),
+                    };
+                }
+                idx => {
+                    let entry = unsafe {
+                        // Safety: DUMMY and FREE are already handled above.
+                        // i is always valid and entry always exists.
+                        let i = idx.index().unwrap_unchecked();
+                        self.entries.get_unchecked(i).as_ref().unwrap_unchecked()
+                    };
+                    if key.is(&entry.key) {
+                        break (idx, index_index);
+                    } else if entry.hash == hash {
+                        let equal = match (self.all_str_keys, str_exact(&key, vm)) {
+                            (true, Some(key_str)) => {
+                                let entry_str = unsafe {
+                                    // Safety: all_str_keys means every key
+                                    // in this dict is an exact str
+                                    entry.key.payload::<PyStr>().unwrap_unchecked()
+                                };
+                                Some(entry_str.as_str() == key_str.as_str())
+                            }
+                            _ => None,
+                        };
+                        match equal {
+                            Some(true) => break (idx, index_index),
+                            Some(false) => {
+                                // hash collision, probe on
+                            }
+                            None => return Err((key, value)),
+                        }
+                    } else {
+                        // entry mismatch
+                    }
+                }
+            }
+        };
+        let removed = if let Some(index) = entry_index.index() {
+            let entry = unsafe {
+                // Safety: the probe above just found this live entry
+                self.entries
+                    .get_unchecked_mut(index)
+                    .as_mut()
+                    .unwrap_unchecked()
+            };
+            debug_assert_eq!(entry.index, index_index);
+            Some(std::mem::replace(&mut entry.value, value))
+        } else {
+            self.unchecked_push(vm, index_index, hash, key, value, entry_index);
+            None
+        };
+        Ok(removed)
+    }
+
+    /// [`Self::push_in_lock`] for the inline representation: no probe table,
+    /// scan the entries in insertion order.
+    fn push_in_lock_inline(
+        &mut self,
+        vm: &VirtualMachine,
+        hash: HashValue,
+        key: PyObjectRef,
+        value: T,
+    ) -> Result<Option<T>, (PyObjectRef, T)> {
+        let mut found = None;
+        for (i, slot) in self.entries.iter().enumerate() {
+            let Some(entry) = slot else { continue };
+            if key.is(&entry.key) {
+                found = Some(i);
+                break;
+            }
+            if entry.hash == hash {
+                let equal = match (self.all_str_keys, str_exact(&key, vm)) {
+                    (true, Some(key_str)) => {
+                        let entry_str = unsafe {
+                            // Safety: all_str_keys means every key in this
+                            // dict is an exact str
+                            entry.key.payload::<PyStr>().unwrap_unchecked()
+                        };
+                        Some(entry_str.as_str() == key_str.as_str())
+                    }
+                    _ => None,
+                };
+                match equal {
+                    Some(true) => {
+                        found = Some(i);
+                        break;
+                    }
+                    Some(false) => {
+                        // hash collision, scan on
+                    }
+                    None => return Err((key, value)),
+                }
+            }
+        }
+        let removed = if let Some(index) = found {
+            let entry = unsafe {
+                // Safety: the scan above just found this live entry
+                self.entries
+                    .get_unchecked_mut(index)
+                    .as_mut()
+                    .unwrap_unchecked()
+            };
+            Some(std::mem::replace(&mut entry.value, value))
+        } else {
+            self.unchecked_push(vm, 0, hash, key, value, IndexEntry::FREE);
+            None
+        };
+        Ok(removed)
+    }
+
     fn size(&self) -> DictSize {
         DictSize {
             indices_size: self.indices.len(),
             entries_size: self.entries.len(),
             used: self.used,
             filled: self.filled,
+            generation: self.generation,
         }
     }
 
@@ -254,8 +615,19 @@ impl<T: Clone> Dict<T> {
         self.inner.read()
     }
 
+    /// Acquire the inner write lock, retiring the current version tag: any
+    /// write access may change the content, so lookups memoized under the old
+    /// version must not be served anymore. Bumping on operations that end up
+    /// not modifying anything only costs a spurious cache miss.
     fn write(&self) -> PyRwLockWriteGuard<'_, DictInner<T>> {
-        self.inner.write()
+        let mut inner = self.inner.write();
+        inner.version = next_dict_version();
+        inner
+    }
+
+    /// version tag of the current content, see [`next_dict_version`]
+    pub fn version(&self) -> u64 {
+        self.read().version
     }
 
     /// Store a key
@@ -264,6 +636,20 @@ impl<T: Clone> Dict<T> {
         K: DictKey + ?Sized,
     {
         let hash = key.key_hash(vm)?;
+        self.insert_hashed(vm, key, hash, value)
+    }
+
+    /// [`Self::insert`] with the key's hash already computed
+    fn insert_hashed<K>(
+        &self,
+        vm: &VirtualMachine,
+        key: &K,
+        hash: HashValue,
+        value: T,
+    ) -> PyResult<()>
+    where
+        K: DictKey + ?Sized,
+    {
         let _removed = loop {
             let (entry_index, index_index) = self.lookup(vm, key, hash, None)?;
             let mut inner = self.write();
@@ -289,31 +675,112 @@ impl<T: Clone> Dict<T> {
                 }
             } else {
                 // New key:
-                inner.unchecked_push(index_index, hash, key.to_pyobject(vm), value, entry_index);
+                inner.unchecked_push(
+                    vm,
+                    index_index,
+                    hash,
+                    key.to_pyobject(vm),
+                    value,
+                    entry_index,
+                );
                 break None;
             }
         };
         Ok(())
     }
 
+    /// Store every pair from `iter`, hashing each key up front and taking
+    /// the write lock once for the whole batch instead of once per key.
+    /// From the first pair whose insertion would have to run Python code to
+    /// decide key equality, the remainder is finished pair by pair outside
+    /// the lock, preserving insertion order.
+    pub fn extend_from_iter<I>(&self, vm: &VirtualMachine, iter: I) -> PyResult<()>
+    where
+        I: IntoIterator<Item = (PyObjectRef, T)>,
+    {
+        // hashing may run `__hash__`, so it has to happen outside the lock
+        let items = iter
+            .into_iter()
+            .map(|(key, value)| Ok((key.key_hash(vm)?, key, value)))
+            .collect::<PyResult<Vec<_>>>()?;
+        let mut items = items.into_iter();
+        // defer dec RC of overwritten values until the lock is released
+        let mut _removed = Vec::new();
+        let deferred = {
+            let mut inner = self.write();
+            inner.entries.reserve(items.len());
+            if matches!(inner.indices, Indices::Inline) {
+                if inner.entries.len() + items.len() > INLINE_CAPACITY {
+                    let new_size = (inner.used + items.len()) * 2;
+                    inner.resize(new_size);
+                }
+            } else if (inner.filled + items.len()) * 3 > inner.indices.len() * 2 {
+                let new_size = (inner.used + items.len()) * 2;
+                inner.resize(new_size);
+            }
+            loop {
+                let Some((hash, key, value)) = items.next() else {
+                    break None;
+                };
+                match inner.push_in_lock(vm, hash, key, value) {
+                    Ok(old) => _removed.extend(old),
+                    // matching this key against an existing one needs Python
+                    // code: finish it and everything behind it one by one
+                    Err((key, value)) => break Some((hash, key, value)),
+                }
+            }
+        };
+        if let Some((hash, key, value)) = deferred {
+            self.insert_hashed(vm, &*key, hash, value)?;
+            for (hash, key, value) in items {
+                self.insert_hashed(vm, &*key, hash, value)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn contains<K: DictKey + ?Sized>(&self, vm: &VirtualMachine, key: &K) -> PyResult<bool> {
         let (entry, _) = self.lookup(vm, key, key.key_hash(vm)?, None)?;
         Ok(entry.index().is_some())
     }
 
     /// Retrieve a key
-    #[cfg_attr(feature = "flame-it", flame("Dict"))]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     pub fn get<K: DictKey + ?Sized>(&self, vm: &VirtualMachine, key: &K) -> PyResult<Option<T>> {
+        if key.is_interned_str() {
+            // namespace dicts are small and keyed by interned strs: a plain
+            // pointer scan usually resolves attribute and global lookups
+            // before any hashing or probing
+            if let Some(value) = self.get_by_identity_inline(key) {
+                return Ok(Some(value));
+            }
+        }
         let hash = key.key_hash(vm)?;
         self._get_inner(vm, key, hash)
     }
 
+    /// Scan the entries of an inline dict by pointer identity alone;
+    /// `None` means "not proven present", not absence.
+    fn get_by_identity_inline<K: DictKey + ?Sized>(&self, key: &K) -> Option<T> {
+        let inner = self.read();
+        if !matches!(inner.indices, Indices::Inline) {
+            return None;
+        }
+        inner.entries.iter().find_map(|slot| {
+            let entry = slot.as_ref()?;
+            key.key_is(&entry.key).then(|| entry.value.clone())
+        })
+    }
+
     fn _get_inner<K: DictKey + ?Sized>(
         &self,
         vm: &VirtualMachine,
         key: &K,
         hash: HashValue,
     ) -> PyResult<Option<T>> {
+        if let ControlFlow::Break(result) = self.get_optimistic(vm, key, hash) {
+            return Ok(result);
+        }
         let ret = loop {
             let (entry, index_index) = self.lookup(vm, key, hash, None)?;
             if let Some(index) = entry.index() {
@@ -331,6 +798,88 @@ impl<T: Clone> Dict<T> {
         Ok(ret)
     }
 
+    /// Optimistic read: probe and clone the value in one pass under a single
+    /// read guard. `lookup` has to release the lock around comparisons that
+    /// may run Python code and revalidate its position afterwards, costing a
+    /// second acquisition even when no such key exists; here the whole read
+    /// completes under the one guard, which is where uncontended lookups
+    /// spend their time under `threading`. Breaks with the result, or yields
+    /// to the generic two-phase path when a candidate key really does need
+    /// Python code to compare.
+    fn get_optimistic<K: DictKey + ?Sized>(
+        &self,
+        vm: &VirtualMachine,
+        key: &K,
+        hash_value: HashValue,
+    ) -> ControlFlow<Option<T>> {
+        let str_key = key.str_key(vm);
+        let inner = self.read();
+        if matches!(inner.indices, Indices::Inline) {
+            for slot in &inner.entries {
+                let Some(entry) = slot else { continue };
+                if key.key_is(&entry.key) {
+                    return ControlFlow::Break(Some(entry.value.clone()));
+                }
+                if entry.hash == hash_value {
+                    match (inner.all_str_keys, str_key) {
+                        (true, Some(str_key)) => {
+                            let entry_str = unsafe {
+                                // Safety: all_str_keys means every key in
+                                // this dict is an exact str
+                                entry.key.payload::<PyStr>().unwrap_unchecked()
+                            };
+                            if entry_str.as_str() == str_key {
+                                return ControlFlow::Break(Some(entry.value.clone()));
+                            }
+                            // hash collision, scan on
+                        }
+                        _ => return ControlFlow::Continue(()),
+                    }
+                }
+            }
+            return ControlFlow::Break(None);
+        }
+        let mut idxs = GenIndexes::new(hash_value, (inner.indices.len() - 1) as i64);
+        loop {
+            let index_index = idxs.next();
+            let index_entry = unsafe {
+                // Safety: index_index is generated
+                inner.indices.get_unchecked(index_index)
+            };
+            match index_entry {
+                IndexEntry::DUMMY => {}
+                IndexEntry::FREE => return ControlFlow::Break(None),
+                idx => {
+                    let entry = unsafe {
+                        // Safety: DUMMY and FREE are already handled above.
+                        // i is always valid and entry always exists.
+                        let i = idx.index().unwrap_unchecked();
+                        inner.entries.get_unchecked(i).as_ref().unwrap_unchecked()
+                    };
+                    if key.key_is(&entry.key) {
+                        return ControlFlow::Break(Some(entry.value.clone()));
+                    }
+                    if entry.hash == hash_value {
+                        match (inner.all_str_keys, str_key) {
+                            (true, Some(str_key)) => {
+                                let entry_str = unsafe {
+                                    // Safety: all_str_keys means every key in
+                                    // this dict is an exact str
+                                    entry.key.payload::<PyStr>().unwrap_unchecked()
+                                };
+                                if entry_str.as_str() == str_key {
+                                    return ControlFlow::Break(Some(entry.value.clone()));
+                                }
+                                // hash collision, probe on
+                            }
+                            _ => return ControlFlow::Continue(()),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     pub fn get_chain<K: DictKey + ?Sized>(
         &self,
         other: &Self,
@@ -348,10 +897,13 @@ impl<T: Clone> Dict<T> {
     pub fn clear(&self) {
         let _removed = {
             let mut inner = self.write();
-            inner.indices.clear();
-            inner.indices.resize(8, IndexEntry::FREE);
+            inner.indices = Indices::Inline;
+            if inner.used != 0 {
+                inner.generation += 1;
+            }
             inner.used = 0;
             inner.filled = 0;
+            inner.all_str_keys = true;
             // defer dec rc
             std::mem::take(&mut inner.entries)
         };
@@ -395,52 +947,70 @@ impl<T: Clone> Dict<T> {
     }
 
     pub fn delete_or_insert(&self, vm: &VirtualMachine, key: &PyObject, value: T) -> PyResult<()> {
-        let hash = key.key_hash(vm)?;
-        let _removed = loop {
-            let lookup = self.lookup(vm, key, hash, None)?;
-            let (entry, index_index) = lookup;
-            if entry.index().is_some() {
-                match self.pop_inner(lookup) {
-                    ControlFlow::Break(Some(entry)) => break Some(entry),
-                    _ => continue,
+        match self.entry(vm, key)? {
+            Entry::Occupied(entry) => {
+                if entry.remove(vm)?.is_none() {
+                    // someone else removed the key between the lookup and the
+                    // removal, so it is our turn to insert again
+                    self.insert(vm, key, value)?;
                 }
-            } else {
-                let mut inner = self.write();
-                inner.unchecked_push(index_index, hash, key.to_owned(), value, entry);
-                break None;
             }
-        };
+            Entry::Vacant(entry) => {
+                entry.insert(vm, value);
+            }
+        }
         Ok(())
     }
 
-    pub fn setdefault<K, F>(&self, vm: &VirtualMachine, key: &K, default: F) -> PyResult<T>
+    /// Look `key` up once and return a handle to its slot, so callers that
+    /// read and then mutate — `setdefault`, counters — don't pay for a
+    /// second lookup.
+    pub fn entry<'a, K>(&'a self, vm: &VirtualMachine, key: &'a K) -> PyResult<Entry<'a, K, T>>
     where
         K: DictKey + ?Sized,
-        F: FnOnce() -> T,
     {
         let hash = key.key_hash(vm)?;
-        let res = loop {
+        let entry = loop {
             let lookup = self.lookup(vm, key, hash, None)?;
             let (index_entry, index_index) = lookup;
             if let Some(index) = index_entry.index() {
                 let inner = self.read();
                 if let Some(entry) = inner.get_entry_checked(index, index_index) {
-                    break entry.value.clone();
+                    break Entry::Occupied(OccupiedEntry {
+                        dict: self,
+                        key,
+                        hash,
+                        index,
+                        index_index,
+                        pair: (entry.key.clone(), entry.value.clone()),
+                    });
                 } else {
                     // The dict was changed since we did lookup, let's try again.
                     continue;
                 }
             } else {
-                let value = default();
-                let mut inner = self.write();
-                inner.unchecked_push(
-                    index_index,
+                break Entry::Vacant(VacantEntry {
+                    dict: self,
+                    key,
                     hash,
-                    key.to_pyobject(vm),
-                    value.clone(),
-                    index_entry,
-                );
-                break value;
+                    lookup,
+                });
+            }
+        };
+        Ok(entry)
+    }
+
+    pub fn setdefault<K, F>(&self, vm: &VirtualMachine, key: &K, default: F) -> PyResult<T>
+    where
+        K: DictKey + ?Sized,
+        F: FnOnce() -> T,
+    {
+        let res = match self.entry(vm, key)? {
+            Entry::Occupied(entry) => entry.into_value(),
+            Entry::Vacant(entry) => {
+                let value = default();
+                entry.insert(vm, value.clone());
+                value
             }
         };
         Ok(res)
@@ -457,25 +1027,12 @@ impl<T: Clone> Dict<T> {
         K: DictKey + ?Sized,
         F: FnOnce() -> T,
     {
-        let hash = key.key_hash(vm)?;
-        let res = loop {
-            let lookup = self.lookup(vm, key, hash, None)?;
-            let (index_entry, index_index) = lookup;
-            if let Some(index) = index_entry.index() {
-                let inner = self.read();
-                if let Some(entry) = inner.get_entry_checked(index, index_index) {
-                    break (entry.key.clone(), entry.value.clone());
-                } else {
-                    // The dict was changed since we did lookup, let's try again.
-                    continue;
-                }
-            } else {
+        let res = match self.entry(vm, key)? {
+            Entry::Occupied(entry) => entry.into_pair(),
+            Entry::Vacant(entry) => {
                 let value = default();
-                let key = key.to_pyobject(vm);
-                let mut inner = self.write();
-                let ret = (key.clone(), value.clone());
-                inner.unchecked_push(index_index, hash, key, value, index_entry);
-                break ret;
+                let key = entry.insert(vm, value.clone());
+                (key, value)
             }
         };
         Ok(res)
@@ -519,9 +1076,30 @@ impl<T: Clone> Dict<T> {
         self.read().entries.len().saturating_sub(position)
     }
 
+    /// `true` when the number of keys differs from what `old` recorded,
+    /// matching CPython's "changed size during iteration" condition
     pub fn has_changed_size(&self, old: &DictSize) -> bool {
-        let current = self.read().size();
-        current != *old
+        self.read().used != old.used
+    }
+
+    /// `true` when the key set changed since `old` was recorded, even if the
+    /// dict ended up holding the same number of keys again; value overwrites
+    /// don't count. Any change reported here may have moved entries, so
+    /// iterator positions taken under `old` are no longer meaningful.
+    pub fn has_changed_keys(&self, old: &DictSize) -> bool {
+        self.read().generation != old.generation
+    }
+
+    /// Every pair, captured under a single read lock, in insertion order.
+    /// Consumers that walk the dict entry by entry interleave with
+    /// concurrent mutation; repr, pickling and friends want one consistent
+    /// view instead.
+    pub fn iter_snapshot(&self) -> Vec<(PyObjectRef, T)> {
+        self.read()
+            .entries
+            .iter()
+            .filter_map(|v| v.as_ref().map(|v| (v.key.clone(), v.value.clone())))
+            .collect()
     }
 
     pub fn keys(&self) -> Vec<PyObjectRef> {
@@ -533,16 +1111,28 @@ impl<T: Clone> Dict<T> {
     }
 
     /// Lookup the index for the given key.
-    #[cfg_attr(feature = "flame-it", flame("Dict"))]
-    fn lookup<K: DictKey + ?Sized>(
-        &self,
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
+    fn lookup<'a, K: DictKey + ?Sized>(
+        &'a self,
         vm: &VirtualMachine,
         key: &K,
         hash_value: HashValue,
-        mut lock: Option<PyRwLockReadGuard<DictInner<T>>>,
+        mut lock: Option<PyRwLockReadGuard<'a, DictInner<T>>>,
     ) -> PyResult<LookupResult> {
+        {
+            let inner = lock.take().unwrap_or_else(|| self.read());
+            if matches!(inner.indices, Indices::Inline) {
+                if let Some(result) = self.lookup_inline(vm, key, hash_value, Some(inner))? {
+                    return Ok(result);
+                }
+                // the dict became hash-based while the lock was released
+            } else {
+                lock = Some(inner);
+            }
+        }
         let mut idxs = None;
         let mut free_slot = None;
+        let str_key = key.str_key(vm);
         let ret = 'outer: loop {
             let (entry_key, ret) = {
                 let inner = lock.take().unwrap_or_else(|| self.read());
@@ -551,7 +1141,7 @@ impl<T: Clone> Dict<T> {
                 });
                 loop {
                     let index_index = idxs.next();
-                    let index_entry = *unsafe {
+                    let index_entry = unsafe {
                         // Safety: index_index is generated
                         inner.indices.get_unchecked(index_index)
                     };
@@ -579,7 +1169,23 @@ impl<T: Clone> Dict<T> {
                             if key.key_is(&entry.key) {
                                 break 'outer ret;
                             } else if entry.hash == hash_value {
-                                break (entry.key.clone(), ret);
+                                if let (true, Some(str_key)) = (inner.all_str_keys, str_key) {
+                                    // both sides are exact strs, so equality
+                                    // is plain content comparison and can't
+                                    // run Python code: no need to leave the
+                                    // lock and go through key_eq
+                                    let entry_str = unsafe {
+                                        // Safety: all_str_keys means every
+                                        // key in this dict is an exact str
+                                        entry.key.payload::<PyStr>().unwrap_unchecked()
+                                    };
+                                    if entry_str.as_str() == str_key {
+                                        break 'outer ret;
+                                    }
+                                    // hash collision, probe on
+                                } else {
+                                    break (entry.key.clone(), ret);
+                                }
                             } else {
                                 // entry mismatch
                             }
@@ -600,6 +1206,64 @@ impl<T: Clone> Dict<T> {
         Ok(ret)
     }
 
+    /// [`Self::lookup`] for the inline representation: no probe table, scan
+    /// the entries in insertion order. `None` when the dict is (or became,
+    /// while the lock was released for `key_eq`) hash-based after all.
+    fn lookup_inline<'a, K: DictKey + ?Sized>(
+        &'a self,
+        vm: &VirtualMachine,
+        key: &K,
+        hash_value: HashValue,
+        mut lock: Option<PyRwLockReadGuard<'a, DictInner<T>>>,
+    ) -> PyResult<Option<LookupResult>> {
+        let str_key = key.str_key(vm);
+        let mut pos = 0;
+        loop {
+            let (entry_key, found) = {
+                let inner = lock.take().unwrap_or_else(|| self.read());
+                if !matches!(inner.indices, Indices::Inline) {
+                    return Ok(None);
+                }
+                loop {
+                    let Some(slot) = inner.entries.get(pos) else {
+                        // the slot an insertion would go to is derived from
+                        // the entry position, so it doesn't matter here
+                        return Ok(Some((IndexEntry::FREE, 0)));
+                    };
+                    let i = pos;
+                    pos += 1;
+                    let Some(entry) = slot else { continue };
+                    let found = unsafe {
+                        // Safety: i indexes a live entry, far below the
+                        // IndexEntry sentinels
+                        (IndexEntry::from_index_unchecked(i), i)
+                    };
+                    if key.key_is(&entry.key) {
+                        return Ok(Some(found));
+                    } else if entry.hash == hash_value {
+                        if let (true, Some(str_key)) = (inner.all_str_keys, str_key) {
+                            let entry_str = unsafe {
+                                // Safety: all_str_keys means every key in
+                                // this dict is an exact str
+                                entry.key.payload::<PyStr>().unwrap_unchecked()
+                            };
+                            if entry_str.as_str() == str_key {
+                                return Ok(Some(found));
+                            }
+                            // hash collision, scan on
+                        } else {
+                            break (entry.key.clone(), found);
+                        }
+                    }
+                }
+            };
+            // This comparison needs to be done outside the lock.
+            if key.key_eq(vm, &entry_key)? {
+                return Ok(Some(found));
+            }
+        }
+    }
+
     // returns Err(()) if changed since lookup
     fn pop_inner(&self, lookup: LookupResult) -> PopInnerResult<T> {
         self.pop_inner_if(lookup, |_| Ok::<_, std::convert::Infallible>(true))
@@ -631,12 +1295,14 @@ impl<T: Clone> Dict<T> {
             // The dict was changed since we did lookup. Let's try again.
             _ => return Ok(ControlFlow::Continue(())),
         }
-        *unsafe {
+        unsafe {
             // index_index is result of lookup
-            inner.indices.get_unchecked_mut(index_index)
-        } = IndexEntry::DUMMY;
+            inner.indices.set_unchecked(index_index, IndexEntry::DUMMY)
+        };
         inner.used -= 1;
+        inner.generation += 1;
         let removed = slot.take();
+        inner.maybe_compact();
         Ok(ControlFlow::Break(removed))
     }
 
@@ -653,8 +1319,62 @@ impl<T: Clone> Dict<T> {
         Ok(removed)
     }
 
+    /// Retrieve and delete the entry least recently inserted, complementing
+    /// [`Self::pop_back`] for FIFO caches
+    #[allow(dead_code)]
+    pub fn pop_front(&self) -> Option<(PyObjectRef, T)> {
+        let inner = &mut *self.write();
+        let entry = inner.entries.iter_mut().find_map(|slot| slot.take())?;
+        inner.used -= 1;
+        inner.generation += 1;
+        unsafe {
+            // entry.index always refers valid index
+            inner.indices.set_unchecked(entry.index, IndexEntry::DUMMY)
+        };
+        inner.maybe_compact();
+        Some((entry.key, entry.value))
+    }
+
+    /// Remove every entry `pred` rejects, under a single write lock; an
+    /// `Err` from the predicate stops the sweep with the entries visited so
+    /// far already gone. pred should be VERY CAREFUL about what it does as
+    /// it is called while the dict's internal mutex is held.
+    #[allow(dead_code)]
+    pub fn retain<F>(&self, mut pred: F) -> PyResult<()>
+    where
+        F: FnMut(&PyObjectRef, &mut T) -> PyResult<bool>,
+    {
+        let mut result = Ok(());
+        let _removed = {
+            let inner = &mut *self.write();
+            let mut removed = Vec::new();
+            for slot in inner.entries.iter_mut() {
+                let Some(entry) = slot else { continue };
+                match pred(&entry.key, &mut entry.value) {
+                    Ok(true) => continue,
+                    Ok(false) => {}
+                    Err(exc) => {
+                        result = Err(exc);
+                        break;
+                    }
+                }
+                unsafe {
+                    // entry.index always refers valid index
+                    inner.indices.set_unchecked(entry.index, IndexEntry::DUMMY)
+                };
+                inner.used -= 1;
+                inner.generation += 1;
+                // defer dec rc
+                removed.push(slot.take());
+            }
+            inner.maybe_compact();
+            removed
+        };
+        result
+    }
+
     pub fn pop_back(&self) -> Option<(PyObjectRef, T)> {
-        let mut inner = &mut *self.write();
+        let inner = &mut *self.write();
         let entry = loop {
             let entry = inner.entries.pop()?;
             if let Some(entry) = entry {
@@ -662,24 +1382,157 @@ impl<T: Clone> Dict<T> {
             }
         };
         inner.used -= 1;
-        *unsafe {
+        inner.generation += 1;
+        unsafe {
             // entry.index always refers valid index
-            inner.indices.get_unchecked_mut(entry.index)
-        } = IndexEntry::DUMMY;
+            inner.indices.set_unchecked(entry.index, IndexEntry::DUMMY)
+        };
+        inner.maybe_compact();
         Some((entry.key, entry.value))
     }
 
+    /// Shed the memory a dict that shrank is still holding on to: drops the
+    /// entry tombstones and rebuilds the index table at the smallest size
+    /// that keeps the load factor healthy.
+    pub fn shrink_to_fit(&self) {
+        let inner = &mut *self.write();
+        if inner.entries.len() != inner.used {
+            // dropping tombstones moves entries, invalidating iterators the
+            // same way a deletion would
+            inner.generation += 1;
+        }
+        inner.entries.retain(Option::is_some);
+        inner.entries.shrink_to_fit();
+        if matches!(inner.indices, Indices::Inline) {
+            // dropping tombstones moved entries down; an inline entry's slot
+            // is its position
+            for (i, slot) in inner.entries.iter_mut().enumerate() {
+                if let Some(entry) = slot {
+                    entry.index = i;
+                }
+            }
+        } else {
+            inner.resize(std::cmp::max(inner.used * 2, 8));
+        }
+    }
+
+    /// Heap bytes owned by the table: the adaptive-width index table (zero
+    /// while the dict is inline) plus the allocated entry slots, counting
+    /// capacity rather than length since the spare room is owned too. The
+    /// `Dict` struct itself lives inside whoever holds it and is theirs to
+    /// account for; once split (shared-key) dicts land, each instance's
+    /// share of the common key table belongs here as well.
     pub fn sizeof(&self) -> usize {
         let inner = self.read();
-        size_of::<Self>()
-            + size_of::<DictInner<T>>()
-            + inner.indices.len() * size_of::<i64>()
-            + inner.entries.len() * size_of::<DictEntry<T>>()
+        inner.indices.sizeof() + inner.entries.capacity() * size_of::<DictEntry<T>>()
     }
 }
 
 type LookupResult = (IndexEntry, IndexIndex);
 
+/// A view into a single key's slot, see [`Dict::entry`].
+pub enum Entry<'a, K: ?Sized, T> {
+    Occupied(OccupiedEntry<'a, K, T>),
+    Vacant(VacantEntry<'a, K, T>),
+}
+
+/// A key that was present when [`Dict::entry`] looked it up. The dict is not
+/// kept locked: mutating through the handle re-validates the recorded
+/// position and falls back to a fresh lookup when another thread moved the
+/// entry, just like the `Dict` methods themselves.
+pub struct OccupiedEntry<'a, K: ?Sized, T> {
+    dict: &'a Dict<T>,
+    key: &'a K,
+    hash: HashValue,
+    index: EntryIndex,
+    index_index: IndexIndex,
+    pair: (PyObjectRef, T),
+}
+
+impl<K: DictKey + ?Sized, T: Clone> OccupiedEntry<'_, K, T> {
+    /// the key as it is stored in the dict
+    #[allow(dead_code)]
+    pub fn key(&self) -> &PyObjectRef {
+        &self.pair.0
+    }
+
+    /// the value as of the lookup
+    #[allow(dead_code)]
+    pub fn get(&self) -> &T {
+        &self.pair.1
+    }
+
+    pub fn into_value(self) -> T {
+        self.pair.1
+    }
+
+    pub fn into_pair(self) -> (PyObjectRef, T) {
+        self.pair
+    }
+
+    /// Overwrite the value in place, degrading to a fresh insert when the
+    /// entry moved since the lookup.
+    #[allow(dead_code)]
+    pub fn set(&self, vm: &VirtualMachine, value: T) -> PyResult<()> {
+        let _removed = {
+            let mut inner = self.dict.write();
+            match inner.entries.get_mut(self.index) {
+                Some(Some(entry)) if entry.index == self.index_index => {
+                    // defer dec RC
+                    std::mem::replace(&mut entry.value, value)
+                }
+                // The dict was changed since we did lookup. Let's start over.
+                _ => {
+                    drop(inner);
+                    return self.dict.insert_hashed(vm, self.key, self.hash, value);
+                }
+            }
+        };
+        Ok(())
+    }
+
+    /// Remove the entry, or `None` when another thread removed the key first.
+    pub fn remove(self, vm: &VirtualMachine) -> PyResult<Option<T>> {
+        let mut lookup = (
+            unsafe {
+                // Safety: the index was taken from a live entry, so it is in
+                // range for an IndexEntry
+                IndexEntry::from_index_unchecked(self.index)
+            },
+            self.index_index,
+        );
+        let removed = loop {
+            match self.dict.pop_inner(lookup) {
+                ControlFlow::Break(entry) => break entry,
+                ControlFlow::Continue(()) => {
+                    // The dict was changed since we did lookup. Let's try again.
+                    lookup = self.dict.lookup(vm, self.key, self.hash, None)?;
+                }
+            }
+        };
+        Ok(removed.map(|entry| entry.value))
+    }
+}
+
+/// A key that was absent when [`Dict::entry`] looked it up.
+pub struct VacantEntry<'a, K: ?Sized, T> {
+    dict: &'a Dict<T>,
+    key: &'a K,
+    hash: HashValue,
+    lookup: LookupResult,
+}
+
+impl<K: DictKey + ?Sized, T: Clone> VacantEntry<'_, K, T> {
+    /// Insert `value`, returning the key as it was stored.
+    pub fn insert(self, vm: &VirtualMachine, value: T) -> PyObjectRef {
+        let (index_entry, index_index) = self.lookup;
+        let key = self.key.to_pyobject(vm);
+        let mut inner = self.dict.write();
+        inner.unchecked_push(vm, index_index, self.hash, key.clone(), value, index_entry);
+        key
+    }
+}
+
 /// Types implementing this trait can be used to index
 /// the dictionary. Typical usecases are:
 /// - PyObjectRef -> arbitrary python type used as key
@@ -694,6 +1547,21 @@ pub trait DictKey {
     fn key_is(&self, other: &PyObject) -> bool;
     fn key_eq(&self, vm: &VirtualMachine, other_key: &PyObject) -> PyResult<bool>;
     fn key_as_isize(&self, vm: &VirtualMachine) -> PyResult<isize>;
+    /// The key's contents when comparing it cannot run Python code: an exact
+    /// `str`, never a subclass. Lets lookups into a dict whose keys are all
+    /// exact strs compare without leaving the lock.
+    #[inline]
+    fn str_key(&self, _vm: &VirtualMachine) -> Option<&str> {
+        None
+    }
+    /// `true` for interned strs: an equal interned key stored in a dict is
+    /// the very same object, so a hit under [`DictKey::key_is`] alone proves
+    /// presence without hashing. A miss proves nothing — an equal
+    /// non-interned key may be stored — and falls back to the normal probe.
+    #[inline]
+    fn is_interned_str(&self) -> bool {
+        false
+    }
 }
 
 /// Implement trait for PyObjectRef such that we can use python objects
@@ -720,6 +1588,10 @@ impl DictKey for PyObject {
     fn key_as_isize(&self, vm: &VirtualMachine) -> PyResult<isize> {
         self.try_index(vm)?.try_to_primitive(vm)
     }
+    #[inline]
+    fn str_key(&self, vm: &VirtualMachine) -> Option<&str> {
+        str_exact(self, vm).map(|s| s.as_str())
+    }
 }
 
 impl DictKey for Py<PyStr> {
@@ -750,6 +1622,10 @@ impl DictKey for Py<PyStr> {
     fn key_as_isize(&self, vm: &VirtualMachine) -> PyResult<isize> {
         self.as_object().key_as_isize(vm)
     }
+    #[inline]
+    fn str_key(&self, vm: &VirtualMachine) -> Option<&str> {
+        self.as_object().str_key(vm)
+    }
 }
 
 impl DictKey for PyStrInterned {
@@ -775,6 +1651,14 @@ impl DictKey for PyStrInterned {
     fn key_as_isize(&self, vm: &VirtualMachine) -> PyResult<isize> {
         (**self).key_as_isize(vm)
     }
+    #[inline]
+    fn str_key(&self, _vm: &VirtualMachine) -> Option<&str> {
+        Some(self.as_str())
+    }
+    #[inline]
+    fn is_interned_str(&self) -> bool {
+        true
+    }
 }
 
 impl DictKey for PyExact<PyStr> {
@@ -799,6 +1683,10 @@ impl DictKey for PyExact<PyStr> {
     fn key_as_isize(&self, vm: &VirtualMachine) -> PyResult<isize> {
         (**self).key_as_isize(vm)
     }
+    #[inline]
+    fn str_key(&self, _vm: &VirtualMachine) -> Option<&str> {
+        Some(self.as_str())
+    }
 }
 
 // AsRef<str> fit this case but not possible in rust 1.46
@@ -836,6 +1724,10 @@ impl DictKey for str {
     fn key_as_isize(&self, vm: &VirtualMachine) -> PyResult<isize> {
         Err(vm.new_type_error("'str' object cannot be interpreted as an integer".to_owned()))
     }
+    #[inline]
+    fn str_key(&self, _vm: &VirtualMachine) -> Option<&str> {
+        Some(self)
+    }
 }
 
 impl DictKey for String {
@@ -860,6 +1752,10 @@ impl DictKey for String {
     fn key_as_isize(&self, vm: &VirtualMachine) -> PyResult<isize> {
         self.as_str().key_as_isize(vm)
     }
+    #[inline]
+    fn str_key(&self, _vm: &VirtualMachine) -> Option<&str> {
+        Some(self)
+    }
 }
 
 impl DictKey for usize {
@@ -895,6 +1791,161 @@ impl DictKey for usize {
     }
 }
 
+impl DictKey for i64 {
+    type Owned = i64;
+    #[inline]
+    fn _to_owned(&self, _vm: &VirtualMachine) -> Self::Owned {
+        *self
+    }
+
+    fn key_hash(&self, _vm: &VirtualMachine) -> PyResult<HashValue> {
+        // the same value an int object with this value hashes to, so these
+        // keys interoperate with keys inserted from Python code
+        Ok(hash::fix_sentinel(hash::mod_int(*self)))
+    }
+
+    fn key_is(&self, _other: &PyObject) -> bool {
+        false
+    }
+
+    fn key_eq(&self, vm: &VirtualMachine, other_key: &PyObject) -> PyResult<bool> {
+        if let Some(int) = other_key.payload_if_exact::<PyInt>(vm) {
+            Ok(int.as_bigint().to_i64() == Some(*self))
+        } else {
+            let int = vm.ctx.new_int(*self);
+            vm.bool_eq(int.as_ref(), other_key)
+        }
+    }
+
+    fn key_as_isize(&self, _vm: &VirtualMachine) -> PyResult<isize> {
+        Ok(*self as isize)
+    }
+}
+
+impl DictKey for u64 {
+    type Owned = u64;
+    #[inline]
+    fn _to_owned(&self, _vm: &VirtualMachine) -> Self::Owned {
+        *self
+    }
+
+    fn key_hash(&self, _vm: &VirtualMachine) -> PyResult<HashValue> {
+        let hash = match i64::try_from(*self) {
+            Ok(i) => hash::fix_sentinel(hash::mod_int(i)),
+            Err(_) => hash::hash_bigint(&BigInt::from(*self)),
+        };
+        Ok(hash)
+    }
+
+    fn key_is(&self, _other: &PyObject) -> bool {
+        false
+    }
+
+    fn key_eq(&self, vm: &VirtualMachine, other_key: &PyObject) -> PyResult<bool> {
+        if let Some(int) = other_key.payload_if_exact::<PyInt>(vm) {
+            Ok(int.as_bigint().to_u64() == Some(*self))
+        } else {
+            let int = vm.ctx.new_int(*self);
+            vm.bool_eq(int.as_ref(), other_key)
+        }
+    }
+
+    fn key_as_isize(&self, _vm: &VirtualMachine) -> PyResult<isize> {
+        Ok(*self as isize)
+    }
+}
+
+impl DictKey for BigInt {
+    type Owned = BigInt;
+    #[inline]
+    fn _to_owned(&self, _vm: &VirtualMachine) -> Self::Owned {
+        self.clone()
+    }
+
+    fn key_hash(&self, _vm: &VirtualMachine) -> PyResult<HashValue> {
+        Ok(hash::hash_bigint(self))
+    }
+
+    fn key_is(&self, _other: &PyObject) -> bool {
+        false
+    }
+
+    fn key_eq(&self, vm: &VirtualMachine, other_key: &PyObject) -> PyResult<bool> {
+        if let Some(int) = other_key.payload_if_exact::<PyInt>(vm) {
+            Ok(int.as_bigint() == self)
+        } else {
+            let int = vm.ctx.new_int(self.clone());
+            vm.bool_eq(int.as_ref(), other_key)
+        }
+    }
+
+    fn key_as_isize(&self, vm: &VirtualMachine) -> PyResult<isize> {
+        self.to_isize()
+            .ok_or_else(|| vm.new_overflow_error("int too large to fit index".to_owned()))
+    }
+}
+
+/// Implement trait for the [u8] type, so that bytestrings
+/// can be used to index dictionaries.
+impl DictKey for [u8] {
+    type Owned = Vec<u8>;
+    #[inline]
+    fn _to_owned(&self, _vm: &VirtualMachine) -> Self::Owned {
+        self.to_vec()
+    }
+
+    #[inline]
+    fn key_hash(&self, vm: &VirtualMachine) -> PyResult<HashValue> {
+        // follow a similar route as the hashing of PyBytesRef
+        Ok(vm.state.hash_secret.hash_bytes(self))
+    }
+
+    #[inline(always)]
+    fn key_is(&self, _other: &PyObject) -> bool {
+        // No matter who the other pyobject is, we are never the same thing, since
+        // we are a byte slice, not a pyobject.
+        false
+    }
+
+    fn key_eq(&self, vm: &VirtualMachine, other_key: &PyObject) -> PyResult<bool> {
+        if let Some(bytes) = other_key.payload_if_exact::<PyBytes>(vm) {
+            Ok(bytes.as_bytes() == self)
+        } else {
+            // Fall back to PyObjectRef implementation.
+            let b = vm.ctx.new_bytes(self.to_vec());
+            b.as_object().key_eq(vm, other_key)
+        }
+    }
+
+    fn key_as_isize(&self, vm: &VirtualMachine) -> PyResult<isize> {
+        Err(vm.new_type_error("'bytes' object cannot be interpreted as an integer".to_owned()))
+    }
+}
+
+impl DictKey for Vec<u8> {
+    type Owned = Vec<u8>;
+    #[inline]
+    fn _to_owned(&self, _vm: &VirtualMachine) -> Self::Owned {
+        self.clone()
+    }
+
+    fn key_hash(&self, vm: &VirtualMachine) -> PyResult<HashValue> {
+        self.as_slice().key_hash(vm)
+    }
+
+    fn key_is(&self, other: &PyObject) -> bool {
+        self.as_slice().key_is(other)
+    }
+
+    fn key_eq(&self, vm: &VirtualMachine, other_key: &PyObject) -> PyResult<bool> {
+        self.as_slice().key_eq(vm, other_key)
+    }
+
+    fn key_as_isize(&self, vm: &VirtualMachine) -> PyResult<isize> {
+        self.as_slice().key_as_isize(vm)
+    }
+}
+
 fn str_exact<'a>(obj: &'a PyObject, vm: &VirtualMachine) -> Option<&'a PyStr> {
     if obj.class().is(vm.ctx.types.str_type) {
         obj.payload::<PyStr>()
@@ -942,6 +1993,120 @@ mod tests {
         })
     }
 
+    /// Hammer a shared dict from several threads at once. Every thread owns a
+    /// disjoint key range and tracks what it last wrote, so any lost update,
+    /// miscounted `used` or stuck retry loop shows up as an assertion failure
+    /// (or a hang) instead of silent corruption.
+    #[cfg(feature = "threading")]
+    #[test]
+    fn test_threaded_stress() {
+        use std::sync::Arc;
+
+        const THREADS: usize = 4;
+        const KEYS_PER_THREAD: usize = 24;
+        const OPS: usize = 2_000;
+
+        Interpreter::without_stdlib(Default::default()).enter(|vm| {
+            let dict: Arc<Dict> = Arc::new(Dict::default());
+
+            // Phase one: each thread mixes inserts, deletes and reads over its
+            // own keys. Values embed the key and write counter so a read can be
+            // checked against the thread's local model.
+            let handles: Vec<_> = (0..THREADS)
+                .map(|thread| {
+                    let dict = dict.clone();
+                    vm.start_thread(move |vm| {
+                        let keys: Vec<String> = (0..KEYS_PER_THREAD)
+                            .map(|i| format!("t{thread}-k{i}"))
+                            .collect();
+                        let mut model: Vec<Option<usize>> = vec![None; KEYS_PER_THREAD];
+                        // deterministic per-thread LCG; no rand dependency
+                        let mut state = thread as u64 + 1;
+                        let mut rand = move || {
+                            state = state
+                                .wrapping_mul(6364136223846793005)
+                                .wrapping_add(1442695040888963407);
+                            (state >> 33) as usize
+                        };
+                        for op in 0..OPS {
+                            let i = rand() % KEYS_PER_THREAD;
+                            let key = keys[i].as_str();
+                            match rand() % 4 {
+                                0 | 1 => {
+                                    let value = vm.new_pyobj(format!("{key}#{op}"));
+                                    dict.insert(vm, key, value).unwrap();
+                                    model[i] = Some(op);
+                                }
+                                2 => {
+                                    let deleted = dict.delete_if_exists(vm, key).unwrap();
+                                    assert_eq!(deleted, model[i].is_some());
+                                    model[i] = None;
+                                }
+                                _ => {
+                                    let value = dict.get(vm, key).unwrap();
+                                    match (&value, model[i]) {
+                                        (Some(value), Some(op)) => {
+                                            let value = value.payload::<PyStr>().unwrap();
+                                            assert_eq!(value.as_str(), format!("{key}#{op}"));
+                                        }
+                                        (None, None) => {}
+                                        (_, expected) => {
+                                            panic!("{key}: found {value:?}, expected {expected:?}")
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        (keys, model)
+                    })
+                })
+                .collect();
+
+            // No thread may have clobbered another's keys.
+            let mut live = 0;
+            for handle in handles {
+                let (keys, model) = handle.join().unwrap();
+                for (key, written) in keys.iter().zip(model) {
+                    let value = dict.get(vm, key.as_str()).unwrap();
+                    match (&value, written) {
+                        (Some(value), Some(op)) => {
+                            let value = value.payload::<PyStr>().unwrap();
+                            assert_eq!(value.as_str(), format!("{key}#{op}"));
+                            live += 1;
+                        }
+                        (None, None) => {}
+                        (_, written) => {
+                            panic!("{key}: found {value:?}, expected {written:?}")
+                        }
+                    }
+                }
+            }
+            assert_eq!(dict.len(), live);
+
+            // Phase two: drain concurrently with pop_back; every pair must come
+            // out exactly once and still match what its writer stored.
+            let handles: Vec<_> = (0..THREADS)
+                .map(|_| {
+                    let dict = dict.clone();
+                    vm.start_thread(move |_vm| {
+                        let mut popped = 0;
+                        while let Some((key, value)) = dict.pop_back() {
+                            let key = key.payload::<PyStr>().unwrap();
+                            let value = value.payload::<PyStr>().unwrap();
+                            let prefix = format!("{}#", key.as_str());
+                            assert!(value.as_str().starts_with(&prefix));
+                            popped += 1;
+                        }
+                        popped
+                    })
+                })
+                .collect();
+            let popped: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+            assert_eq!(popped, live);
+            assert_eq!(dict.len(), 0);
+        })
+    }
+
     macro_rules! hash_tests {
         ($($name:ident: $example_hash:expr,)*) => {
             $(
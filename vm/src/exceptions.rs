@@ -5,6 +5,7 @@ use crate::{
         traceback::PyTracebackRef, tuple::IntoPyTuple, PyNone, PyStr, PyStrRef, PyTuple,
         PyTupleRef, PyType, PyTypeRef,
     },
+    bytecode,
     class::{PyClassImpl, StaticType},
     convert::{ToPyException, ToPyObject},
     function::{ArgIterable, FuncArgs, IntoFuncArgs},
@@ -224,7 +225,7 @@ impl VirtualMachine {
 fn print_source_line<W: Write>(
     output: &mut W,
     filename: &str,
-    lineno: usize,
+    location: bytecode::Location,
 ) -> Result<(), W::Error> {
     // TODO: use io.open() method instead, when available, according to https://github.com/python/cpython/blob/main/Python/traceback.c#L393
     // TODO: support different encodings
@@ -235,10 +236,12 @@ fn print_source_line<W: Write>(
     let file = BufReader::new(file);
 
     for (i, line) in file.lines().enumerate() {
-        if i + 1 == lineno {
+        if i + 1 == location.row() {
             if let Ok(line) = line {
+                let trimmed = line.trim_start();
                 // Indented with 4 spaces
-                writeln!(output, "    {}", line.trim_start())?;
+                writeln!(output, "    {trimmed}")?;
+                print_source_underline(output, location, &line)?;
             }
             return Ok(());
         }
@@ -247,6 +250,35 @@ fn print_source_line<W: Write>(
     Ok(())
 }
 
+/// The PEP 657 caret line under the echoed source line, marking the column
+/// range of the span that raised; printed only when the span lies within a
+/// single line and is narrower than that whole line, like CPython 3.11.
+fn print_source_underline<W: Write>(
+    output: &mut W,
+    location: bytecode::Location,
+    line: &str,
+) -> Result<(), W::Error> {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+    let (start, end) = (location.column(), location.end_column());
+    if location.end_row() != location.row()
+        || start < indent
+        || end <= start
+        || end > line.len()
+        || end - start >= trimmed.trim_end().len()
+    {
+        return Ok(());
+    }
+    writeln!(
+        output,
+        "    {:pad$}{carets}",
+        "",
+        pad = start - indent,
+        carets = "^".repeat(end - start)
+    )?;
+    Ok(())
+}
+
 /// Print exception occurrence location from traceback element
 fn write_traceback_entry<W: Write>(
     output: &mut W,
@@ -256,9 +288,11 @@ fn write_traceback_entry<W: Write>(
     writeln!(
         output,
         r##"  File "{}", line {}, in {}"##,
-        filename, tb_entry.lineno, tb_entry.frame.code.obj_name
+        filename,
+        tb_entry.location.row(),
+        tb_entry.frame.code.obj_name
     )?;
-    print_source_line(output, filename, tb_entry.lineno)?;
+    print_source_line(output, filename, tb_entry.location)?;
 
     Ok(())
 }
@@ -331,6 +365,7 @@ impl ExceptionCtor {
 pub struct ExceptionZoo {
     pub base_exception_type: &'static Py<PyType>,
     pub base_exception_group: &'static Py<PyType>,
+    pub exception_group: &'static Py<PyType>,
     pub system_exit: &'static Py<PyType>,
     pub keyboard_interrupt: &'static Py<PyType>,
     pub generator_exit: &'static Py<PyType>,
@@ -373,6 +408,7 @@ pub struct ExceptionZoo {
     pub runtime_error: &'static Py<PyType>,
     pub not_implemented_error: &'static Py<PyType>,
     pub recursion_error: &'static Py<PyType>,
+    pub budget_exceeded: &'static Py<PyType>,
     pub syntax_error: &'static Py<PyType>,
     pub indentation_error: &'static Py<PyType>,
     pub tab_error: &'static Py<PyType>,
@@ -531,7 +567,7 @@ impl Constructor for PyBaseException {
     type Args = FuncArgs;
 
     fn py_new(cls: PyTypeRef, args: FuncArgs, vm: &VirtualMachine) -> PyResult {
-        PyBaseException::new(args.args, vm)
+        PyBaseException::new(args.args.into_vec(), vm)
             .into_ref_with_type(vm, cls)
             .map(Into::into)
     }
@@ -541,7 +577,7 @@ impl Initializer for PyBaseException {
     type Args = FuncArgs;
 
     fn init(zelf: PyRef<Self>, args: Self::Args, vm: &VirtualMachine) -> PyResult<()> {
-        *zelf.args.write() = PyTuple::new_ref(args.args, &vm.ctx);
+        *zelf.args.write() = PyTuple::new_ref(args.args.into_vec(), &vm.ctx);
         Ok(())
     }
 }
@@ -559,6 +595,17 @@ impl ExceptionZoo {
         let generator_exit = PyGeneratorExit::init_builtin_type();
 
         let exception_type = PyException::init_builtin_type();
+        // ExceptionGroup is the only builtin exception with two bases: it has
+        // to be caught both by `except Exception` and by group-aware checks
+        let exception_group = PyExceptionGroup::init_manually(
+            PyType::new_static_with_bases(
+                vec![base_exception_group.to_owned(), exception_type.to_owned()],
+                Default::default(),
+                PyExceptionGroup::make_slots(),
+                PyExceptionGroup::static_metaclass().to_owned(),
+            )
+            .unwrap(),
+        );
         let stop_iteration = PyStopIteration::init_builtin_type();
         let stop_async_iteration = PyStopAsyncIteration::init_builtin_type();
         let arithmetic_error = PyArithmeticError::init_builtin_type();
@@ -608,6 +655,7 @@ impl ExceptionZoo {
         let runtime_error = PyRuntimeError::init_builtin_type();
         let not_implemented_error = PyNotImplementedError::init_builtin_type();
         let recursion_error = PyRecursionError::init_builtin_type();
+        let budget_exceeded = PyBudgetExceeded::init_builtin_type();
 
         let syntax_error = PySyntaxError::init_builtin_type();
         let indentation_error = PyIndentationError::init_builtin_type();
@@ -640,6 +688,7 @@ impl ExceptionZoo {
         Self {
             base_exception_type,
             base_exception_group,
+            exception_group,
             system_exit,
             keyboard_interrupt,
             generator_exit,
@@ -682,6 +731,7 @@ impl ExceptionZoo {
             runtime_error,
             not_implemented_error,
             recursion_error,
+            budget_exceeded,
             syntax_error,
             indentation_error,
             tab_error,
@@ -724,7 +774,12 @@ impl ExceptionZoo {
         extend_exception!(PyBaseExceptionGroup, ctx, excs.base_exception_group, {
             "message" => ctx.new_readonly_getset("message", excs.base_exception_group, make_arg_getter(0)),
             "exceptions" => ctx.new_readonly_getset("exceptions", excs.base_exception_group, make_arg_getter(1)),
+            "subgroup" => ctx.new_method(identifier!(ctx, subgroup), excs.base_exception_group, exception_group_subgroup),
+            "split" => ctx.new_method(identifier!(ctx, split), excs.base_exception_group, exception_group_split),
+            "derive" => ctx.new_method(identifier!(ctx, derive), excs.base_exception_group, exception_group_derive),
+            "__str__" => ctx.new_method(identifier!(ctx, __str__), excs.base_exception_group, exception_group_str),
         });
+        extend_exception!(PyExceptionGroup, ctx, excs.exception_group);
         extend_exception!(PySystemExit, ctx, excs.system_exit, {
             "code" => ctx.new_readonly_getset("code", excs.system_exit, system_exit_code),
         });
@@ -824,6 +879,7 @@ impl ExceptionZoo {
         extend_exception!(PyRuntimeError, ctx, excs.runtime_error);
         extend_exception!(PyNotImplementedError, ctx, excs.not_implemented_error);
         extend_exception!(PyRecursionError, ctx, excs.recursion_error);
+        extend_exception!(PyBudgetExceeded, ctx, excs.budget_exceeded);
 
         extend_exception!(PySyntaxError, ctx, excs.syntax_error, {
             "msg" => ctx.new_readonly_getset("msg", excs.syntax_error, make_arg_getter(0)),
@@ -966,6 +1022,174 @@ fn os_error_reduce(exc: PyBaseExceptionRef, vm: &VirtualMachine) -> PyTupleRef {
     result.into_pytuple(vm)
 }
 
+fn exception_group_str(exc: PyBaseExceptionRef, vm: &VirtualMachine) -> PyResult<PyStrRef> {
+    let message = match exc.get_arg(0) {
+        Some(message) => message.str(vm)?,
+        None => vm.ctx.empty_str.to_owned(),
+    };
+    let count = exc
+        .get_arg(1)
+        .and_then(|exceptions| exceptions.downcast::<PyTuple>().ok())
+        .map_or(0, |exceptions| exceptions.len());
+    let plural = if count == 1 { "" } else { "s" };
+    Ok(vm
+        .ctx
+        .new_str(format!("{message} ({count} sub-exception{plural})")))
+}
+
+/// Apply a `subgroup`/`split`/`except*` condition to a single exception: a
+/// type or tuple of types is an isinstance check, anything else a predicate.
+fn exception_group_matches(
+    condition: &PyObjectRef,
+    exc: &PyBaseExceptionRef,
+    vm: &VirtualMachine,
+) -> PyResult<bool> {
+    if condition.fast_isinstance(vm.ctx.types.type_type)
+        || condition.fast_isinstance(vm.ctx.types.tuple_type)
+    {
+        exc.as_object().is_instance(condition, vm)
+    } else {
+        vm.invoke(condition, (exc.clone(),))?.try_to_bool(vm)
+    }
+}
+
+/// Split `exc` by `condition` into its matching and non-matching parts,
+/// recursing into nested exception groups; either side may come out empty.
+fn exception_group_split_inner(
+    exc: &PyBaseExceptionRef,
+    condition: &PyObjectRef,
+    vm: &VirtualMachine,
+) -> PyResult<(Option<PyObjectRef>, Option<PyObjectRef>)> {
+    if exception_group_matches(condition, exc, vm)? {
+        return Ok((Some(exc.clone().into()), None));
+    }
+    if !exc.fast_isinstance(vm.ctx.exceptions.base_exception_group) {
+        return Ok((None, Some(exc.clone().into())));
+    }
+    let exceptions: PyTupleRef = match exc.get_arg(1).and_then(|e| e.downcast().ok()) {
+        Some(exceptions) => exceptions,
+        None => return Ok((None, Some(exc.clone().into()))),
+    };
+    let mut matched = Vec::new();
+    let mut rest = Vec::new();
+    for sub in exceptions.as_slice() {
+        match sub.clone().downcast::<PyBaseException>() {
+            Ok(sub) => {
+                let (sub_matched, sub_rest) = exception_group_split_inner(&sub, condition, vm)?;
+                matched.extend(sub_matched);
+                rest.extend(sub_rest);
+            }
+            // not actually an exception; keep it on the unmatched side
+            Err(obj) => rest.push(obj),
+        }
+    }
+    let matched = match matched.is_empty() {
+        true => None,
+        false => Some(derive_exception_group(exc, matched, vm)?),
+    };
+    let rest = match rest.is_empty() {
+        true => None,
+        false => Some(derive_exception_group(exc, rest, vm)?),
+    };
+    Ok((matched, rest))
+}
+
+/// Build a group like `exc` holding `exceptions`, going through the possibly
+/// overridden `derive` method and copying the metadata over (PEP 654).
+fn derive_exception_group(
+    exc: &PyBaseExceptionRef,
+    exceptions: Vec<PyObjectRef>,
+    vm: &VirtualMachine,
+) -> PyResult<PyObjectRef> {
+    let exceptions = vm.ctx.new_tuple(exceptions);
+    let derived = vm.call_method(exc.as_object(), "derive", (exceptions,))?;
+    if let Some(derived) = derived.payload::<PyBaseException>() {
+        derived.set_cause(exc.cause());
+        derived.set_context(exc.context());
+        derived.set_traceback(exc.traceback());
+    }
+    Ok(derived)
+}
+
+fn exception_group_subgroup(
+    exc: PyBaseExceptionRef,
+    condition: PyObjectRef,
+    vm: &VirtualMachine,
+) -> PyResult {
+    let (matched, _) = exception_group_split_inner(&exc, &condition, vm)?;
+    Ok(vm.unwrap_or_none(matched))
+}
+
+fn exception_group_split(
+    exc: PyBaseExceptionRef,
+    condition: PyObjectRef,
+    vm: &VirtualMachine,
+) -> PyResult {
+    let (matched, rest) = exception_group_split_inner(&exc, &condition, vm)?;
+    Ok(vm
+        .ctx
+        .new_tuple(vec![vm.unwrap_or_none(matched), vm.unwrap_or_none(rest)])
+        .into())
+}
+
+fn exception_group_derive(
+    exc: PyBaseExceptionRef,
+    exceptions: PyObjectRef,
+    vm: &VirtualMachine,
+) -> PyResult {
+    // the default derive builds a plain BaseExceptionGroup; the constructor
+    // downgrades it to an ExceptionGroup when the contents allow it
+    let message = exc
+        .get_arg(0)
+        .unwrap_or_else(|| vm.ctx.empty_str.to_owned().into());
+    vm.invoke(
+        vm.ctx.exceptions.base_exception_group,
+        (message, exceptions),
+    )
+}
+
+/// Split `exc` for a single `except* typ` clause, returning `(rest, matched)`
+/// with None standing in for an empty side. A matched naked exception is
+/// wrapped in a group, so the name bound by the clause is always a group.
+pub(crate) fn exception_group_match(
+    exc: PyBaseExceptionRef,
+    typ: &PyObjectRef,
+    vm: &VirtualMachine,
+) -> PyResult<(PyObjectRef, PyObjectRef)> {
+    let types = match typ.payload::<PyTuple>() {
+        Some(tuple) => tuple.as_slice().to_vec(),
+        None => vec![typ.clone()],
+    };
+    for typ in &types {
+        let typ = typ.downcast_ref::<PyType>().ok_or_else(|| {
+            vm.new_type_error(
+                "catching classes that do not inherit from BaseException is not allowed".to_owned(),
+            )
+        })?;
+        if !typ.fast_issubclass(vm.ctx.exceptions.base_exception_type) {
+            return Err(vm.new_type_error(
+                "catching classes that do not inherit from BaseException is not allowed".to_owned(),
+            ));
+        }
+        if typ.fast_issubclass(vm.ctx.exceptions.base_exception_group) {
+            return Err(vm.new_type_error(
+                "catching ExceptionGroup with except* is not allowed. Use except instead."
+                    .to_owned(),
+            ));
+        }
+    }
+    let (matched, rest) = exception_group_split_inner(&exc, typ, vm)?;
+    let matched = match matched {
+        Some(matched) if !matched.fast_isinstance(vm.ctx.exceptions.base_exception_group) => vm
+            .invoke(
+                vm.ctx.exceptions.base_exception_group,
+                (vm.ctx.empty_str.to_owned(), vm.ctx.new_tuple(vec![matched])),
+            )?,
+        other => vm.unwrap_or_none(other),
+    };
+    Ok((vm.unwrap_or_none(rest), matched))
+}
+
 fn system_exit_code(exc: PyBaseExceptionRef) -> Option<PyObjectRef> {
     exc.args.read().first().map(|code| {
         match_class!(match code {
@@ -1130,11 +1354,11 @@ pub(super) mod types {
     use crate::common::lock::PyRwLock;
     #[cfg_attr(target_arch = "wasm32", allow(unused_imports))]
     use crate::{
-        builtins::{traceback::PyTracebackRef, PyInt, PyTupleRef, PyTypeRef},
+        builtins::{traceback::PyTracebackRef, PyInt, PyStrRef, PyTupleRef, PyTypeRef},
         convert::ToPyResult,
-        function::FuncArgs,
+        function::{FuncArgs, IntoFuncArgs},
         types::{Constructor, Initializer},
-        PyObjectRef, PyRef, PyResult, VirtualMachine,
+        AsObject, PyObjectRef, PyPayload, PyRef, PyResult, VirtualMachine,
     };
     use crossbeam_utils::atomic::AtomicCell;
 
@@ -1165,7 +1389,60 @@ pub(super) mod types {
         PyBaseExceptionGroup,
         PyBaseException,
         base_exception_group,
-        "A combination of multiple unrelated exceptions."
+        "A combination of multiple unrelated exceptions.",
+        base_exception_group_new,
+        base_exception_group_init
+    }
+    define_exception! {
+        PyExceptionGroup,
+        PyBaseExceptionGroup,
+        exception_group,
+        "A combination of multiple unrelated exceptions, all of which also inherit from Exception."
+    }
+    fn base_exception_group_new(cls: PyTypeRef, args: FuncArgs, vm: &VirtualMachine) -> PyResult {
+        let (message, exceptions): (PyStrRef, PyObjectRef) = args.clone().bind(vm)?;
+        let exceptions: Vec<PyBaseExceptionRef> = exceptions.try_to_value(vm).map_err(|_| {
+            vm.new_type_error(
+                "second argument (exceptions) must be a sequence of exceptions".to_owned(),
+            )
+        })?;
+        if exceptions.is_empty() {
+            return Err(vm.new_value_error(
+                "second argument (exceptions) must be a non-empty sequence".to_owned(),
+            ));
+        }
+        let all_exceptions = exceptions
+            .iter()
+            .all(|exc| exc.fast_isinstance(vm.ctx.exceptions.exception_type));
+        // a BaseExceptionGroup whose contents are all Exceptions becomes an
+        // ExceptionGroup; a plain ExceptionGroup refuses BaseExceptions
+        let cls = if cls.is(vm.ctx.exceptions.base_exception_group) && all_exceptions {
+            vm.ctx.exceptions.exception_group.to_owned()
+        } else if cls.fast_issubclass(vm.ctx.exceptions.exception_group) && !all_exceptions {
+            return Err(
+                vm.new_type_error(format!("Cannot nest BaseExceptions in '{}'", cls.name()))
+            );
+        } else {
+            cls
+        };
+        let exceptions = vm
+            .ctx
+            .new_tuple(exceptions.into_iter().map(Into::into).collect());
+        PyBaseException::new(vec![message.into(), exceptions.into()], vm)
+            .into_ref_with_type(vm, cls)
+            .map(Into::into)
+    }
+    fn base_exception_group_init(
+        zelf: PyObjectRef,
+        args: FuncArgs,
+        vm: &VirtualMachine,
+    ) -> PyResult<()> {
+        let (message, exceptions): (PyStrRef, PyObjectRef) = args.bind(vm)?;
+        // store the normalized args so that `message`/`exceptions` resolve
+        // even when a subclass forwards something list-like
+        let exceptions: Vec<PyObjectRef> = exceptions.try_to_value(vm)?;
+        let exceptions = vm.ctx.new_tuple(exceptions);
+        PyBaseException::slot_init(zelf, (message, exceptions).into_args(vm), vm)
     }
     define_exception! {
         PyGeneratorExit,
@@ -1505,6 +1782,12 @@ pub(super) mod types {
         recursion_error,
         "Recursion limit exceeded."
     }
+    define_exception! {
+        PyBudgetExceeded,
+        PyRuntimeError,
+        budget_exceeded,
+        "Instruction budget exhausted."
+    }
 
     define_exception! {
         PySyntaxError,
@@ -0,0 +1,96 @@
+//! A post-mortem execution trace for the interpreter, enabled with the
+//! `execution-trace` feature: the dispatch loop records every instruction and
+//! raised exception into a fixed-size ring buffer, and the most recent
+//! entries are dumped to stderr when the interpreter panics, so a crash
+//! report says what the vm was doing rather than only where it died.
+
+use crate::{bytecode::Instruction, common::lock::PyMutex};
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+
+/// how many events the ring buffer keeps
+const CAPACITY: usize = 256;
+
+struct Event {
+    /// id of the frame object, to tell interleaved frames apart
+    frame: usize,
+    lasti: u32,
+    /// value-stack depth before the event
+    depth: usize,
+    kind: EventKind,
+}
+
+enum EventKind {
+    Instruction(Instruction),
+    Exception(String),
+}
+
+static EVENTS: Lazy<PyMutex<VecDeque<Event>>> = Lazy::new(|| {
+    // every panic gets the dump, not just the explicit `fatal` calls
+    let prev = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        dump();
+        prev(info);
+    }));
+    PyMutex::new(VecDeque::with_capacity(CAPACITY))
+});
+
+fn record(event: Event) {
+    let mut events = EVENTS.lock();
+    if events.len() == CAPACITY {
+        events.pop_front();
+    }
+    events.push_back(event);
+}
+
+/// Record that the frame with id `frame` is about to execute `instruction`
+/// at offset `lasti` with `depth` values on its stack.
+pub(crate) fn record_instruction(frame: usize, lasti: u32, depth: usize, instruction: Instruction) {
+    record(Event {
+        frame,
+        lasti,
+        depth,
+        kind: EventKind::Instruction(instruction),
+    });
+}
+
+/// Record that the instruction at offset `lasti` raised an exception of the
+/// named type.
+pub(crate) fn record_exception(frame: usize, lasti: u32, depth: usize, name: String) {
+    record(Event {
+        frame,
+        lasti,
+        depth,
+        kind: EventKind::Exception(name),
+    });
+}
+
+/// Write the buffered events to stderr, oldest first, and clear the buffer
+/// so a dump from a panic hook after an explicit dump doesn't repeat it.
+pub(crate) fn dump() {
+    // try_lock: a panic while the buffer is locked must not deadlock here
+    let events = match EVENTS.try_lock() {
+        Some(mut events) => std::mem::take(&mut *events),
+        None => return,
+    };
+    if events.is_empty() {
+        return;
+    }
+    eprintln!("last {} interpreter events, oldest first:", events.len());
+    for event in &events {
+        let Event {
+            frame,
+            lasti,
+            depth,
+            kind,
+        } = event;
+        match kind {
+            EventKind::Instruction(instruction) => {
+                eprintln!("frame {frame:#x} lasti={lasti:4} depth={depth:3} {instruction:?}")
+            }
+            EventKind::Exception(name) => {
+                eprintln!("frame {frame:#x} lasti={lasti:4} depth={depth:3} raised {name}")
+            }
+        }
+    }
+}
@@ -1,25 +1,28 @@
-use crate::common::{boxvec::BoxVec, lock::PyMutex};
+use crate::common::{boxvec::BoxVec, lock::PyMutex, rc::PyRc};
 use crate::{
     builtins::{
         asyncgenerator::PyAsyncGenWrappedValue,
+        code::{InlineCache, OpSpec, SPECIALIZE_THRESHOLD},
         function::{PyCell, PyCellRef, PyFunction},
         tuple::{PyTuple, PyTupleTyped},
-        PyBaseExceptionRef, PyCode, PyCoroutine, PyDict, PyDictRef, PyGenerator, PyList, PySet,
-        PySlice, PyStr, PyStrInterned, PyStrRef, PyTraceback, PyType,
+        PyBaseException, PyBaseExceptionRef, PyBaseObject, PyCode, PyCoroutine, PyDict, PyDictRef,
+        PyFloat, PyGenerator, PyInt, PyList, PySet, PySlice, PyStr, PyStrInterned, PyStrRef,
+        PyTraceback, PyType,
     },
     bytecode,
     convert::{IntoObject, ToPyResult},
     coroutine::Coro,
-    exceptions::ExceptionCtor,
-    function::{ArgMapping, Either, FuncArgs},
-    protocol::{PyIter, PyIterReturn},
-    scope::Scope,
+    exceptions::{self, ExceptionCtor},
+    function::{ArgMapping, Either, FuncArgs, PySetterValue},
+    protocol::{PyIter, PyIterReturn, PyMapping, PySequence},
     stdlib::builtins,
     vm::{Context, PyMethod},
     AsObject, Py, PyObject, PyObjectRef, PyPayload, PyRef, PyResult, TryFromObject, VirtualMachine,
 };
 use indexmap::IndexMap;
 use itertools::Itertools;
+use num_traits::ToPrimitive;
+use smallvec::SmallVec;
 use std::fmt;
 use std::iter::zip;
 #[cfg(feature = "threading")]
@@ -75,18 +78,37 @@ enum UnwindReason {
     Continue { target: bytecode::Label },
 }
 
+/// how many block frames fit on the block stack before it spills to the heap
+const INLINE_BLOCKS: usize = 4;
+
 #[derive(Debug)]
 struct FrameState {
     // We need 1 stack per frame
     /// The main data frame of the stack machine
     stack: BoxVec<PyObjectRef>,
     /// Block frames, for controlling loops and exceptions
-    blocks: Vec<Block>,
+    blocks: SmallVec<[Block; INLINE_BLOCKS]>,
+    /// send path for the `yield from` delegate this frame is suspended on,
+    /// keyed by the delegate's identity so every resume of a long delegation
+    /// chain doesn't repeat the lookup
+    yield_from: Option<(PyObjectRef, YieldFromPath)>,
     /// index of last instruction ran
     #[cfg(feature = "threading")]
     lasti: u32,
 }
 
+/// How `execute_yield_from` steps the delegate it is suspended on.
+#[derive(Clone, Debug)]
+enum YieldFromPath {
+    /// a builtin generator/coroutine: send through `Coro` directly
+    Builtin,
+    /// the delegate's bound `send` method
+    Send(PyObjectRef),
+    /// no `send` resolved yet; `None` values go through the iterator protocol,
+    /// and the first real value upgrades the path to `Send`
+    Next,
+}
+
 #[cfg(feature = "threading")]
 type Lasti = atomic::AtomicU32;
 #[cfg(not(feature = "threading"))]
@@ -98,7 +120,10 @@ pub struct Frame {
 
     pub fastlocals: PyMutex<Box<[Option<PyObjectRef>]>>,
     pub(crate) cells_frees: Box<[PyCellRef]>,
-    pub locals: ArgMapping,
+    /// the `f_locals` mapping; frames that start with fresh locals keep all
+    /// their variables in `fastlocals` and only materialize the mapping when
+    /// something actually asks for it
+    locals: PyMutex<Option<ArgMapping>>,
     pub globals: PyDictRef,
     pub builtins: PyDictRef,
 
@@ -112,7 +137,11 @@ pub struct Frame {
 
     // member
     pub trace_lines: PyMutex<bool>,
+    pub trace_opcodes: PyMutex<bool>,
     pub temporary_refs: PyMutex<Vec<PyObjectRef>>,
+
+    /// where the value-stack buffer goes back to when the frame drops
+    stack_pool: PyRc<StackPool>,
 }
 
 impl PyPayload for Frame {
@@ -121,19 +150,85 @@ impl PyPayload for Frame {
     }
 }
 
+impl Drop for Frame {
+    fn drop(&mut self) {
+        // hand the value-stack buffer back for reuse by later frames
+        let stack = std::mem::replace(&mut self.state.lock().stack, BoxVec::empty());
+        self.stack_pool.release(stack);
+    }
+}
+
 // Running a frame can result in one of the below:
 pub enum ExecutionResult {
     Return(PyObjectRef),
     Yield(PyObjectRef),
+    /// The frame reached a call of an ordinary Python function: instead of
+    /// recursing into another `Frame::run` on the Rust stack, the suspended
+    /// caller hands `run_frame`'s dispatch loop the callee frame to run, and
+    /// is later resumed with its result via `Py::<Frame>::resume_call`.
+    Call(FrameRef),
+    /// A safepoint honored [`VirtualMachine::request_frame_suspension`]: the
+    /// frame stopped between two instructions with all of its state still in
+    /// the frame object, ready to be resumed anywhere.
+    Suspended,
 }
 
 /// A valid execution result, or an exception
 type FrameResult = PyResult<Option<ExecutionResult>>;
 
+/// capacity classes for pooled value stacks: 8, 16, ..., 1024 slots
+const STACK_POOL_CLASSES: usize = 8;
+/// how many buffers of each class are kept around for reuse
+const STACK_POOL_DEPTH: usize = 16;
+
+/// A pool of value-stack buffers reused between frames, bucketed by capacity
+/// class, so call-heavy code stops allocating a fresh `BoxVec` per frame.
+/// Every frame keeps a handle to the pool of the vm that created it and
+/// returns its buffer there on drop.
+#[derive(Default)]
+pub(crate) struct StackPool {
+    buckets: [PyMutex<Vec<BoxVec<PyObjectRef>>>; STACK_POOL_CLASSES],
+}
+
+impl StackPool {
+    /// The smallest capacity class that fits `capacity`, if any does.
+    fn class(capacity: usize) -> Option<usize> {
+        (0..STACK_POOL_CLASSES).find(|&class| capacity <= 8 << class)
+    }
+
+    /// An empty stack buffer with room for at least `capacity` values,
+    /// reusing a pooled one when its class has any.
+    fn acquire(&self, capacity: usize) -> BoxVec<PyObjectRef> {
+        match Self::class(capacity) {
+            Some(class) => self.buckets[class]
+                .lock()
+                .pop()
+                .unwrap_or_else(|| BoxVec::new(8 << class)),
+            // stacks deeper than the largest class stay frame-private
+            None => BoxVec::new(capacity),
+        }
+    }
+
+    /// Return `stack` for reuse, dropping any values left in it. Buffers of
+    /// unpooled sizes, or arriving while their bucket is full, are freed.
+    fn release(&self, mut stack: BoxVec<PyObjectRef>) {
+        let exact_class = (0..STACK_POOL_CLASSES).find(|&class| stack.capacity() == 8 << class);
+        if let Some(class) = exact_class {
+            stack.clear();
+            let mut bucket = self.buckets[class].lock();
+            if bucket.len() < STACK_POOL_DEPTH {
+                bucket.push(stack);
+            }
+        }
+    }
+}
+
 impl Frame {
+    /// `locals` of `None` defers the mapping to first use; see the field doc.
     pub(crate) fn new(
         code: PyRef<PyCode>,
-        scope: Scope,
+        locals: Option<ArgMapping>,
+        globals: PyDictRef,
         builtins: PyDictRef,
         closure: &[PyCellRef],
         vm: &VirtualMachine,
@@ -144,8 +239,13 @@ impl Frame {
             .collect();
 
         let state = FrameState {
-            stack: BoxVec::new(code.max_stackdepth as usize),
-            blocks: Vec::new(),
+            stack: vm.stack_pool.acquire(code.max_stackdepth as usize),
+            blocks: if code.max_blockdepth as usize <= INLINE_BLOCKS {
+                SmallVec::new()
+            } else {
+                SmallVec::with_capacity(code.max_blockdepth as usize)
+            },
+            yield_from: None,
             #[cfg(feature = "threading")]
             lasti: 0,
         };
@@ -153,15 +253,17 @@ impl Frame {
         Frame {
             fastlocals: PyMutex::new(vec![None; code.varnames.len()].into_boxed_slice()),
             cells_frees,
-            locals: scope.locals,
-            globals: scope.globals,
+            locals: PyMutex::new(locals),
+            globals,
             builtins,
             code,
             lasti: Lasti::new(0),
             state: PyMutex::new(state),
             trace: PyMutex::new(vm.ctx.none()),
             trace_lines: PyMutex::new(true),
+            trace_opcodes: PyMutex::new(false),
             temporary_refs: PyMutex::new(vec![]),
+            stack_pool: vm.stack_pool.clone(),
         }
     }
 
@@ -169,6 +271,31 @@ impl Frame {
         self.code.locations[self.lasti() as usize - 1]
     }
 
+    /// Drop the value stack, block stack, fastlocals and the contents of the
+    /// cells this frame created, breaking reference cycles that go through
+    /// the frame (backs `frame.clear()`). Returns `false` without touching
+    /// anything when the frame is currently executing, recognizable by its
+    /// state being locked by the running dispatch loop.
+    pub(crate) fn try_clear(&self) -> bool {
+        let mut state = match self.state.try_lock() {
+            Some(state) => state,
+            None => return false,
+        };
+        state.stack.clear();
+        state.blocks.clear();
+        state.yield_from = None;
+        drop(state);
+        for fastlocal in self.fastlocals.lock().iter_mut() {
+            *fastlocal = None;
+        }
+        // only the cells belonging to this frame; the trailing free cells are
+        // borrowed from enclosing scopes and stay untouched
+        for cell in self.cells_frees.iter().take(self.code.cellvars.len()) {
+            cell.set(None);
+        }
+        true
+    }
+
     pub fn lasti(&self) -> u32 {
         #[cfg(feature = "threading")]
         {
@@ -180,8 +307,17 @@ impl Frame {
         }
     }
 
+    /// The locals mapping itself, materializing it for frames created
+    /// without one.
+    pub(crate) fn lazy_locals(&self, vm: &VirtualMachine) -> ArgMapping {
+        self.locals
+            .lock()
+            .get_or_insert_with(|| ArgMapping::from_dict_exact(vm.ctx.new_dict()))
+            .clone()
+    }
+
     pub fn locals(&self, vm: &VirtualMachine) -> PyResult<ArgMapping> {
-        let locals = &self.locals;
+        let locals = self.lazy_locals(vm);
         let code = &**self.code;
         let map = &code.varnames;
         let j = std::cmp::min(map.len(), code.varnames.len());
@@ -215,7 +351,7 @@ impl Frame {
                 map_to_dict(&code.freevars, &self.cells_frees[code.cellvars.len()..])?;
             }
         }
-        Ok(locals.clone())
+        Ok(locals)
     }
 }
 
@@ -227,7 +363,6 @@ impl Py<Frame> {
             code: &self.code,
             fastlocals: &self.fastlocals,
             cells_frees: &self.cells_frees,
-            locals: &self.locals,
             globals: &self.globals,
             builtins: &self.builtins,
             lasti: &self.lasti,
@@ -237,7 +372,7 @@ impl Py<Frame> {
         f(exec)
     }
 
-    // #[cfg_attr(feature = "flame-it", flame("Frame"))]
+    // #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     pub fn run(&self, vm: &VirtualMachine) -> PyResult<ExecutionResult> {
         self.with_exec(|mut exec| exec.run(vm))
     }
@@ -255,6 +390,23 @@ impl Py<Frame> {
         })
     }
 
+    /// Continue execution after a call instruction whose callee ran on the VM
+    /// frame stack (see [`ExecutionResult::Call`]), either pushing the value
+    /// the callee returned or unwinding with the exception it raised.
+    pub(crate) fn resume_call(
+        &self,
+        result: PyResult,
+        vm: &VirtualMachine,
+    ) -> PyResult<ExecutionResult> {
+        self.with_exec(|mut exec| match result {
+            Ok(value) => {
+                exec.push_value(value);
+                exec.run(vm)
+            }
+            Err(exception) => exec.resume_raising(exception, vm),
+        })
+    }
+
     pub(crate) fn gen_throw(
         &self,
         vm: &VirtualMachine,
@@ -265,8 +417,21 @@ impl Py<Frame> {
         self.with_exec(|mut exec| exec.gen_throw(vm, exc_type, exc_val, exc_tb))
     }
 
+    /// The delegate this frame is suspended on in a `yield from` / `await`,
+    /// backing `gi_yieldfrom`, `cr_await` and `ag_await`. An executing frame
+    /// has its state locked by the dispatch loop and, like in CPython, is not
+    /// suspended on anything: report no delegate rather than block on the
+    /// lock, so debuggers can poke at running generators from signal handlers
+    /// or other threads.
     pub fn yield_from_target(&self) -> Option<PyObjectRef> {
-        self.with_exec(|exec| exec.yield_from_target().map(PyObject::to_owned))
+        let state = self.state.try_lock()?;
+        match self.code.instructions.get(self.lasti() as usize) {
+            Some(bytecode::CodeUnit {
+                op: bytecode::Instruction::YieldFrom,
+                ..
+            }) => state.stack.last().cloned(),
+            _ => None,
+        }
     }
 
     pub fn is_internal_frame(&self) -> bool {
@@ -297,7 +462,6 @@ struct ExecutingFrame<'a> {
     code: &'a PyRef<PyCode>,
     fastlocals: &'a PyMutex<Box<[Option<PyObjectRef>]>>,
     cells_frees: &'a [PyCellRef],
-    locals: &'a ArgMapping,
     globals: &'a PyDictRef,
     builtins: &'a PyDictRef,
     object: &'a Py<Frame>,
@@ -344,15 +508,42 @@ impl ExecutingFrame<'_> {
         }
     }
 
+    #[inline]
+    fn locals(&self, vm: &VirtualMachine) -> ArgMapping {
+        self.object.lazy_locals(vm)
+    }
+
     fn run(&mut self, vm: &VirtualMachine) -> PyResult<ExecutionResult> {
         flame_guard!(format!("Frame::run({})", self.code.obj_name));
+        #[cfg(feature = "instruction-stats")]
+        crate::stats::record_frame_entry(
+            self.code.source_path.as_str(),
+            self.code.obj_name.as_str(),
+        );
+        if vm.use_tracing.get() {
+            // ask sys.settrace's function for a frame-local trace function;
+            // generators get a fresh "call" event on every resumption, like CPython
+            self.trace_call(vm)?;
+            // the sys.setprofile function sees every frame entry as well
+            self.profile_event("call", None, vm)?;
+        }
+        // row of the last "line" event fired, so each source line is only reported
+        // once even though it usually spans several instructions
+        let mut traced_row = None;
         // Execute until return or exception:
         let instrs = &self.code.instructions;
         let mut arg_state = bytecode::OpArgState::default();
+        // Generator and coroutine frames are resumed by arbitrary Rust call
+        // sites, not the dispatch loop, so they can't be parked; a suspension
+        // request stays armed for the enclosing ordinary frame.
+        let can_suspend = !self
+            .code
+            .flags
+            .intersects(bytecode::CodeFlags::IS_GENERATOR | bytecode::CodeFlags::IS_COROUTINE);
         #[allow(unused_variables)]
         #[allow(unused_mut)]
         let mut gc_count = 0;
-        loop {
+        let result = loop {
             #[cfg(feature = "gc_bacon")]
             {
                 gc_count += 1;
@@ -362,8 +553,35 @@ impl ExecutingFrame<'_> {
                 }
             }
             let idx = self.lasti() as usize;
+            if vm.use_tracing.get() {
+                if *self.object.trace_lines.lock() {
+                    let row = self.code.locations[idx].row();
+                    if traced_row != Some(row) {
+                        traced_row = Some(row);
+                        self.trace_local_event("line", None, vm)?;
+                    }
+                }
+                // sub-line granularity for debuggers that opt in via f_trace_opcodes
+                if *self.object.trace_opcodes.lock() {
+                    self.trace_local_event("opcode", None, vm)?;
+                }
+            }
+            // A requested suspension parks the frame between instructions, so
+            // nothing is skipped or repeated on resume.
+            if can_suspend && vm.take_suspend_request() {
+                break Ok(ExecutionResult::Suspended);
+            }
             self.update_lasti(|i| *i += 1);
             let bytecode::CodeUnit { op, arg } = instrs[idx];
+            #[cfg(feature = "instruction-stats")]
+            crate::stats::record_instruction(op);
+            #[cfg(feature = "execution-trace")]
+            crate::exec_trace::record_instruction(
+                self.object.get_id(),
+                idx as u32,
+                self.state.stack.len(),
+                op,
+            );
             let arg = arg_state.extend(arg);
             let mut do_extend_arg = false;
             let result = self.execute_instruction(op, arg, &mut do_extend_arg, vm);
@@ -374,6 +592,13 @@ impl ExecutingFrame<'_> {
                 }
                 // Instruction raised an exception
                 Err(exception) => {
+                    #[cfg(feature = "execution-trace")]
+                    crate::exec_trace::record_exception(
+                        self.object.get_id(),
+                        idx as u32,
+                        self.state.stack.len(),
+                        exception.class().name().to_string(),
+                    );
                     #[cold]
                     fn handle_exception(
                         frame: &mut ExecutingFrame,
@@ -387,12 +612,8 @@ impl ExecutingFrame<'_> {
 
                         let loc = frame.code.locations[idx];
                         let next = exception.traceback();
-                        let new_traceback = PyTraceback::new(
-                            next,
-                            frame.object.to_owned(),
-                            frame.lasti(),
-                            loc.row(),
-                        );
+                        let new_traceback =
+                            PyTraceback::new(next, frame.object.to_owned(), frame.lasti(), loc);
                         vm_trace!("Adding to traceback: {:?} {:?}", new_traceback, loc.row());
                         exception.set_traceback(Some(new_traceback.into_ref(&vm.ctx)));
 
@@ -401,6 +622,9 @@ impl ExecutingFrame<'_> {
                         frame.unwind_blocks(vm, UnwindReason::Raising { exception })
                     }
 
+                    if vm.use_tracing.get() {
+                        self.trace_exception(&exception, vm)?;
+                    }
                     match handle_exception(self, exception, idx, vm) {
                         Ok(None) => {}
                         Ok(Some(result)) => break Ok(result),
@@ -413,9 +637,165 @@ impl ExecutingFrame<'_> {
             if !do_extend_arg {
                 arg_state.reset()
             }
+        };
+        if vm.use_tracing.get() {
+            match &result {
+                Ok(ExecutionResult::Return(value)) | Ok(ExecutionResult::Yield(value)) => {
+                    self.trace_local_event("return", Some(value.clone()), vm)?;
+                    self.profile_event("return", Some(value.clone()), vm)?;
+                }
+                // the frame isn't leaving, it's only paused for the duration
+                // of the call or the suspension: no "return" event
+                Ok(ExecutionResult::Call(_)) | Ok(ExecutionResult::Suspended) => {}
+                Err(_) => {
+                    // the frame is being popped because of the exception; like
+                    // CPython, report that to the tracer and profiler as a None
+                    // return. The original exception wins over any error from
+                    // the tracer.
+                    let _ = self.trace_local_event("return", None, vm);
+                    let _ = self.profile_event("return", None, vm);
+                }
+            }
+        }
+        result
+    }
+
+    /// Unwind from an exception raised by a callee that ran on the VM frame
+    /// stack, exactly as the dispatch loop would have had the call been made
+    /// recursively: the traceback entry points at the call instruction, which
+    /// `lasti` has already moved past.
+    fn resume_raising(
+        &mut self,
+        exception: PyBaseExceptionRef,
+        vm: &VirtualMachine,
+    ) -> PyResult<ExecutionResult> {
+        let idx = self.lasti() as usize - 1;
+        let loc = self.code.locations[idx];
+        let new_traceback = PyTraceback::new(
+            exception.traceback(),
+            self.object.to_owned(),
+            self.lasti(),
+            loc,
+        );
+        exception.set_traceback(Some(new_traceback.into_ref(&vm.ctx)));
+
+        vm.contextualize_exception(&exception);
+
+        match self.unwind_blocks(vm, UnwindReason::Raising { exception })? {
+            Some(result) => Ok(result),
+            None => self.run(vm),
+        }
+    }
+
+    /// invoke a trace or profile function with `(frame, event, arg)`, with
+    /// tracing suspended so the trace function itself isn't traced (like
+    /// CPython's `call_trampoline`)
+    fn run_trace_callable(
+        &mut self,
+        func: PyObjectRef,
+        event: &str,
+        arg: Option<PyObjectRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult {
+        vm.use_tracing.set(false);
+        let result = func.call(
+            (
+                self.object.to_owned(),
+                vm.ctx.new_str(event),
+                vm.unwrap_or_none(arg),
+            ),
+            vm,
+        );
+        // the trace function may have called sys.settrace itself, so recompute
+        // instead of restoring the previous value
+        let trace_is_none = vm.is_none(&vm.trace_func.borrow());
+        let profile_is_none = vm.is_none(&vm.profile_func.borrow());
+        vm.use_tracing.set(!(trace_is_none && profile_is_none));
+        result
+    }
+
+    /// fire the "call" event: the global trace function set by `sys.settrace`
+    /// decides whether this frame gets a local trace function (stored in
+    /// `f_trace`), which then receives the line/return/exception events
+    fn trace_call(&mut self, vm: &VirtualMachine) -> PyResult<()> {
+        let global_trace = vm.trace_func.borrow().clone();
+        if vm.is_none(&global_trace) {
+            return Ok(());
+        }
+        let local = self
+            .run_trace_callable(global_trace, "call", None, vm)
+            .map_err(|e| {
+                // a failing trace function disables tracing for this frame
+                *self.object.trace.lock() = vm.ctx.none();
+                e
+            })?;
+        if !vm.is_none(&local) {
+            *self.object.trace.lock() = local;
+        }
+        Ok(())
+    }
+
+    /// fire a "line"/"return"/"exception" event on the frame-local trace
+    /// function, if any. Its return value becomes the new local trace
+    /// function; returning None turns tracing off for the rest of the frame.
+    fn trace_local_event(
+        &mut self,
+        event: &str,
+        arg: Option<PyObjectRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult<()> {
+        let trace_func = self.object.trace.lock().clone();
+        if vm.is_none(&trace_func) {
+            return Ok(());
+        }
+        let result = self
+            .run_trace_callable(trace_func, event, arg, vm)
+            .map_err(|e| {
+                *self.object.trace.lock() = vm.ctx.none();
+                e
+            })?;
+        *self.object.trace.lock() = result;
+        Ok(())
+    }
+
+    /// fire a sys.setprofile event for this frame. Unlike trace functions the
+    /// profiler gets no line events and its return value is ignored; if it
+    /// raises, profiling is disabled and the error propagated.
+    fn profile_event(
+        &mut self,
+        event: &str,
+        arg: Option<PyObjectRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult<()> {
+        let profile_func = vm.profile_func.borrow().clone();
+        if vm.is_none(&profile_func) {
+            return Ok(());
+        }
+        match self.run_trace_callable(profile_func, event, arg, vm) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                vm.profile_func.replace(vm.ctx.none());
+                vm.use_tracing.set(!vm.is_none(&vm.trace_func.borrow()));
+                Err(e)
+            }
         }
     }
 
+    /// report a raised exception to the tracer as `(type, value, traceback)`,
+    /// before the block stack is unwound to find a handler
+    fn trace_exception(
+        &mut self,
+        exception: &PyBaseExceptionRef,
+        vm: &VirtualMachine,
+    ) -> PyResult<()> {
+        let arg = vm.new_tuple((
+            exception.class().to_owned(),
+            exception.clone(),
+            vm.unwrap_or_none(exception.traceback().map(Into::into)),
+        ));
+        self.trace_local_event("exception", Some(arg.into()), vm)
+    }
+
     fn yield_from_target(&self) -> Option<&PyObject> {
         if let Some(bytecode::CodeUnit {
             op: bytecode::Instruction::YieldFrom,
@@ -455,6 +835,7 @@ impl ExecutingFrame<'_> {
                 };
                 return ret.map(ExecutionResult::Yield).or_else(|err| {
                     self.pop_value();
+                    self.state.yield_from = None;
                     self.update_lasti(|i| *i += 1);
                     if err.fast_isinstance(vm.ctx.exceptions.stop_iteration) {
                         let val = vm.unwrap_or_none(err.get_arg(0));
@@ -500,6 +881,7 @@ impl ExecutingFrame<'_> {
         vm: &VirtualMachine,
     ) -> FrameResult {
         vm.check_signals()?;
+        vm.check_instruction_budget()?;
 
         flame_guard!(format!("Frame::execute_instruction({:?})", instruction));
 
@@ -522,10 +904,45 @@ impl ExecutingFrame<'_> {
         }
 
         match instruction {
+            // the hottest opcodes first, in the order the instruction-stats
+            // counters report them on typical workloads, so the dispatch
+            // code for the common cases stays together
+            bytecode::Instruction::LoadFast(idx) => {
+                #[cold]
+                fn reference_error(
+                    varname: &'static PyStrInterned,
+                    vm: &VirtualMachine,
+                ) -> PyBaseExceptionRef {
+                    vm.new_exception_msg(
+                        vm.ctx.exceptions.unbound_local_error.to_owned(),
+                        format!("local variable '{varname}' referenced before assignment",),
+                    )
+                }
+                let idx = idx.get(arg) as usize;
+                let x = self.fastlocals.lock()[idx]
+                    .clone()
+                    .ok_or_else(|| reference_error(self.code.varnames[idx], vm))?;
+                self.push_value(x);
+                Ok(None)
+            }
             bytecode::Instruction::LoadConst { idx } => {
                 self.push_value(self.code.constants[idx.get(arg) as usize].clone().into());
                 Ok(None)
             }
+            bytecode::Instruction::CallFunctionPositional { nargs } => {
+                let nargs = nargs.get(arg);
+                match self.execute_vectorcall(nargs, false, vm) {
+                    Some(result) => result,
+                    None => {
+                        let args = self.collect_positional_args(nargs);
+                        self.execute_call(args, vm)
+                    }
+                }
+            }
+            bytecode::Instruction::BinaryOperation { op } => self.execute_binop(vm, op.get(arg)),
+            bytecode::Instruction::JumpIfFalse { target } => {
+                self.jump_if(vm, target.get(arg), false)
+            }
             bytecode::Instruction::ImportName { idx } => {
                 self.import(vm, Some(self.code.names[idx.get(arg) as usize]))?;
                 Ok(None)
@@ -543,27 +960,9 @@ impl ExecutingFrame<'_> {
                 self.push_value(obj);
                 Ok(None)
             }
-            bytecode::Instruction::LoadFast(idx) => {
-                #[cold]
-                fn reference_error(
-                    varname: &'static PyStrInterned,
-                    vm: &VirtualMachine,
-                ) -> PyBaseExceptionRef {
-                    vm.new_exception_msg(
-                        vm.ctx.exceptions.unbound_local_error.to_owned(),
-                        format!("local variable '{varname}' referenced before assignment",),
-                    )
-                }
-                let idx = idx.get(arg) as usize;
-                let x = self.fastlocals.lock()[idx]
-                    .clone()
-                    .ok_or_else(|| reference_error(self.code.varnames[idx], vm))?;
-                self.push_value(x);
-                Ok(None)
-            }
             bytecode::Instruction::LoadNameAny(idx) => {
                 let name = self.code.names[idx.get(arg) as usize];
-                let result = self.locals.mapping().subscript(name, vm);
+                let result = self.locals(vm).mapping().subscript(name, vm);
                 match result {
                     Ok(x) => self.push_value(x),
                     Err(e) if e.fast_isinstance(vm.ctx.exceptions.key_error) => {
@@ -574,8 +973,8 @@ impl ExecutingFrame<'_> {
                 Ok(None)
             }
             bytecode::Instruction::LoadGlobal(idx) => {
-                let name = &self.code.names[idx.get(arg) as usize];
-                let x = self.load_global_or_builtin(name, vm)?;
+                let name = self.code.names[idx.get(arg) as usize];
+                let x = self.load_global_cached(name, vm)?;
                 self.push_value(x);
                 Ok(None)
             }
@@ -590,7 +989,7 @@ impl ExecutingFrame<'_> {
             bytecode::Instruction::LoadClassDeref(i) => {
                 let i = i.get(arg) as usize;
                 let name = self.code.freevars[i - self.code.cellvars.len()];
-                let value = self.locals.mapping().subscript(name, vm).ok();
+                let value = self.locals(vm).mapping().subscript(name, vm).ok();
                 self.push_value(match value {
                     Some(v) => v,
                     None => self.cells_frees[i]
@@ -607,7 +1006,9 @@ impl ExecutingFrame<'_> {
             bytecode::Instruction::StoreLocal(idx) => {
                 let name = self.code.names[idx.get(arg) as usize];
                 let value = self.pop_value();
-                self.locals.mapping().ass_subscript(name, Some(value), vm)?;
+                self.locals(vm)
+                    .mapping()
+                    .ass_subscript(name, Some(value), vm)?;
                 Ok(None)
             }
             bytecode::Instruction::StoreGlobal(idx) => {
@@ -627,7 +1028,7 @@ impl ExecutingFrame<'_> {
             }
             bytecode::Instruction::DeleteLocal(idx) => {
                 let name = self.code.names[idx.get(arg) as usize];
-                let res = self.locals.mapping().ass_subscript(name, None, vm);
+                let res = self.locals(vm).mapping().ass_subscript(name, None, vm);
 
                 match res {
                     Ok(()) => {}
@@ -787,7 +1188,6 @@ impl ExecutingFrame<'_> {
                 dict.set_item(&*key, value, vm)?;
                 Ok(None)
             }
-            bytecode::Instruction::BinaryOperation { op } => self.execute_binop(vm, op.get(arg)),
             bytecode::Instruction::BinaryOperationInplace { op } => {
                 self.execute_binop_inplace(vm, op.get(arg))
             }
@@ -861,18 +1261,17 @@ impl ExecutingFrame<'_> {
                         context_manager.class().name(),
                     )
                 };
-                let enter_res = vm
-                    .get_special_method(&context_manager, identifier!(vm, __enter__))?
-                    .ok_or_else(|| vm.new_type_error(error_string()))?
-                    .invoke((), vm)?;
-
-                let exit = context_manager
-                    .get_attr(identifier!(vm, __exit__), vm)
-                    .map_err(|_exc| {
-                        vm.new_type_error({
-                            format!("'{} (missed __exit__ method)", error_string())
-                        })
-                    })?;
+                let (enter, exit) = self.with_methods_cached(
+                    &context_manager,
+                    identifier!(vm, __enter__),
+                    identifier!(vm, __exit__),
+                    vm,
+                )?;
+                let enter = enter.ok_or_else(|| vm.new_type_error(error_string()))?;
+                let exit = exit.ok_or_else(|| {
+                    vm.new_type_error(format!("'{} (missed __exit__ method)", error_string()))
+                })?;
+                let enter_res = enter.invoke((), vm)?;
                 self.push_value(exit);
                 self.push_block(BlockType::Finally {
                     handler: end.get(arg),
@@ -888,18 +1287,17 @@ impl ExecutingFrame<'_> {
                         mgr.class().name(),
                     )
                 };
-
-                let aenter_res = vm
-                    .get_special_method(&mgr, identifier!(vm, __aenter__))?
-                    .ok_or_else(|| vm.new_type_error(error_string()))?
-                    .invoke((), vm)?;
-                let aexit = mgr
-                    .get_attr(identifier!(vm, __aexit__), vm)
-                    .map_err(|_exc| {
-                        vm.new_type_error({
-                            format!("'{} (missed __aexit__ method)", error_string())
-                        })
-                    })?;
+                let (aenter, aexit) = self.with_methods_cached(
+                    &mgr,
+                    identifier!(vm, __aenter__),
+                    identifier!(vm, __aexit__),
+                    vm,
+                )?;
+                let aenter = aenter.ok_or_else(|| vm.new_type_error(error_string()))?;
+                let aexit = aexit.ok_or_else(|| {
+                    vm.new_type_error(format!("'{} (missed __aexit__ method)", error_string()))
+                })?;
+                let aenter_res = aenter.invoke((), vm)?;
                 self.push_value(aexit);
                 self.push_value(aenter_res);
 
@@ -1012,17 +1410,50 @@ impl ExecutingFrame<'_> {
                     Err(exc.downcast().unwrap())
                 }
             }
+            bytecode::Instruction::GetLen => {
+                let len = self.last_value_ref().length(vm)?;
+                self.push_value(vm.ctx.new_int(len).into());
+                Ok(None)
+            }
+            bytecode::Instruction::MatchMapping => {
+                let is_mapping = self.match_mapping_check(vm);
+                self.push_value(vm.ctx.new_bool(is_mapping).into());
+                Ok(None)
+            }
+            bytecode::Instruction::MatchSequence => {
+                let is_sequence = self.match_sequence_check(vm);
+                self.push_value(vm.ctx.new_bool(is_sequence).into());
+                Ok(None)
+            }
+            bytecode::Instruction::MatchKeys => self.execute_match_keys(vm),
+            bytecode::Instruction::CopyDictWithoutKeys => self.execute_copy_dict_without_keys(vm),
+            bytecode::Instruction::MatchClass { nargs } => {
+                self.execute_match_class(nargs.get(arg) as usize, vm)
+            }
+            bytecode::Instruction::ExceptionGroupMatch => {
+                let typ = self.pop_value();
+                let exc = self
+                    .pop_value()
+                    .downcast::<PyBaseException>()
+                    .expect("ExceptionGroupMatch expects an exception at TOS1");
+                let (rest, matched) = exceptions::exception_group_match(exc, &typ, vm)?;
+                self.push_value(rest);
+                self.push_value(matched);
+                Ok(None)
+            }
             bytecode::Instruction::ForIter { target } => self.execute_for_iter(vm, target.get(arg)),
             bytecode::Instruction::MakeFunction(flags) => {
                 self.execute_make_function(vm, flags.get(arg))
             }
-            bytecode::Instruction::CallFunctionPositional { nargs } => {
-                let args = self.collect_positional_args(nargs.get(arg));
-                self.execute_call(args, vm)
-            }
             bytecode::Instruction::CallFunctionKeyword { nargs } => {
-                let args = self.collect_keyword_args(nargs.get(arg));
-                self.execute_call(args, vm)
+                let nargs = nargs.get(arg);
+                match self.execute_vectorcall(nargs, true, vm) {
+                    Some(result) => result,
+                    None => {
+                        let args = self.collect_keyword_args(nargs, vm);
+                        self.execute_call(args, vm)
+                    }
+                }
             }
             bytecode::Instruction::CallFunctionEx { has_kwargs } => {
                 let args = self.collect_ex_args(vm, has_kwargs.get(arg))?;
@@ -1031,15 +1462,15 @@ impl ExecutingFrame<'_> {
             bytecode::Instruction::LoadMethod { idx } => {
                 let obj = self.pop_value();
                 let method_name = self.code.names[idx.get(arg) as usize];
-                let method = PyMethod::get(obj, method_name, vm)?;
-                let (target, is_method, func) = match method {
-                    PyMethod::Function { target, func } => (target, true, func),
-                    PyMethod::Attribute(val) => (vm.ctx.none(), false, val),
+                let method = self.load_method_cached(obj, method_name, vm)?;
+                // Mirrors CPython's target==NULL convention: an attribute
+                // that is not an unbound method leaves the sentinel in the
+                // `self` slot, and `CallMethod*` checks it by identity.
+                let (target, func) = match method {
+                    PyMethod::Function { target, func } => (target, func),
+                    PyMethod::Attribute(val) => (vm.ctx.no_self_sentinel.clone(), val),
                 };
-                // TODO: figure out a better way to communicate PyMethod::Attribute - CPython uses
-                // target==NULL, maybe we could use a sentinel value or something?
                 self.push_value(target);
-                self.push_value(vm.ctx.new_bool(is_method).into());
                 self.push_value(func);
                 Ok(None)
             }
@@ -1048,7 +1479,7 @@ impl ExecutingFrame<'_> {
                 self.execute_method_call(args, vm)
             }
             bytecode::Instruction::CallMethodKeyword { nargs } => {
-                let args = self.collect_keyword_args(nargs.get(arg));
+                let args = self.collect_keyword_args(nargs.get(arg), vm);
                 self.execute_method_call(args, vm)
             }
             bytecode::Instruction::CallMethodEx { has_kwargs } => {
@@ -1060,9 +1491,6 @@ impl ExecutingFrame<'_> {
                 Ok(None)
             }
             bytecode::Instruction::JumpIfTrue { target } => self.jump_if(vm, target.get(arg), true),
-            bytecode::Instruction::JumpIfFalse { target } => {
-                self.jump_if(vm, target.get(arg), false)
-            }
             bytecode::Instruction::JumpIfTrueOrPop { target } => {
                 self.jump_if_or_pop(vm, target.get(arg), true)
             }
@@ -1129,7 +1557,7 @@ impl ExecutingFrame<'_> {
             })
     }
 
-    #[cfg_attr(feature = "flame-it", flame("Frame"))]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     fn unpack_elements(&mut self, vm: &VirtualMachine, size: usize) -> PyResult<Vec<PyObjectRef>> {
         let mut result = Vec::<PyObjectRef>::new();
         for element in self.pop_multiple(size) {
@@ -1139,7 +1567,7 @@ impl ExecutingFrame<'_> {
         Ok(result)
     }
 
-    #[cfg_attr(feature = "flame-it", flame("Frame"))]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     fn import(&mut self, vm: &VirtualMachine, module: Option<&Py<PyStr>>) -> PyResult<()> {
         let module = module.unwrap_or(vm.ctx.empty_str);
         let from_list = <Option<PyTupleTyped<PyStrRef>>>::try_from_object(vm, self.pop_value())?;
@@ -1151,7 +1579,7 @@ impl ExecutingFrame<'_> {
         Ok(())
     }
 
-    #[cfg_attr(feature = "flame-it", flame("Frame"))]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     fn import_from(&mut self, vm: &VirtualMachine, idx: bytecode::NameIdx) -> PyResult {
         let module = self.last_value();
         let name = self.code.names[idx as usize];
@@ -1170,12 +1598,14 @@ impl ExecutingFrame<'_> {
         sys_modules.get_item(&full_mod_name, vm).map_err(|_| err())
     }
 
-    #[cfg_attr(feature = "flame-it", flame("Frame"))]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
+    #[cold]
     fn import_star(&mut self, vm: &VirtualMachine) -> PyResult<()> {
         let module = self.pop_value();
 
         // Grab all the names from the module and put them in the context
         if let Some(dict) = module.dict() {
+            let locals = self.locals(vm);
             let filter_pred: Box<dyn Fn(&str) -> bool> =
                 if let Ok(all) = dict.get_item(identifier!(vm, __all__), vm) {
                     let all: Vec<PyStrRef> = all.try_to_value(vm)?;
@@ -1187,12 +1617,14 @@ impl ExecutingFrame<'_> {
                 } else {
                     Box::new(|name| !name.starts_with('_'))
                 };
+            let mut pairs = Vec::new();
             for (k, v) in dict {
                 let k = PyStrRef::try_from_object(vm, k)?;
                 if filter_pred(k.as_str()) {
-                    self.locals.mapping().ass_subscript(&k, Some(v), vm)?;
+                    pairs.push((k, v));
                 }
             }
+            locals.update_from_pairs(pairs, vm)?;
         }
         Ok(())
     }
@@ -1201,7 +1633,7 @@ impl ExecutingFrame<'_> {
     /// The reason for unwinding gives a hint on what to do when
     /// unwinding a block.
     /// Optionally returns an exception.
-    #[cfg_attr(feature = "flame-it", flame("Frame"))]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     fn unwind_blocks(&mut self, vm: &VirtualMachine, reason: UnwindReason) -> FrameResult {
         // First unwind all existing blocks on the block stack:
         while let Some(block) = self.current_block() {
@@ -1274,11 +1706,83 @@ impl ExecutingFrame<'_> {
     fn execute_subscript(&mut self, vm: &VirtualMachine) -> FrameResult {
         let b_ref = self.pop_value();
         let a_ref = self.pop_value();
-        let value = a_ref.get_item(&*b_ref, vm)?;
+        let value = match self.subscript_quickened(&a_ref, &b_ref, vm) {
+            Some(result) => result,
+            None => a_ref.get_item(&*b_ref, vm),
+        }?;
         self.push_value(value);
         Ok(None)
     }
 
+    /// specialized `list[int]`/`dict[str]` fast paths for hot subscripts
+    /// (quickening); `None` falls through to the generic `get_item`, which
+    /// also produces the proper `IndexError`/`KeyError` when a specialized
+    /// access doesn't find the element
+    fn subscript_quickened(
+        &self,
+        a: &PyObject,
+        b: &PyObject,
+        vm: &VirtualMachine,
+    ) -> Option<PyResult> {
+        let spec = OpSpec::of_subscript(a, b, vm)?;
+        if !self.advance_quickening(spec) {
+            return None;
+        }
+        match spec {
+            OpSpec::ListInt => {
+                let vec = a.payload::<PyList>()?.borrow_vec();
+                let i = b.payload::<PyInt>()?.as_bigint().to_isize()?;
+                let i = if i < 0 { i + vec.len() as isize } else { i };
+                let value = vec.get(usize::try_from(i).ok()?)?.clone();
+                Some(Ok(value))
+            }
+            OpSpec::DictStr => {
+                let dict = a.downcast_ref::<PyDict>()?;
+                let key = b.downcast_ref::<PyStr>()?;
+                match dict.get_item_opt(key, vm) {
+                    Ok(Some(value)) => Some(Ok(value)),
+                    // missing key: let the generic path raise the KeyError
+                    Ok(None) => None,
+                    Err(e) => Some(Err(e)),
+                }
+            }
+            // binop-only specs never classify for a subscript
+            OpSpec::IntInt | OpSpec::FloatFloat => None,
+        }
+    }
+
+    /// Advance the PEP 659-style warmup/specialization state of the current
+    /// instruction for the observed operand `spec`; returns whether the
+    /// specialized fast path should run. A spec change restarts the warmup,
+    /// so sites with unstable operand types stay on the generic path.
+    fn advance_quickening(&self, spec: OpSpec) -> bool {
+        let mut cache = self.code.inline_caches[self.lasti() as usize - 1].lock();
+        match &mut *cache {
+            InlineCache::Adaptive {
+                spec: cached_spec,
+                counter,
+                specialized,
+            } if *cached_spec == spec => {
+                if !*specialized {
+                    *counter += 1;
+                    if *counter < SPECIALIZE_THRESHOLD {
+                        return false;
+                    }
+                    *specialized = true;
+                }
+                true
+            }
+            slot => {
+                *slot = InlineCache::Adaptive {
+                    spec,
+                    counter: 1,
+                    specialized: false,
+                };
+                false
+            }
+        }
+    }
+
     fn execute_store_subscript(&mut self, vm: &VirtualMachine) -> FrameResult {
         let idx = self.pop_value();
         let obj = self.pop_value();
@@ -1296,10 +1800,8 @@ impl ExecutingFrame<'_> {
 
     fn execute_build_map(&mut self, vm: &VirtualMachine, size: u32) -> FrameResult {
         let size = size as usize;
-        let map_obj = vm.ctx.new_dict();
-        for (key, value) in self.pop_multiple(2 * size).tuples() {
-            map_obj.set_item(&*key, value, vm)?;
-        }
+        let map_obj = vm.ctx.new_dict_with_capacity(size);
+        map_obj.extend_from_iter(vm, self.pop_multiple(2 * size).tuples())?;
 
         self.push_value(map_obj.into());
         Ok(None)
@@ -1313,7 +1815,7 @@ impl ExecutingFrame<'_> {
             let dict: PyDictRef = obj.downcast().map_err(|obj| {
                 vm.new_type_error(format!("'{}' object is not a mapping", obj.class().name()))
             })?;
-            for (key, value) in dict {
+            for (key, _value) in &dict {
                 if map_obj.contains_key(&*key, vm) {
                     let key_repr = &key.repr(vm)?;
                     let msg = format!(
@@ -1322,8 +1824,8 @@ impl ExecutingFrame<'_> {
                     );
                     return Err(vm.new_type_error(msg));
                 }
-                map_obj.set_item(&*key, value, vm)?;
             }
+            map_obj.extend_from_iter(vm, &dict)?;
         }
 
         self.push_value(map_obj.into());
@@ -1352,17 +1854,19 @@ impl ExecutingFrame<'_> {
         }
     }
 
-    fn collect_keyword_args(&mut self, nargs: u32) -> FuncArgs {
+    fn collect_keyword_args(&mut self, nargs: u32, vm: &VirtualMachine) -> FuncArgs {
         let kwarg_names = self
             .pop_value()
             .downcast::<PyTuple>()
             .expect("kwarg names should be tuple of strings");
         let args = self.pop_multiple(nargs as usize);
 
+        // the names come from the code object's constants, so they are
+        // interned and can be borrowed instead of copied
         let kwarg_names = kwarg_names
             .as_slice()
             .iter()
-            .map(|pyobj| pyobj.payload::<PyStr>().unwrap().as_ref().to_owned());
+            .map(|name| FuncArgs::kwarg_name(name, vm));
         FuncArgs::with_kwargs_names(args, kwarg_names)
     }
 
@@ -1377,34 +1881,112 @@ impl ExecutingFrame<'_> {
                 let key = key
                     .payload_if_subclass::<PyStr>(vm)
                     .ok_or_else(|| vm.new_type_error("keywords must be strings".to_owned()))?;
-                kwargs.insert(key.as_str().to_owned(), value);
+                kwargs.insert(key.as_str().to_owned().into(), value);
             }
             kwargs
         } else {
             IndexMap::new()
         };
         let args = self.pop_value();
-        let args = args.try_to_value(vm)?;
-        Ok(FuncArgs { args, kwargs })
+        let args: Vec<PyObjectRef> = args.try_to_value(vm)?;
+        Ok(FuncArgs {
+            args: args.into(),
+            kwargs,
+        })
+    }
+
+    /// The function about to be called, if the call can run on the VM frame
+    /// stack (see [`ExecutionResult::Call`]) instead of recursing into
+    /// `Frame::run`. Generator and coroutine frames are resumed from
+    /// arbitrary Rust call sites rather than by `run_frame`'s dispatch loop,
+    /// so calls they make stay on the recursive path; so do all calls while
+    /// tracing is active, which relies on the nested `call` events the
+    /// recursive path reports.
+    fn stackless_callee<'a>(
+        &self,
+        func: &'a PyObject,
+        vm: &VirtualMachine,
+    ) -> Option<&'a PyFunction> {
+        if vm.use_tracing.get()
+            || self
+                .code
+                .flags
+                .intersects(bytecode::CodeFlags::IS_GENERATOR | bytecode::CodeFlags::IS_COROUTINE)
+        {
+            return None;
+        }
+        func.payload::<PyFunction>()
+            .filter(|func| func.is_stackless_callable())
+    }
+
+    /// Try to run a call through the callee's vectorcall slot, passing the
+    /// arguments as a borrowed slice of the value stack instead of boxing
+    /// them into [`FuncArgs`]. Returns `None`, leaving the stack untouched,
+    /// when the callee does not support it (or runs stackless and should
+    /// keep going through [`Self::execute_call`]).
+    fn execute_vectorcall(
+        &mut self,
+        nargs: u32,
+        has_kwnames: bool,
+        vm: &VirtualMachine,
+    ) -> Option<FrameResult> {
+        let nvalues = nargs as usize + has_kwnames as usize;
+        let stack = self.state.stack.as_slice();
+        let func = &stack[stack.len() - nvalues - 1];
+        let vectorcall = func.class().slots.vectorcall.load()?;
+        if self.stackless_callee(func, vm).is_some() {
+            return None;
+        }
+        let kwnames = if has_kwnames {
+            let names = stack
+                .last()
+                .unwrap()
+                .downcast_ref::<PyTuple>()
+                .expect("kwarg names should be tuple of strings");
+            Some(names)
+        } else {
+            None
+        };
+        let args = &stack[stack.len() - nvalues..stack.len() - nvalues + nargs as usize];
+        let result = vectorcall(func, args, kwnames, vm);
+        self.pop_multiple(nvalues + 1).for_each(drop);
+        match result {
+            Ok(value) => {
+                self.push_value(value);
+                Some(Ok(None))
+            }
+            Err(exc) => Some(Err(exc)),
+        }
     }
 
     #[inline]
     fn execute_call(&mut self, args: FuncArgs, vm: &VirtualMachine) -> FrameResult {
         let func_ref = self.pop_value();
+        if let Some(func) = self.stackless_callee(&func_ref, vm) {
+            let frame = func.create_frame(args, None, vm)?;
+            return Ok(Some(ExecutionResult::Call(frame)));
+        }
         let value = func_ref.call(args, vm)?;
         self.push_value(value);
         Ok(None)
     }
 
     #[inline]
-    fn execute_method_call(&mut self, args: FuncArgs, vm: &VirtualMachine) -> FrameResult {
+    fn execute_method_call(&mut self, mut args: FuncArgs, vm: &VirtualMachine) -> FrameResult {
         let func = self.pop_value();
-        let is_method = self.pop_value().is(&vm.ctx.true_value);
         let target = self.pop_value();
+        let is_method = !target.is(&vm.ctx.no_self_sentinel);
+        if is_method {
+            if let Some(func) = self.stackless_callee(&func, vm) {
+                args.prepend_arg(target);
+                let frame = func.create_frame(args, None, vm)?;
+                return Ok(Some(ExecutionResult::Call(frame)));
+            }
+        }
         let method = if is_method {
             PyMethod::Function { target, func }
         } else {
-            drop(target); // should be None
+            drop(target); // just the sentinel
             PyMethod::Attribute(func)
         };
         let value = method.invoke(args, vm)?;
@@ -1455,18 +2037,42 @@ impl ExecutingFrame<'_> {
         })
     }
 
+    /// The cached send path for `gen`, resolving and remembering it when the
+    /// delegate differs from the one the frame was last suspended on.
+    fn yield_from_path(&mut self, gen: &PyObject) -> YieldFromPath {
+        if let Some((cached, path)) = &self.state.yield_from {
+            if cached.is(gen) {
+                return path.clone();
+            }
+        }
+        let path = if self.builtin_coro(gen).is_some() {
+            YieldFromPath::Builtin
+        } else {
+            YieldFromPath::Next
+        };
+        self.state.yield_from = Some((gen.to_owned(), path.clone()));
+        path
+    }
+
     fn _send(
-        &self,
+        &mut self,
         gen: &PyObject,
         val: PyObjectRef,
         vm: &VirtualMachine,
     ) -> PyResult<PyIterReturn> {
-        match self.builtin_coro(gen) {
-            Some(coro) => coro.send(gen, val, vm),
+        match self.yield_from_path(gen) {
+            YieldFromPath::Builtin => {
+                let coro = self.builtin_coro(gen).expect("path cached as builtin");
+                coro.send(gen, val, vm)
+            }
+            // like CPython, non-builtin delegates are stepped through the
+            // iterator protocol whenever the value sent is `None`
             // FIXME: turn return type to PyResult<PyIterReturn> then ExecutionResult will be simplified
-            None if vm.is_none(&val) => PyIter::new(gen).next(vm),
-            None => {
+            _ if vm.is_none(&val) => PyIter::new(gen).next(vm),
+            YieldFromPath::Send(meth) => PyIterReturn::from_pyresult(meth.call((val,), vm), vm),
+            YieldFromPath::Next => {
                 let meth = gen.get_attr("send", vm)?;
+                self.state.yield_from = Some((gen.to_owned(), YieldFromPath::Send(meth.clone())));
                 PyIterReturn::from_pyresult(meth.call((val,), vm), vm)
             }
         }
@@ -1475,8 +2081,8 @@ impl ExecutingFrame<'_> {
     fn execute_yield_from(&mut self, vm: &VirtualMachine) -> FrameResult {
         // Value send into iterator:
         let val = self.pop_value();
-        let coro = self.last_value_ref();
-        let result = self._send(coro, val, vm)?;
+        let coro = self.last_value_ref().to_owned();
+        let result = self._send(&coro, val, vm)?;
 
         // PyIterReturn returned from e.g. gen.__next__() or gen.send()
         match result {
@@ -1489,6 +2095,7 @@ impl ExecutingFrame<'_> {
                 let value = vm.unwrap_or_none(value);
                 self.pop_value();
                 self.push_value(value);
+                self.state.yield_from = None;
                 Ok(None)
             }
         }
@@ -1656,10 +2263,14 @@ impl ExecutingFrame<'_> {
         Ok(None)
     }
 
-    #[cfg_attr(feature = "flame-it", flame("Frame"))]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     fn execute_binop(&mut self, vm: &VirtualMachine, op: bytecode::BinaryOperator) -> FrameResult {
         let b_ref = &self.pop_value();
         let a_ref = &self.pop_value();
+        if let Some(result) = self.binop_quickened(a_ref, b_ref, op, vm) {
+            self.push_value(result?);
+            return Ok(None);
+        }
         let value = match op {
             bytecode::BinaryOperator::Subtract => vm._sub(a_ref, b_ref),
             bytecode::BinaryOperator::Add => vm._add(a_ref, b_ref),
@@ -1679,6 +2290,53 @@ impl ExecutingFrame<'_> {
         self.push_value(value);
         Ok(None)
     }
+
+    /// specialized `int`/`float` fast paths for hot binary operations
+    /// (quickening); `None` falls through to the generic protocol dispatch
+    fn binop_quickened(
+        &self,
+        a: &PyObject,
+        b: &PyObject,
+        op: bytecode::BinaryOperator,
+        vm: &VirtualMachine,
+    ) -> Option<PyResult> {
+        use bytecode::BinaryOperator::*;
+        if !matches!(op, Add | Subtract | Multiply) {
+            return None;
+        }
+        let spec = OpSpec::of_binop(a, b, vm)?;
+        if !self.advance_quickening(spec) {
+            return None;
+        }
+        let value: PyObjectRef = match spec {
+            OpSpec::IntInt => {
+                let a = a.payload::<PyInt>()?.as_bigint();
+                let b = b.payload::<PyInt>()?.as_bigint();
+                let value = match op {
+                    Add => a + b,
+                    Subtract => a - b,
+                    Multiply => a * b,
+                    _ => unreachable!("op filtered above"),
+                };
+                vm.ctx.new_int(value).into()
+            }
+            OpSpec::FloatFloat => {
+                let a = a.payload::<PyFloat>()?.to_f64();
+                let b = b.payload::<PyFloat>()?.to_f64();
+                let value = match op {
+                    Add => a + b,
+                    Subtract => a - b,
+                    Multiply => a * b,
+                    _ => unreachable!("op filtered above"),
+                };
+                vm.ctx.new_float(value).into()
+            }
+            // subscript-only specs never classify for a binary operation
+            OpSpec::ListInt | OpSpec::DictStr => return None,
+        };
+        Some(Ok(value))
+    }
+
     fn execute_binop_inplace(
         &mut self,
         vm: &VirtualMachine,
@@ -1706,7 +2364,7 @@ impl ExecutingFrame<'_> {
         Ok(None)
     }
 
-    #[cfg_attr(feature = "flame-it", flame("Frame"))]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     fn execute_unop(&mut self, vm: &VirtualMachine, op: bytecode::UnaryOperator) -> FrameResult {
         let a = self.pop_value();
         let value = match op {
@@ -1725,13 +2383,9 @@ impl ExecutingFrame<'_> {
     #[cold]
     fn setup_annotations(&mut self, vm: &VirtualMachine) -> FrameResult {
         let __annotations__ = identifier!(vm, __annotations__);
+        let locals = self.locals(vm);
         // Try using locals as dict first, if not, fallback to generic method.
-        let has_annotations = match self
-            .locals
-            .clone()
-            .into_object()
-            .downcast_exact::<PyDict>(vm)
-        {
+        let has_annotations = match locals.clone().into_object().downcast_exact::<PyDict>(vm) {
             Ok(d) => d.contains_key(__annotations__, vm),
             Err(o) => {
                 let needle = __annotations__.to_object();
@@ -1739,7 +2393,7 @@ impl ExecutingFrame<'_> {
             }
         };
         if !has_annotations {
-            self.locals
+            locals
                 .as_object()
                 .set_item(__annotations__, vm.ctx.new_dict().into(), vm)?;
         }
@@ -1787,6 +2441,189 @@ impl ExecutingFrame<'_> {
         Err(vm.new_value_error(msg))
     }
 
+    /// Whether TOS would match a mapping pattern. CPython tests the
+    /// `Py_TPFLAGS_MAPPING` flag here; we approximate it by accepting dicts
+    /// and anything else exposing both the mapping protocol and a `keys`
+    /// method, which covers `collections.abc.Mapping` implementors.
+    fn match_mapping_check(&self, vm: &VirtualMachine) -> bool {
+        let subject = self.last_value_ref();
+        if subject.fast_isinstance(vm.ctx.types.dict_type) {
+            return true;
+        }
+        PyMapping::check(subject)
+            && vm
+                .get_method(subject.to_owned(), identifier!(vm, keys))
+                .is_some()
+    }
+
+    /// Whether TOS would match a sequence pattern: roughly CPython's
+    /// `Py_TPFLAGS_SEQUENCE`, i.e. the sequence protocol minus str, bytes
+    /// and bytearray, which match as atoms instead.
+    fn match_sequence_check(&self, vm: &VirtualMachine) -> bool {
+        let subject = self.last_value_ref();
+        if subject.fast_isinstance(vm.ctx.types.list_type)
+            || subject.fast_isinstance(vm.ctx.types.tuple_type)
+        {
+            return true;
+        }
+        if subject.fast_isinstance(vm.ctx.types.str_type)
+            || subject.fast_isinstance(vm.ctx.types.bytes_type)
+            || subject.fast_isinstance(vm.ctx.types.bytearray_type)
+        {
+            return false;
+        }
+        PySequence::find_methods(subject, vm).map_or(false, |f| f.as_ref().item.load().is_some())
+    }
+
+    fn execute_match_keys(&mut self, vm: &VirtualMachine) -> FrameResult {
+        let keys = self
+            .last_value()
+            .downcast::<PyTuple>()
+            .expect("match keys must be a tuple");
+        let subject = self.nth_value(1).to_owned();
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys.as_slice() {
+            match subject.get_item(&**key, vm) {
+                Ok(value) => values.push(value),
+                Err(exc) if exc.fast_isinstance(vm.ctx.exceptions.key_error) => {
+                    self.push_value(vm.ctx.none());
+                    return Ok(None);
+                }
+                Err(exc) => return Err(exc),
+            }
+        }
+        self.push_value(vm.ctx.new_tuple(values).into());
+        Ok(None)
+    }
+
+    fn execute_copy_dict_without_keys(&mut self, vm: &VirtualMachine) -> FrameResult {
+        let keys = self
+            .pop_value()
+            .downcast::<PyTuple>()
+            .expect("match keys must be a tuple");
+        let subject = self.last_value();
+        let rest = vm.ctx.new_dict();
+        rest.merge_object(subject, vm)?;
+        for key in keys.as_slice() {
+            rest.del_item(&**key, vm)?;
+        }
+        self.push_value(rest.into());
+        Ok(None)
+    }
+
+    fn execute_match_class(&mut self, nargs: usize, vm: &VirtualMachine) -> FrameResult {
+        let kw_names = self
+            .pop_value()
+            .downcast::<PyTuple>()
+            .expect("match attribute names must be a tuple");
+        let cls = self.pop_value();
+        let subject = self.pop_value();
+        let attrs = self.match_class(subject, cls, nargs, &kw_names, vm)?;
+        self.push_value(match attrs {
+            Some(attrs) => vm.ctx.new_tuple(attrs).into(),
+            None => vm.ctx.none(),
+        });
+        Ok(None)
+    }
+
+    /// Extract the attributes a class pattern matches against, or None when
+    /// `subject` is not an instance of `cls` or lacks one of the attributes.
+    fn match_class(
+        &self,
+        subject: PyObjectRef,
+        cls: PyObjectRef,
+        nargs: usize,
+        kw_names: &Py<PyTuple>,
+        vm: &VirtualMachine,
+    ) -> PyResult<Option<Vec<PyObjectRef>>> {
+        let cls = cls
+            .downcast::<PyType>()
+            .map_err(|_| vm.new_type_error("called match pattern must be a type".to_owned()))?;
+        if !subject.fast_isinstance(&cls) {
+            return Ok(None);
+        }
+        let mut attrs = Vec::with_capacity(nargs + kw_names.len());
+        if nargs > 0 {
+            let match_args =
+                vm.get_attribute_opt(cls.clone().into(), identifier!(vm, __match_args__))?;
+            match match_args {
+                Some(match_args) => {
+                    let match_args = match_args.downcast::<PyTuple>().map_err(|obj| {
+                        vm.new_type_error(format!(
+                            "{}.__match_args__ must be a tuple (got {})",
+                            cls.name(),
+                            obj.class().name()
+                        ))
+                    })?;
+                    if nargs > match_args.len() {
+                        return Err(vm.new_type_error(format!(
+                            "{}() accepts {} positional sub-patterns ({} given)",
+                            cls.name(),
+                            match_args.len(),
+                            nargs
+                        )));
+                    }
+                    for name in &match_args.as_slice()[..nargs] {
+                        let name = name.downcast_ref::<PyStr>().ok_or_else(|| {
+                            vm.new_type_error(format!(
+                                "__match_args__ elements must be strings (got {})",
+                                name.class().name()
+                            ))
+                        })?;
+                        match subject.get_attr(name, vm) {
+                            Ok(value) => attrs.push(value),
+                            Err(exc) if exc.fast_isinstance(vm.ctx.exceptions.attribute_error) => {
+                                return Ok(None)
+                            }
+                            Err(exc) => return Err(exc),
+                        }
+                    }
+                }
+                None => {
+                    // builtin types like int or str match their sole
+                    // positional sub-pattern against the subject itself
+                    let matches_self = [
+                        vm.ctx.types.bool_type,
+                        vm.ctx.types.bytearray_type,
+                        vm.ctx.types.bytes_type,
+                        vm.ctx.types.dict_type,
+                        vm.ctx.types.float_type,
+                        vm.ctx.types.frozenset_type,
+                        vm.ctx.types.int_type,
+                        vm.ctx.types.list_type,
+                        vm.ctx.types.set_type,
+                        vm.ctx.types.str_type,
+                        vm.ctx.types.tuple_type,
+                    ]
+                    .into_iter()
+                    .any(|typ| cls.fast_issubclass(typ));
+                    if nargs == 1 && matches_self {
+                        attrs.push(subject.clone());
+                    } else {
+                        return Err(vm.new_type_error(format!(
+                            "{}() accepts 0 positional sub-patterns ({} given)",
+                            cls.name(),
+                            nargs
+                        )));
+                    }
+                }
+            }
+        }
+        for name in kw_names.as_slice() {
+            let name = name
+                .downcast_ref::<PyStr>()
+                .expect("match attribute names must be strings");
+            match subject.get_attr(name, vm) {
+                Ok(value) => attrs.push(value),
+                Err(exc) if exc.fast_isinstance(vm.ctx.exceptions.attribute_error) => {
+                    return Ok(None)
+                }
+                Err(exc) => return Err(exc),
+            }
+        }
+        Ok(Some(attrs))
+    }
+
     fn format_value(
         &mut self,
         conversion: bytecode::ConversionFlag,
@@ -1822,7 +2659,7 @@ impl ExecutingFrame<'_> {
         Ok(!self._in(vm, needle, haystack)?)
     }
 
-    #[cfg_attr(feature = "flame-it", flame("Frame"))]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     fn execute_test(&mut self, vm: &VirtualMachine, op: bytecode::TestOperator) -> FrameResult {
         let b = self.pop_value();
         let a = self.pop_value();
@@ -1838,7 +2675,7 @@ impl ExecutingFrame<'_> {
         Ok(None)
     }
 
-    #[cfg_attr(feature = "flame-it", flame("Frame"))]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     fn execute_compare(
         &mut self,
         vm: &VirtualMachine,
@@ -1854,19 +2691,227 @@ impl ExecutingFrame<'_> {
     fn load_attr(&mut self, vm: &VirtualMachine, attr: bytecode::NameIdx) -> FrameResult {
         let attr_name = self.code.names[attr as usize];
         let parent = self.pop_value();
-        let obj = parent.get_attr(attr_name, vm)?;
+        let obj = self.getattr_cached(parent, attr_name, vm)?;
         self.push_value(obj);
         Ok(None)
     }
 
+    /// `parent.get_attr(..)` routed through the inline cache slot of the
+    /// current instruction: when the receiver uses the generic
+    /// `__getattribute__` the memoized MRO lookup is reused and only the
+    /// descriptor/instance-dict half of the protocol runs
+    fn getattr_cached(
+        &self,
+        parent: PyObjectRef,
+        attr_name: &'static PyStrInterned,
+        vm: &VirtualMachine,
+    ) -> PyResult {
+        let cls = parent.class();
+        let getattro = cls.mro_find_map(|cls| cls.slots.getattro.load()).unwrap();
+        if getattro as usize != PyBaseObject::getattro as usize {
+            return parent.get_attr(attr_name, vm);
+        }
+        let cls_attr = match self.lookup_attr_cached(cls, attr_name) {
+            Some(cls_attr) => cls_attr,
+            None => return parent.get_attr(attr_name, vm),
+        };
+        parent
+            .generic_getattr_resolved(attr_name, cls_attr, None, vm)
+            .and_then(|attr| {
+                attr.ok_or_else(|| {
+                    vm.new_attribute_error(format!(
+                        "'{}' object has no attribute '{}'",
+                        parent.class().name(),
+                        attr_name.as_str()
+                    ))
+                })
+            })
+            .map_err(|exc| {
+                vm.set_attribute_error_context(&exc, parent.clone(), attr_name.to_owned());
+                exc
+            })
+    }
+
     fn store_attr(&mut self, vm: &VirtualMachine, attr: bytecode::NameIdx) -> FrameResult {
         let attr_name = self.code.names[attr as usize];
         let parent = self.pop_value();
         let value = self.pop_value();
-        parent.set_attr(attr_name, value, vm)?;
+        self.setattr_cached(parent, attr_name, value, vm)?;
         Ok(None)
     }
 
+    /// `parent.set_attr(..)` routed through the inline cache slot of the
+    /// current instruction, analogous to [`Self::getattr_cached`]
+    fn setattr_cached(
+        &self,
+        parent: PyObjectRef,
+        attr_name: &'static PyStrInterned,
+        value: PyObjectRef,
+        vm: &VirtualMachine,
+    ) -> PyResult<()> {
+        let cls = parent.class();
+        let setattro = cls.mro_find_map(|cls| cls.slots.setattro.load());
+        if setattro.map(|f| f as usize) != Some(PyBaseObject::slot_setattro as usize) {
+            return parent.set_attr(attr_name, value, vm);
+        }
+        match self.lookup_attr_cached(cls, attr_name) {
+            Some(cls_attr) => parent.generic_setattr_resolved(
+                attr_name,
+                cls_attr,
+                PySetterValue::Assign(value),
+                vm,
+            ),
+            None => parent.set_attr(attr_name, value, vm),
+        }
+    }
+
+    /// Resolve `attr_name` on `cls` through the inline cache slot of the
+    /// current instruction, falling back to (and memoizing) a full MRO walk
+    /// when the cached entry was made for another type or a retired version
+    /// tag. Returns `None` when `cls` can't get a version tag, in which case
+    /// the caller has to take the uncached path.
+    fn lookup_attr_cached(
+        &self,
+        cls: &Py<PyType>,
+        attr_name: &'static PyStrInterned,
+    ) -> Option<Option<PyObjectRef>> {
+        let version = cls.version_tag();
+        if version == 0 {
+            return None;
+        }
+        // the run loop has already advanced lasti past the current instruction
+        let mut cache = self.code.inline_caches[self.lasti() as usize - 1].lock();
+        if let InlineCache::Attr {
+            version: cached_version,
+            cls_attr,
+        } = &*cache
+        {
+            if *cached_version == version {
+                return Some(cls_attr.clone());
+            }
+        }
+        let cls_attr = cls.get_attr(attr_name);
+        *cache = InlineCache::Attr {
+            version,
+            cls_attr: cls_attr.clone(),
+        };
+        Some(cls_attr)
+    }
+
+    /// `PyMethod::get` routed through the inline cache slot of the current
+    /// instruction: tight loops calling the same method on receivers of the
+    /// same type skip the MRO walk and only run the descriptor/instance-dict
+    /// half of the lookup
+    fn load_method_cached(
+        &self,
+        obj: PyObjectRef,
+        method_name: &'static PyStrInterned,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyMethod> {
+        let cls = obj.class();
+        let getattro = cls.mro_find_map(|cls| cls.slots.getattro.load()).unwrap();
+        if getattro as usize != PyBaseObject::getattro as usize {
+            return PyMethod::get(obj, method_name, vm);
+        }
+        match self.lookup_attr_cached(cls, method_name) {
+            Some(cls_attr) => PyMethod::get_resolved(obj, method_name, cls_attr, vm),
+            None => PyMethod::get(obj, method_name, vm),
+        }
+    }
+
+    /// Resolve the enter/exit descriptors on `cls` through the inline cache
+    /// slot of the current instruction, analogous to
+    /// [`Self::lookup_attr_cached`] but memoizing both MRO walks at once.
+    fn with_descrs_cached(
+        &self,
+        cls: &Py<PyType>,
+        enter_name: &'static PyStrInterned,
+        exit_name: &'static PyStrInterned,
+    ) -> (Option<PyObjectRef>, Option<PyObjectRef>) {
+        let version = cls.version_tag();
+        if version == 0 {
+            return (cls.get_attr(enter_name), cls.get_attr(exit_name));
+        }
+        let mut cache = self.code.inline_caches[self.lasti() as usize - 1].lock();
+        if let InlineCache::With {
+            version: cached_version,
+            enter,
+            exit,
+        } = &*cache
+        {
+            if *cached_version == version {
+                return (enter.clone(), exit.clone());
+            }
+        }
+        let enter = cls.get_attr(enter_name);
+        let exit = cls.get_attr(exit_name);
+        *cache = InlineCache::With {
+            version,
+            enter: enter.clone(),
+            exit: exit.clone(),
+        };
+        (enter, exit)
+    }
+
+    /// The enter/exit pair of the context manager `mgr`, looked up on its
+    /// type only (like CPython's special method lookup) with the MRO walks
+    /// served from the inline cache, so tight loops entering the same kind
+    /// of manager only run the descriptor binding. The enter method is about
+    /// to be invoked and comes back as a `PyMethod`; the exit method outlives
+    /// this instruction on the value stack and is bound eagerly.
+    fn with_methods_cached(
+        &self,
+        mgr: &PyObjectRef,
+        enter_name: &'static PyStrInterned,
+        exit_name: &'static PyStrInterned,
+        vm: &VirtualMachine,
+    ) -> PyResult<(Option<PyMethod>, Option<PyObjectRef>)> {
+        let (enter, exit) = self.with_descrs_cached(mgr.class(), enter_name, exit_name);
+        let enter = enter
+            .map(|descr| PyMethod::get_special_resolved(mgr, descr, vm))
+            .transpose()?;
+        let exit = exit
+            .map(|descr| {
+                vm.call_get_descriptor_specific(
+                    &descr,
+                    Some(mgr.clone()),
+                    Some(mgr.class().to_owned().into()),
+                )
+                .unwrap_or(Ok(descr))
+            })
+            .transpose()?;
+        Ok((enter, exit))
+    }
+
+    /// `load_global_or_builtin` routed through the inline cache slot of the
+    /// current instruction: as long as neither the globals nor the builtins
+    /// dict has been written to since the memoized lookup, its result is
+    /// reused without touching either dict
+    fn load_global_cached(&self, name: &'static PyStrInterned, vm: &VirtualMachine) -> PyResult {
+        // read the versions before the lookup: a write in between leaves a
+        // retired version in the cache, which can only cause a miss
+        let globals_version = self.globals.version();
+        let builtins_version = self.builtins.version();
+        let cache_slot = &self.code.inline_caches[self.lasti() as usize - 1];
+        if let InlineCache::Global {
+            globals_version: cached_globals,
+            builtins_version: cached_builtins,
+            value,
+        } = &*cache_slot.lock()
+        {
+            if *cached_globals == globals_version && *cached_builtins == builtins_version {
+                return Ok(value.clone());
+            }
+        }
+        let value = self.load_global_or_builtin(name, vm)?;
+        *cache_slot.lock() = InlineCache::Global {
+            globals_version,
+            builtins_version,
+            value: value.clone(),
+        };
+        Ok(value)
+    }
+
     fn delete_attr(&mut self, vm: &VirtualMachine, attr: bytecode::NameIdx) -> FrameResult {
         let attr_name = self.code.names[attr as usize];
         let parent = self.pop_value();
@@ -1935,6 +2980,9 @@ impl ExecutingFrame<'_> {
     #[cold]
     #[inline(never)]
     fn fatal(&self, msg: &'static str) -> ! {
+        #[cfg(feature = "execution-trace")]
+        crate::exec_trace::dump();
+        #[cfg(not(feature = "execution-trace"))]
         dbg!(self);
         panic!("{}", msg)
     }
@@ -1960,13 +3008,13 @@ impl fmt::Debug for Frame {
             .map(|elem| format!("\n  > {elem:?}"))
             .collect::<String>();
         // TODO: fix this up
-        let locals = self.locals.clone();
+        let locals = self.locals.lock().clone();
         write!(
             f,
             "Frame Object {{ \n Stack:{}\n Blocks:{}\n Locals:{:?}\n}}",
             stack_str,
             block_str,
-            locals.into_object()
+            locals.map(|l| l.into_object())
         )
     }
 }
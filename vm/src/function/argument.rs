@@ -1,11 +1,16 @@
 use crate::{
-    builtins::{PyBaseExceptionRef, PyTupleRef, PyTypeRef},
+    builtins::{PyBaseExceptionRef, PyStr, PyTuple, PyTupleRef, PyTypeRef},
     convert::ToPyObject,
-    AsObject, PyObjectRef, PyPayload, PyRef, PyResult, TryFromObject, VirtualMachine,
+    AsObject, Py, PyObject, PyObjectRef, PyPayload, PyRef, PyResult, TryFromObject, VirtualMachine,
 };
 use indexmap::IndexMap;
 use itertools::Itertools;
-use std::ops::RangeInclusive;
+use smallvec::SmallVec;
+use std::{borrow::Cow, ops::RangeInclusive};
+
+/// Storage for positional arguments. Calls passing up to 4 positional
+/// arguments — the overwhelming majority — keep them inline on the stack.
+pub type ArgsVec = SmallVec<[PyObjectRef; 4]>;
 
 pub trait IntoFuncArgs: Sized {
     fn into_args(self, vm: &VirtualMachine) -> FuncArgs;
@@ -59,9 +64,12 @@ into_func_args_from_tuple!((v1, T1), (v2, T2), (v3, T3), (v4, T4), (v5, T5));
 /// arguments, as well as keyword arguments passed to the function.
 #[derive(Debug, Default, Clone)]
 pub struct FuncArgs {
-    pub args: Vec<PyObjectRef>,
+    pub args: ArgsVec,
     // sorted map, according to https://www.python.org/dev/peps/pep-0468/
-    pub kwargs: IndexMap<String, PyObjectRef>,
+    // names are borrowed from the code object's interned kwnames tuple when
+    // the call comes from bytecode, and owned only for dynamically built calls.
+    // `IndexMap::new` does not allocate, so kwarg-less calls stay allocation-free
+    pub kwargs: IndexMap<Cow<'static, str>, PyObjectRef>,
 }
 
 #[cfg(feature = "gc_bacon")]
@@ -79,7 +87,7 @@ where
 {
     fn from(args: A) -> Self {
         FuncArgs {
-            args: args.into().into_vec(),
+            args: args.into().into_vec().into(),
             kwargs: IndexMap::new(),
         }
     }
@@ -88,8 +96,12 @@ where
 impl From<KwArgs> for FuncArgs {
     fn from(kwargs: KwArgs) -> Self {
         FuncArgs {
-            args: Vec::new(),
-            kwargs: kwargs.0,
+            args: ArgsVec::new(),
+            kwargs: kwargs
+                .0
+                .into_iter()
+                .map(|(name, value)| (name.into(), value))
+                .collect(),
         }
     }
 }
@@ -108,13 +120,19 @@ impl FuncArgs {
     {
         let PosArgs(args) = args.into();
         let KwArgs(kwargs) = kwargs.into();
-        Self { args, kwargs }
+        Self {
+            args: args.into(),
+            kwargs: kwargs
+                .into_iter()
+                .map(|(name, value)| (name.into(), value))
+                .collect(),
+        }
     }
 
     pub fn with_kwargs_names<A, KW>(mut args: A, kwarg_names: KW) -> Self
     where
         A: ExactSizeIterator<Item = PyObjectRef>,
-        KW: ExactSizeIterator<Item = String>,
+        KW: ExactSizeIterator<Item = Cow<'static, str>>,
     {
         // last `kwarg_names.len()` elements of args in order of appearance in the call signature
         let total_argc = args.len();
@@ -131,6 +149,36 @@ impl FuncArgs {
         }
     }
 
+    /// The name of one element of a kwnames tuple, borrowed for 'static when
+    /// the string is interned — which names coming from a code object always
+    /// are — and copied otherwise.
+    pub(crate) fn kwarg_name(name: &PyObject, vm: &VirtualMachine) -> Cow<'static, str> {
+        match name.as_interned_str(vm) {
+            Some(interned) => Cow::Borrowed(interned.as_str()),
+            None => Cow::Owned(
+                name.downcast_ref::<PyStr>()
+                    .expect("kwnames must be a tuple of strings")
+                    .as_str()
+                    .to_owned(),
+            ),
+        }
+    }
+
+    /// Box vectorcall-style arguments — positional arguments followed by the
+    /// values of `kwnames` — for a callee without a vectorcall slot; see
+    /// `PyObject::vectorcall`.
+    pub fn from_vectorcall(
+        args: &[PyObjectRef],
+        kwnames: Option<&Py<PyTuple>>,
+        vm: &VirtualMachine,
+    ) -> Self {
+        let kwnames = kwnames.map_or(&[][..], |names| names.as_slice());
+        Self::with_kwargs_names(
+            args.iter().cloned(),
+            kwnames.iter().map(|name| Self::kwarg_name(name, vm)),
+        )
+    }
+
     pub fn prepend_arg(&mut self, item: PyObjectRef) {
         self.args.reserve_exact(1);
         self.args.insert(0, item)
@@ -182,6 +230,21 @@ impl FuncArgs {
         }
     }
 
+    /// Like [`FuncArgs::take_positional`], but reports a caller trying to
+    /// pass the parameter by its (positional-only) name as an error.
+    pub fn take_positional_only(
+        &mut self,
+        name: &str,
+    ) -> Result<Option<PyObjectRef>, ArgumentError> {
+        match self.take_positional() {
+            Some(value) => Ok(Some(value)),
+            None if self.kwargs.contains_key(name) => Err(
+                ArgumentError::PositionalOnlyPassedAsKeyword(name.to_owned()),
+            ),
+            None => Ok(None),
+        }
+    }
+
     pub fn take_positional_keyword(&mut self, name: &str) -> Option<PyObjectRef> {
         self.take_positional().or_else(|| self.take_keyword(name))
     }
@@ -191,7 +254,9 @@ impl FuncArgs {
     }
 
     pub fn remaining_keywords(&mut self) -> impl Iterator<Item = (String, PyObjectRef)> + '_ {
-        self.kwargs.drain(..)
+        self.kwargs
+            .drain(..)
+            .map(|(name, value)| (name.into_owned(), value))
     }
 
     /// Binds these arguments to their respective values.
@@ -239,6 +304,10 @@ pub enum ArgumentError {
     InvalidKeywordArgument(String),
     /// The function require a keyword argument with the given name, but one wasn't provided
     RequiredKeywordArgument(String),
+    /// The function accepts no keyword arguments at all, but some were provided.
+    NoKeywordArguments,
+    /// A positional-only parameter was passed by its name.
+    PositionalOnlyPassedAsKeyword(String),
     /// An exception was raised while binding arguments to the function
     /// parameters.
     Exception(PyBaseExceptionRef),
@@ -274,6 +343,12 @@ impl ArgumentError {
             ArgumentError::RequiredKeywordArgument(name) => {
                 vm.new_type_error(format!("Required keyqord only argument {name}"))
             }
+            ArgumentError::NoKeywordArguments => {
+                vm.new_type_error("takes no keyword arguments".to_owned())
+            }
+            ArgumentError::PositionalOnlyPassedAsKeyword(name) => vm.new_type_error(format!(
+                "got some positional-only arguments passed as keyword arguments: '{name}'"
+            )),
             ArgumentError::Exception(ex) => ex,
         }
     }
@@ -1,7 +1,7 @@
 use crate::{
     builtins::{PyStr, PyStrRef},
     common::borrow::{BorrowedValue, BorrowedValueMut},
-    protocol::PyBuffer,
+    protocol::{BufferOrder, PyBuffer},
     PyObject, PyObjectRef, PyResult, TryFromBorrowedObject, TryFromObject, VirtualMachine,
 };
 
@@ -68,7 +68,7 @@ impl From<ArgBytesLike> for PyBuffer {
 impl<'a> TryFromBorrowedObject<'a> for ArgBytesLike {
     fn try_from_borrowed_object(vm: &VirtualMachine, obj: &'a PyObject) -> PyResult<Self> {
         let buffer = PyBuffer::try_from_borrowed_object(vm, obj)?;
-        if buffer.desc.is_contiguous() {
+        if buffer.desc.is_contiguous(BufferOrder::C) {
             Ok(Self(buffer))
         } else {
             Err(vm.new_type_error("non-contiguous buffer is not a bytes-like object".to_owned()))
@@ -110,7 +110,7 @@ impl From<ArgMemoryBuffer> for PyBuffer {
 impl<'a> TryFromBorrowedObject<'a> for ArgMemoryBuffer {
     fn try_from_borrowed_object(vm: &VirtualMachine, obj: &'a PyObject) -> PyResult<Self> {
         let buffer = PyBuffer::try_from_borrowed_object(vm, obj)?;
-        if !buffer.desc.is_contiguous() {
+        if !buffer.desc.is_contiguous(BufferOrder::C) {
             Err(vm.new_type_error("non-contiguous buffer is not a bytes-like object".to_owned()))
         } else if buffer.desc.readonly {
             Err(vm.new_type_error("buffer is not a read-write bytes-like object".to_owned()))
@@ -1,13 +1,56 @@
 use super::{FromArgs, FuncArgs};
 use crate::{
-    convert::ToPyResult, object::PyThreadingConstraint, Py, PyPayload, PyRef, PyResult,
-    VirtualMachine,
+    builtins::PyStrInterned, convert::ToPyResult, object::PyThreadingConstraint, Py, PyObjectRef,
+    PyPayload, PyRef, PyResult, VirtualMachine,
 };
 use std::marker::PhantomData;
 
 /// A built-in Python function.
 pub type PyNativeFunc = Box<py_dyn_fn!(dyn Fn(&VirtualMachine, FuncArgs) -> PyResult)>;
 
+/// The keyword names of a fastcall, matching the tail of the argument slice
+/// like CPython's `kwnames` tuple. The names are interned, so spreading them
+/// into `FuncArgs` does not allocate per name.
+#[derive(Debug, Clone, Default)]
+pub struct KwNames(Vec<&'static PyStrInterned>);
+
+impl KwNames {
+    pub fn new(names: Vec<&'static PyStrInterned>) -> Self {
+        Self(names)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[&'static PyStrInterned] {
+        &self.0
+    }
+}
+
+/// A built-in Python function following the METH_FASTCALL convention: the
+/// values for `kwnames` sit at the tail of the argument slice.
+pub type PyNativeFastFunc =
+    Box<py_dyn_fn!(dyn Fn(&VirtualMachine, &[PyObjectRef], Option<&KwNames>) -> PyResult)>;
+
+fn fastcall_args(args: &[PyObjectRef], kwnames: Option<&KwNames>) -> FuncArgs {
+    let split = args.len() - kwnames.map_or(0, |names| names.len());
+    let mut func_args = FuncArgs::from(args[..split].to_vec());
+    if let Some(kwnames) = kwnames {
+        func_args.kwargs = kwnames
+            .as_slice()
+            .iter()
+            .zip(&args[split..])
+            .map(|(name, value)| (name.as_str().into(), value.clone()))
+            .collect();
+    }
+    func_args
+}
+
 /// Implemented by types that are or can generate built-in functions.
 ///
 /// This trait is implemented by any function that matches the pattern:
@@ -35,6 +78,14 @@ pub trait IntoPyNativeFunc<Kind>: Sized + PyThreadingConstraint + 'static {
     fn into_func(self) -> PyNativeFunc {
         Box::new(move |vm: &VirtualMachine, args| self.call(vm, args))
     }
+    /// The METH_FASTCALL-style counterpart of [`into_func`](Self::into_func),
+    /// for callers that already hold the arguments as a slice with the
+    /// keyword names split off.
+    fn into_fast_func(self) -> PyNativeFastFunc {
+        Box::new(move |vm: &VirtualMachine, args, kwnames| {
+            self.call(vm, fastcall_args(args, kwnames))
+        })
+    }
 }
 
 // TODO: once higher-rank trait bounds are stabilized, remove the `Kind` type
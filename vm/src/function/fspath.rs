@@ -5,7 +5,10 @@ use crate::{
     protocol::PyBuffer,
     PyObjectRef, PyResult, TryFromObject, VirtualMachine,
 };
-use std::{ffi::OsStr, path::PathBuf};
+use std::{
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
 
 #[derive(Clone)]
 pub enum FsPath {
@@ -128,3 +131,43 @@ impl TryFromObject for FsPath {
         Self::try_from(obj, true, vm)
     }
 }
+
+/// A filesystem path argument: str, bytes or any os.PathLike, decoded to the
+/// platform's native form up front. For builtins that only need the `OsStr`
+/// and not the original object (which [`FsPath`] keeps around to e.g. mirror
+/// str-in/str-out, bytes-in/bytes-out results).
+#[derive(Debug, Clone)]
+pub struct ArgPath(OsString);
+
+impl ArgPath {
+    pub fn as_os_str(&self) -> &OsStr {
+        &self.0
+    }
+
+    pub fn as_path(&self) -> &Path {
+        Path::new(&self.0)
+    }
+
+    pub fn into_os_string(self) -> OsString {
+        self.0
+    }
+}
+
+impl AsRef<OsStr> for ArgPath {
+    fn as_ref(&self) -> &OsStr {
+        self.as_os_str()
+    }
+}
+
+impl AsRef<Path> for ArgPath {
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl TryFromObject for ArgPath {
+    fn try_from_object(vm: &VirtualMachine, obj: PyObjectRef) -> PyResult<Self> {
+        let fspath = FsPath::try_from(obj, true, vm)?;
+        Ok(Self(fspath.as_os_str(vm)?.to_owned()))
+    }
+}
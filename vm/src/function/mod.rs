@@ -7,21 +7,23 @@ mod fspath;
 mod getset;
 mod number;
 mod protocol;
+mod signature;
 
 pub use argument::{
-    ArgumentError, FromArgOptional, FromArgs, FuncArgs, IntoFuncArgs, KwArgs, OptionalArg,
+    ArgsVec, ArgumentError, FromArgOptional, FromArgs, FuncArgs, IntoFuncArgs, KwArgs, OptionalArg,
     OptionalOption, PosArgs,
 };
 pub use arithmetic::{PyArithmeticValue, PyComparisonValue};
 pub use buffer::{ArgAsciiBuffer, ArgBytesLike, ArgMemoryBuffer, ArgStrOrBytesLike};
 pub(self) use builtin::{BorrowedParam, OwnedParam, RefParam};
-pub use builtin::{IntoPyNativeFunc, PyNativeFunc};
+pub use builtin::{IntoPyNativeFunc, KwNames, PyNativeFastFunc, PyNativeFunc};
 pub use either::Either;
-pub use fspath::FsPath;
+pub use fspath::{ArgPath, FsPath};
 pub use getset::PySetterValue;
 pub(super) use getset::{IntoPyGetterFunc, IntoPySetterFunc, PyGetterFunc, PySetterFunc};
 pub use number::{ArgIndex, ArgIntoBool, ArgIntoComplex, ArgIntoFloat, ArgPrimitiveIndex, ArgSize};
 pub use protocol::{ArgCallable, ArgIterable, ArgMapping, ArgSequence};
+pub use signature::{ParameterDef, ParameterKind};
 
 use crate::{builtins::PyStr, convert::TryFromBorrowedObject, PyObject, PyResult, VirtualMachine};
 
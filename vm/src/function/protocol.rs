@@ -4,7 +4,7 @@ use crate::{
     convert::ToPyObject,
     identifier,
     protocol::{PyIter, PyIterIter, PyMapping, PyMappingMethods},
-    types::{AsMapping, GenericMethod},
+    types::{AsMapping, GenericMethod, VectorcallFunc},
     AsObject, PyObject, PyObjectRef, PyPayload, PyResult, TryFromObject, VirtualMachine,
 };
 use std::{borrow::Borrow, marker::PhantomData, ops::Deref};
@@ -15,6 +15,8 @@ pub struct ArgCallable {
     obj: PyObjectRef,
     #[notrace]
     call: GenericMethod,
+    #[notrace]
+    vectorcall: Option<VectorcallFunc>,
 }
 
 impl ArgCallable {
@@ -22,6 +24,18 @@ impl ArgCallable {
     pub fn invoke(&self, args: impl IntoFuncArgs, vm: &VirtualMachine) -> PyResult {
         (self.call)(&self.obj, args.into_args(vm), vm)
     }
+
+    /// Call with positional arguments still borrowed from the caller, going
+    /// through the vectorcall slot remembered at conversion time when the
+    /// callee has one; anything else boxes the arguments as `invoke` does.
+    /// Meant for inner loops that call the same object over and over.
+    #[inline]
+    pub fn invoke_fast(&self, args: &[PyObjectRef], vm: &VirtualMachine) -> PyResult {
+        match self.vectorcall {
+            Some(vectorcall) => vectorcall(&self.obj, args, None, vm),
+            None => (self.call)(&self.obj, args.to_vec().into(), vm),
+        }
+    }
 }
 
 impl std::fmt::Debug for ArgCallable {
@@ -57,10 +71,19 @@ impl From<ArgCallable> for PyObjectRef {
 impl TryFromObject for ArgCallable {
     fn try_from_object(vm: &VirtualMachine, obj: PyObjectRef) -> PyResult<Self> {
         let Some(callable) = obj.to_callable() else {
-            return Err(vm.new_type_error(format!("'{}' object is not callable", obj.class().name())));
+            return Err(
+                vm.new_type_error(format!("'{}' object is not callable", obj.class().name()))
+            );
         };
         let call = callable.call;
-        Ok(ArgCallable { obj, call })
+        // the vectorcall slot is looked up on the exact class on purpose, see
+        // the field's description in `PyTypeSlots`
+        let vectorcall = obj.class().slots.vectorcall.load();
+        Ok(ArgCallable {
+            obj,
+            call,
+            vectorcall,
+        })
     }
 }
 
@@ -96,6 +119,39 @@ impl<T> ArgIterable<T> {
         });
         iter.into_iter(vm)
     }
+
+    /// A best-effort estimate of the number of items, from `__length_hint__`
+    /// (falling back to `__len__`). `None` means the iterable does not say.
+    pub fn length_hint(&self, vm: &VirtualMachine) -> PyResult<Option<usize>> {
+        vm.length_hint_opt(self.iterable.clone())
+    }
+}
+
+impl<T> ArgIterable<T>
+where
+    T: TryFromObject,
+{
+    /// Collect exactly `n` items into a pre-sized vector, raising a
+    /// `ValueError` in the style of sequence unpacking when the iterable
+    /// yields fewer or more.
+    pub fn try_collect_exact(&self, n: usize, vm: &VirtualMachine) -> PyResult<Vec<T>> {
+        let mut iter = self.iter(vm)?;
+        let mut items = Vec::with_capacity(n);
+        for item in iter.by_ref().take(n) {
+            items.push(item?);
+        }
+        if items.len() < n {
+            return Err(vm.new_value_error(format!(
+                "not enough values to unpack (expected {}, got {})",
+                n,
+                items.len()
+            )));
+        }
+        if iter.next().transpose()?.is_some() {
+            return Err(vm.new_value_error(format!("too many values to unpack (expected {n})")));
+        }
+        Ok(items)
+    }
 }
 
 impl<T> TryFromObject for ArgIterable<T>
@@ -148,6 +204,45 @@ impl ArgMapping {
             methods: self.methods,
         }
     }
+
+    /// Store every `(key, value)` pair, resolving the `ass_subscript` slot
+    /// once up front instead of per key.
+    pub fn update_from_pairs<K, I>(&self, pairs: I, vm: &VirtualMachine) -> PyResult<()>
+    where
+        K: AsObject,
+        I: IntoIterator<Item = (K, PyObjectRef)>,
+    {
+        let mapping = self.mapping();
+        let f = self.methods.ass_subscript.load().ok_or_else(|| {
+            vm.new_type_error(format!(
+                "'{}' object does not support item assignment",
+                self.obj.class()
+            ))
+        })?;
+        for (key, value) in pairs {
+            f(mapping, key.as_object(), Some(value), vm)?;
+        }
+        Ok(())
+    }
+
+    /// The mapping equivalent of `dict.setdefault`: the stored value for
+    /// `key`, or `default` after inserting it when the key is missing.
+    pub fn setdefault(
+        &self,
+        key: impl AsObject,
+        default: PyObjectRef,
+        vm: &VirtualMachine,
+    ) -> PyResult {
+        let mapping = self.mapping();
+        match mapping.subscript(&key, vm) {
+            Ok(value) => Ok(value),
+            Err(exc) if exc.fast_isinstance(vm.ctx.exceptions.key_error) => {
+                mapping.ass_subscript(&key, Some(default.clone()), vm)?;
+                Ok(default)
+            }
+            Err(exc) => Err(exc),
+        }
+    }
 }
 
 impl Borrow<PyObject> for ArgMapping {
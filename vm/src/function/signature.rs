@@ -0,0 +1,39 @@
+//! Structured signature metadata for built-in callables.
+//!
+//! The #[pyfunction]/#[pymethod] macros record the parameter layout of the
+//! underlying Rust function here, so `inspect.signature` can be answered
+//! from data instead of parsing `__text_signature__` strings — the moral
+//! equivalent of CPython's Argument Clinic output.
+
+/// How a parameter binds its argument; mirrors `inspect.Parameter.kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterKind {
+    PositionalOnly,
+    PositionalOrKeyword,
+    VarPositional,
+    KeywordOnly,
+    VarKeyword,
+}
+
+impl ParameterKind {
+    /// the name of the matching `inspect.Parameter` kind constant
+    pub fn as_inspect_name(self) -> &'static str {
+        match self {
+            Self::PositionalOnly => "POSITIONAL_ONLY",
+            Self::PositionalOrKeyword => "POSITIONAL_OR_KEYWORD",
+            Self::VarPositional => "VAR_POSITIONAL",
+            Self::KeywordOnly => "KEYWORD_ONLY",
+            Self::VarKeyword => "VAR_KEYWORD",
+        }
+    }
+}
+
+/// One parameter of a built-in callable.
+#[derive(Debug, Clone, Copy)]
+pub struct ParameterDef {
+    pub name: &'static str,
+    pub kind: ParameterKind,
+    /// whether the parameter may be omitted (`OptionalArg` and friends);
+    /// the concrete default value is not recoverable from the Rust side
+    pub has_default: bool,
+}
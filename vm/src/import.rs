@@ -110,6 +110,13 @@ pub fn import_file(
     file_path: String,
     content: String,
 ) -> PyResult {
+    let cache_path = cached_code_path(&file_path);
+    if let Some(code) = cache_path
+        .as_deref()
+        .and_then(|path| load_cached_code(vm, path, &content))
+    {
+        return import_codeobj(vm, module_name, code, true);
+    }
     let code = vm
         .compile_with_opts(
             &content,
@@ -118,9 +125,65 @@ pub fn import_file(
             vm.compile_opts(),
         )
         .map_err(|err| vm.new_syntax_error(&err))?;
+    if let Some(path) = cache_path {
+        if !vm.state.settings.dont_write_bytecode {
+            // failing to write the cache is never an import failure
+            let _ = write_cached_code(&path, &content, &code);
+        }
+    }
     import_codeobj(vm, module_name, code, true)
 }
 
+/// Where the cached bytecode for the given source file goes: a `.pyc` next
+/// to it, or nowhere if the path doesn't look like a python source file.
+#[cfg(feature = "rustpython-compiler")]
+fn cached_code_path(file_path: &str) -> Option<String> {
+    file_path
+        .strip_suffix(".py")
+        .map(|stem| format!("{stem}.pyc"))
+}
+
+/// A hash of the module source, stored in the cache file so that editing the
+/// source invalidates the cached bytecode.
+#[cfg(feature = "rustpython-compiler")]
+fn source_hash(source: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    // deterministic across processes, unlike the vm's randomized hashing
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The code cached for `source` at `path`, if there is any and it was
+/// written by this version of the marshal format for this exact source.
+/// Anything unexpected in the file means recompiling, never an error.
+#[cfg(feature = "rustpython-compiler")]
+fn load_cached_code(vm: &VirtualMachine, path: &str, source: &str) -> Option<PyRef<PyCode>> {
+    use rustpython_compiler_core::marshal;
+    let data = std::fs::read(path).ok()?;
+    if data.len() < 12 {
+        return None;
+    }
+    let (header, code) = data.split_at(12);
+    if header[..4] != marshal::FORMAT_VERSION.to_le_bytes()
+        || header[4..] != source_hash(source).to_le_bytes()
+    {
+        return None;
+    }
+    let code = marshal::deserialize_code(&mut &*code, crate::builtins::code::PyObjBag(&vm.ctx));
+    Some(vm.ctx.new_code(code.ok()?))
+}
+
+#[cfg(feature = "rustpython-compiler")]
+fn write_cached_code(path: &str, source: &str, code: &PyCode) -> std::io::Result<()> {
+    use rustpython_compiler_core::marshal;
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&marshal::FORMAT_VERSION.to_le_bytes());
+    buf.extend_from_slice(&source_hash(source).to_le_bytes());
+    marshal::serialize_code(&mut buf, &code.code);
+    std::fs::write(path, buf)
+}
+
 pub fn import_codeobj(
     vm: &VirtualMachine,
     module_name: &str,
@@ -184,7 +247,7 @@ fn remove_importlib_frames_inner(
                 inner_tb,
                 traceback.frame.clone(),
                 traceback.lasti,
-                traceback.lineno,
+                traceback.location,
             )
             .into_ref(&vm.ctx),
         ),
@@ -14,10 +14,6 @@
 #![doc(html_logo_url = "https://raw.githubusercontent.com/RustPython/RustPython/main/logo.png")]
 #![doc(html_root_url = "https://docs.rs/rustpython-vm/")]
 
-#[cfg(feature = "flame-it")]
-#[macro_use]
-extern crate flamer;
-
 #[macro_use]
 extern crate bitflags;
 #[macro_use]
@@ -53,6 +49,8 @@ mod dictdatatype;
 #[cfg(feature = "rustpython-compiler")]
 pub mod eval;
 pub mod exceptions;
+#[cfg(feature = "execution-trace")]
+pub(crate) mod exec_trace;
 pub mod format;
 pub mod frame;
 mod frozen;
@@ -63,6 +61,7 @@ pub mod iter;
 pub mod object;
 pub mod prelude;
 pub mod protocol;
+pub mod py_future;
 pub mod py_io;
 #[cfg(feature = "serde")]
 pub mod py_serde;
@@ -72,8 +71,11 @@ pub mod scope;
 pub mod sequence;
 pub mod signal;
 pub mod sliceable;
+#[cfg(feature = "instruction-stats")]
+pub(crate) mod stats;
 pub mod stdlib;
 pub mod suggestion;
+pub(crate) mod tracemalloc;
 pub mod types;
 pub mod utils;
 pub mod version;
@@ -82,10 +84,12 @@ pub mod warn;
 
 pub use self::convert::{TryFromBorrowedObject, TryFromObject};
 pub use self::object::{
-    AsObject, Py, PyAtomicRef, PyExact, PyObject, PyObjectRef, PyPayload, PyRef, PyRefExact,
-    PyResult, PyWeakRef,
+    AsObject, Py, PyAtomicRef, PyExact, PyObject, PyObjectRef, PyOnceRef, PyPayload, PyRef,
+    PyRefExact, PyResult, PyWeakRef,
+};
+pub use self::vm::{
+    Context, FrameExecution, Interpreter, Settings, SuspendedFrame, VirtualMachine,
 };
-pub use self::vm::{Context, Interpreter, Settings, VirtualMachine};
 
 pub use rustpython_common as common;
 pub use rustpython_compiler_core as bytecode;
@@ -194,10 +194,16 @@ macro_rules! vm_trace {
     }
 }
 
+/// Start a profiling span covering the rest of the enclosing scope.
+///
+/// With the `flame-it` feature this enters a `tracing` span; the embedder
+/// decides what to do with it by installing a `tracing` subscriber (the
+/// rustpython CLI bridges the spans back into `flame` output), so the same
+/// tool can correlate GC pauses with Python call stacks.
 macro_rules! flame_guard {
     ($name:expr) => {
         #[cfg(feature = "flame-it")]
-        let _guard = ::flame::start_guard($name);
+        let _guard = ::tracing::trace_span!("flame", name = %$name).entered();
     };
 }
 
@@ -83,6 +83,23 @@ pub static ID2TYPE: once_cell::sync::Lazy<
 /// A type to just represent "we've erased the type of this object, cast it before you use it"
 #[derive(Debug)]
 pub(super) struct Erased;
+
+/// Count of live `PyInner` allocations, i.e. python objects created and not
+/// yet deallocated; backs `sys.getallocatedblocks`.
+static ALLOCATED_BLOCKS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// How many python objects are currently allocated.
+pub fn allocated_blocks() -> usize {
+    ALLOCATED_BLOCKS.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+pub(super) fn count_block_alloc() {
+    ALLOCATED_BLOCKS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub(super) fn count_block_dealloc() {
+    ALLOCATED_BLOCKS.fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+}
 #[cfg(not(feature = "gc_bacon"))]
 pub(super) struct PyObjVTable {
     pub(super) drop_dealloc: unsafe fn(*mut PyObject),
@@ -91,6 +108,8 @@ pub(super) struct PyObjVTable {
 
 #[cfg(not(feature = "gc_bacon"))]
 unsafe fn drop_dealloc_obj<T: PyObjectPayload>(x: *mut PyObject) {
+    count_block_dealloc();
+    crate::tracemalloc::trace_dealloc(x as *const ());
     drop(Box::from_raw(x as *mut PyInner<T>));
 }
 
@@ -532,7 +551,7 @@ impl InstanceDict {
 impl<T: PyObjectPayload> PyInner<T> {
     fn new(payload: T, typ: PyTypeRef, dict: Option<PyDictRef>) -> Box<Self> {
         let member_count = typ.slots.member_count;
-        Box::new(PyInner {
+        let inner = Box::new(PyInner {
             #[cfg(not(feature = "gc_bacon"))]
             ref_count: RefCount::new(),
             #[cfg(feature = "gc_bacon")]
@@ -549,7 +568,14 @@ impl<T: PyObjectPayload> PyInner<T> {
                 .take(member_count)
                 .collect_vec()
                 .into_boxed_slice(),
-        })
+        });
+        count_block_alloc();
+        // the box never moves its allocation, so this is the final address
+        crate::tracemalloc::trace_alloc(
+            &*inner as *const Self as *const (),
+            std::mem::size_of::<Self>(),
+        );
+        inner
     }
 }
 /// The `PyObjectRef` is one of the most used types. It is a reference to a
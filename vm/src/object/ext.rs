@@ -4,7 +4,7 @@ use super::{
 };
 use crate::common::{
     atomic::{Ordering, PyAtomic, Radium},
-    lock::PyRwLockReadGuard,
+    lock::{OnceCell, PyRwLockReadGuard},
 };
 use crate::{
     builtins::{PyBaseExceptionRef, PyStrInterned, PyType},
@@ -437,6 +437,67 @@ impl PyAtomicRef<Option<PyObject>> {
     }
 }
 
+/// A once-settable slot for lazily cached object references inside payloads.
+///
+/// This is the payload-field analogue of [`OnceCell`]: the first `set` (or
+/// `get_or_init`) wins and every later read sees the same value, so fields
+/// like a memoized `__doc__` or a cached stat result don't need a full
+/// `PyRwLock`. Unlike a bare `OnceCell`, the contained value participates in
+/// garbage-collector tracing.
+#[derive(Debug)]
+pub struct PyOnceRef<T = PyObjectRef>(OnceCell<T>);
+
+impl<T> Default for PyOnceRef<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PyOnceRef<T> {
+    pub fn new() -> Self {
+        Self(OnceCell::new())
+    }
+
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        self.0.get()
+    }
+
+    /// Set the value, failing (and handing the value back) if one is already
+    /// stored.
+    #[inline]
+    pub fn set(&self, value: T) -> Result<(), T> {
+        self.0.set(value)
+    }
+
+    #[inline]
+    pub fn get_or_init<F>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        self.0.get_or_init(f)
+    }
+
+    #[inline]
+    pub fn get_or_try_init<F, E>(&self, f: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        self.0.get_or_try_init(f)
+    }
+}
+
+#[cfg(feature = "gc_bacon")]
+unsafe impl<T: crate::object::gc::Trace> crate::object::gc::Trace for PyOnceRef<T> {
+    fn trace(&self, tracer_fn: &mut crate::object::gc::TracerFn) {
+        // a set value can never be replaced or dropped while the cell is
+        // alive, so tracing the borrow is safe
+        if let Some(value) = self.0.get() {
+            value.trace(tracer_fn);
+        }
+    }
+}
+
 pub trait AsObject
 where
     Self: Borrow<PyObject>,
@@ -66,6 +66,9 @@ pub struct Collector {
     is_enabled: PyMutex<bool>,
     /// acquire this to prevent a new gc to happen before this gc is completed
     cleanup_cycle: PyMutex<()>,
+    /// how many mutator threads are currently attached, see [`Collector::attach_thread`]
+    #[cfg(feature = "threading")]
+    attached_threads: PyMutex<usize>,
 }
 
 impl std::fmt::Debug for Collector {
@@ -89,6 +92,8 @@ impl Default for Collector {
             last_gc_time: PyMutex::new(Instant::now()),
             is_enabled: PyMutex::new(true),
             cleanup_cycle: PyMutex::new(()),
+            #[cfg(feature = "threading")]
+            attached_threads: PyMutex::new(0),
         }
     }
 }
@@ -120,7 +125,10 @@ impl Collector {
                     match self.pause.try_write_for(std::time::Duration::from_secs(1)) {
                         Some(v) => v,
                         None => {
-                            warn!("Can't acquire lock to stop the world.");
+                            warn!(
+                                "Can't acquire lock to stop the world ({} threads attached).",
+                                self.attached_threads()
+                            );
                             return (0, 0).into();
                         }
                     }
@@ -153,9 +161,21 @@ impl Collector {
         // 1. mark roots: which get trial DECREF object so cycles get zero rc
         // 2. scan roots: get non-cycle object back to normal values
         // 3. collect roots: collect cycles starting from roots
-        let freed = Self::mark_roots(&mut *self.roots.lock());
-        Self::scan_roots(&mut *self.roots.lock());
-        let ret_cycle = self.collect_roots(self.roots.lock(), lock);
+        // each phase gets its own profiling span so GC pauses show up next to
+        // the Python call stacks in the same trace
+        flame_guard!("GC::collect_cycles");
+        let freed = {
+            flame_guard!("GC::mark_roots");
+            Self::mark_roots(&mut *self.roots.lock())
+        };
+        {
+            flame_guard!("GC::scan_roots");
+            Self::scan_roots(&mut *self.roots.lock());
+        }
+        let ret_cycle = {
+            flame_guard!("GC::collect_roots");
+            self.collect_roots(self.roots.lock(), lock)
+        };
         (freed, ret_cycle).into()
     }
 
@@ -591,4 +611,56 @@ impl Collector {
         #[cfg(not(feature = "threading"))]
         return None;
     }
+
+    // methods about thread registration
+
+    /// Register the calling thread as a mutator of this collector's heap.
+    ///
+    /// Attaching first waits out any in-progress stop-the-world collection, so
+    /// a thread spawned while the world is stopped cannot start mutating the
+    /// object graph halfway through a collection. Threads spawned through
+    /// [`crate::VirtualMachine::start_thread`] (or anything built on
+    /// `new_thread()`) attach automatically; embedders running python objects
+    /// on a hand-rolled thread should call this themselves and keep the
+    /// returned guard alive for the thread's lifetime.
+    #[cfg(feature = "threading")]
+    pub fn attach_thread(self: &PyRc<Self>) -> ThreadAttachGuard {
+        // pause *before* announcing the thread, so the collector never counts
+        // a thread that isn't allowed to mutate yet
+        self.do_pausing();
+        *self.attached_threads.lock() += 1;
+        ThreadAttachGuard {
+            collector: self.clone(),
+        }
+    }
+
+    #[cfg(feature = "threading")]
+    fn detach_thread(&self) {
+        let mut attached = self.attached_threads.lock();
+        debug_assert!(*attached > 0);
+        *attached = attached.saturating_sub(1);
+    }
+
+    /// number of threads currently attached via [`Collector::attach_thread`]
+    #[cfg(feature = "threading")]
+    pub fn attached_threads(&self) -> usize {
+        *self.attached_threads.lock()
+    }
+}
+
+/// RAII registration of a mutator thread, returned by
+/// [`Collector::attach_thread`]. The thread is detached again when the guard
+/// is dropped, so drop it only after the last python object on the thread is
+/// gone.
+#[cfg(feature = "threading")]
+#[must_use = "the thread is detached again as soon as the guard is dropped"]
+pub struct ThreadAttachGuard {
+    collector: PyRc<Collector>,
+}
+
+#[cfg(feature = "threading")]
+impl Drop for ThreadAttachGuard {
+    fn drop(&mut self) {
+        self.collector.detach_thread();
+    }
 }
@@ -102,11 +102,19 @@ fn test_state() {
     assert_eq!(state.color(), Color::Purple);
 }
 
+/// integer type backing the reference count. On memory-constrained targets
+/// (wasm/embedded) the `gc_header_u32` feature shrinks the counter to a single
+/// u32 word, at the price of a much lower maximum refcount.
+#[cfg(feature = "gc_header_u32")]
+type HeaderRc = u32;
+#[cfg(not(feature = "gc_header_u32"))]
+type HeaderRc = usize;
+
 /// Garbage collect header, containing ref count and other info, using repr(C) to stay consistent with PyInner 's repr
 #[repr(C)]
 #[derive(Debug)]
 pub struct GcHeader {
-    ref_cnt: PyAtomic<usize>,
+    ref_cnt: PyAtomic<HeaderRc>,
     state: PyMutex<State>,
     exclusive: PyMutex<()>,
     gc: PyRc<Collector>,
@@ -129,13 +137,15 @@ impl Default for GcHeader {
 }
 
 // TODO: use macro for getter/setter
+// the casts are no-ops without `gc_header_u32`
+#[allow(clippy::unnecessary_cast)]
 impl GcHeader {
     pub fn new() -> Self {
         Default::default()
     }
 
     pub fn get(&self) -> usize {
-        self.ref_cnt.load(Ordering::Relaxed)
+        self.ref_cnt.load(Ordering::Relaxed) as usize
     }
 
     /// gain a exclusive lock to header
@@ -268,7 +278,14 @@ impl GcHeader {
     }
     /// simple RC += 1
     pub fn inc(&self) -> usize {
-        self.ref_cnt.fetch_add(1, Ordering::Relaxed) + 1
+        let prev = self.ref_cnt.fetch_add(1, Ordering::Relaxed);
+        // with the compact u32 header the refcount can realistically approach
+        // its maximum; treat such an object as leaked rather than wrapping
+        #[cfg(feature = "gc_header_u32")]
+        if prev >= HeaderRc::MAX / 2 {
+            self.leak();
+        }
+        prev as usize + 1
     }
     /// only inc if non-zero(and return true if success)
     #[inline]
@@ -286,10 +303,10 @@ impl GcHeader {
     }
     /// simple RC -= 1
     pub fn dec(&self) -> usize {
-        self.ref_cnt.fetch_sub(1, Ordering::Relaxed) - 1
+        (self.ref_cnt.fetch_sub(1, Ordering::Relaxed) - 1) as usize
     }
     pub fn rc(&self) -> usize {
-        self.ref_cnt.load(Ordering::Relaxed)
+        self.ref_cnt.load(Ordering::Relaxed) as usize
     }
 }
 
@@ -41,6 +41,8 @@ mod header;
 pub(crate) mod object;
 mod trace;
 
+#[cfg(feature = "threading")]
+pub use collector::ThreadAttachGuard;
 pub use collector::{Collector, GLOBAL_COLLECTOR};
 pub use header::{Color, GcHeader, GcResult};
 pub use trace::{MaybeTrace, Trace, TraceHelper, TracerFn};
@@ -47,6 +47,8 @@ unsafe fn drop_dealloc_obj<T: PyObjectPayload>(x: *mut PyObject) {
         error!("Try to drop&dealloc a buffered object! Drop only for now!");
         drop_only_obj::<T>(x);
     } else {
+        super::super::core::count_block_dealloc();
+        crate::tracemalloc::trace_dealloc(x as *const ());
         drop(Box::from_raw(x as *mut PyInner<T>));
     }
 }
@@ -115,6 +117,8 @@ unsafe fn dealloc_only_obj<T: PyObjectPayload>(x: *mut PyObject) {
         let obj = &*x.cast::<PyInner<T>>();
         partially_drop!(obj.header, vtable, weak_list);
     } // don't want keep a ref to a to be deallocated object
+    super::super::core::count_block_dealloc();
+    crate::tracemalloc::trace_dealloc(x as *const ());
     std::alloc::dealloc(
         x.cast(),
         std::alloc::Layout::for_value(&*x.cast::<PyInner<T>>()),
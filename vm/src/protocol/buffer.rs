@@ -5,38 +5,83 @@ use crate::{
     common::{
         borrow::{BorrowedValue, BorrowedValueMut},
         lock::{MapImmutable, PyMutex, PyMutexGuard},
+        rc::PyRc,
     },
-    object::PyObjectPayload,
+    object::{PyObjectPayload, PyThreadingConstraint},
     sliceable::SequenceIndexOp,
     types::{Constructor, Unconstructible},
-    Py, PyObject, PyObjectRef, PyPayload, PyRef, PyResult, TryFromBorrowedObject, VirtualMachine,
+    PyObject, PyObjectRef, PyPayload, PyRef, PyResult, TryFromBorrowedObject, VirtualMachine,
 };
+use crossbeam_utils::atomic::AtomicCell;
 use itertools::Itertools;
 use std::{borrow::Cow, fmt::Debug, ops::Range};
 
-pub struct BufferMethods {
-    pub obj_bytes: fn(&PyBuffer) -> BorrowedValue<[u8]>,
-    pub obj_bytes_mut: fn(&PyBuffer) -> BorrowedValueMut<[u8]>,
-    pub release: fn(&PyBuffer),
-    pub retain: fn(&PyBuffer),
+/// The exporter side of the buffer protocol: how the underlying bytes are
+/// borrowed and how export counts are maintained. The defaults suit read-only
+/// exporters that need no bookkeeping; writable exporters override
+/// [`obj_bytes_mut`](Self::obj_bytes_mut).
+///
+/// Payload types implement this directly; the blanket impl below forwards
+/// through `PyRef`, so exporters in other crates stay on the right side of
+/// the orphan rule.
+pub trait BufferInternal: Debug + PyThreadingConstraint {
+    /// borrow the underlying bytes
+    fn obj_bytes(&self) -> BorrowedValue<[u8]>;
+
+    /// borrow the underlying bytes mutably; only called on writable buffers
+    fn obj_bytes_mut(&self) -> BorrowedValueMut<[u8]> {
+        unreachable!("read-only buffer borrowed mutably")
+    }
+
+    /// a new export of the buffer was created
+    fn retain(&self) {}
+
+    /// an export of the buffer was dropped
+    fn release(&self) {}
 }
 
-impl Debug for BufferMethods {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("BufferMethods")
-            .field("obj_bytes", &(self.obj_bytes as usize))
-            .field("obj_bytes_mut", &(self.obj_bytes_mut as usize))
-            .field("release", &(self.release as usize))
-            .field("retain", &(self.retain as usize))
-            .finish()
+impl<T> BufferInternal for PyRef<T>
+where
+    T: PyObjectPayload + BufferInternal,
+{
+    fn obj_bytes(&self) -> BorrowedValue<[u8]> {
+        (**self).obj_bytes()
+    }
+
+    fn obj_bytes_mut(&self) -> BorrowedValueMut<[u8]> {
+        (**self).obj_bytes_mut()
+    }
+
+    fn retain(&self) {
+        (**self).retain()
+    }
+
+    fn release(&self) {
+        (**self).release()
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct PyBuffer {
     pub obj: PyObjectRef,
     pub desc: BufferDescriptor,
-    methods: &'static BufferMethods,
+    internal: PyRc<dyn BufferInternal>,
+    // whether this handle already gave up its export, either on drop or
+    // via an early `release()`
+    released: AtomicCell<bool>,
+}
+
+impl Clone for PyBuffer {
+    fn clone(&self) -> Self {
+        // a clone is a new export of the buffer and accounts for itself
+        self.retain();
+        Self {
+            obj: self.obj.clone(),
+            desc: self.desc.clone(),
+            internal: self.internal.clone(),
+            released: AtomicCell::new(false),
+        }
+    }
 }
 
 #[cfg(feature = "gc_bacon")]
@@ -47,11 +92,16 @@ unsafe impl crate::object::gc::Trace for PyBuffer {
 }
 
 impl PyBuffer {
-    pub fn new(obj: PyObjectRef, desc: BufferDescriptor, methods: &'static BufferMethods) -> Self {
+    pub fn new<T>(internal: PyRef<T>, desc: BufferDescriptor) -> Self
+    where
+        T: PyObjectPayload,
+        PyRef<T>: BufferInternal,
+    {
         let zelf = Self {
-            obj,
+            obj: internal.clone().into(),
             desc: desc.validate(),
-            methods,
+            internal: PyRc::new(internal),
+            released: AtomicCell::new(false),
         };
         zelf.retain();
         zelf
@@ -59,24 +109,132 @@ impl PyBuffer {
 
     pub fn as_contiguous(&self) -> Option<BorrowedValue<[u8]>> {
         self.desc
-            .is_contiguous()
+            .is_contiguous(BufferOrder::C)
             .then(|| unsafe { self.contiguous_unchecked() })
     }
 
     pub fn as_contiguous_mut(&self) -> Option<BorrowedValueMut<[u8]>> {
-        (!self.desc.readonly && self.desc.is_contiguous())
+        (!self.desc.readonly && self.desc.is_contiguous(BufferOrder::C))
             .then(|| unsafe { self.contiguous_mut_unchecked() })
     }
 
+    /// Borrow the buffer's bytes as one contiguous slice, erroring instead
+    /// of panicking when the buffer cannot be borrowed that way.
+    pub fn try_bytes(&self, vm: &VirtualMachine) -> PyResult<BorrowedValue<[u8]>> {
+        self.as_contiguous()
+            .ok_or_else(|| vm.new_buffer_error("underlying buffer is not C-contiguous".to_owned()))
+    }
+
+    /// The writable counterpart of [`try_bytes`](Self::try_bytes).
+    pub fn try_bytes_mut(&self, vm: &VirtualMachine) -> PyResult<BorrowedValueMut<[u8]>> {
+        if self.desc.readonly {
+            return Err(vm.new_buffer_error("Object is not writable".to_owned()));
+        }
+        self.as_contiguous_mut()
+            .ok_or_else(|| vm.new_buffer_error("underlying buffer is not C-contiguous".to_owned()))
+    }
+
+    /// View the buffer as a slice of `T` without copying. Errors if the
+    /// buffer is not contiguous or its length or alignment does not fit `T`.
+    pub fn as_slice_of<T: Pod>(&self, vm: &VirtualMachine) -> PyResult<BorrowedValue<[T]>> {
+        let bytes = self.try_bytes(vm)?;
+        let size = std::mem::size_of::<T>();
+        if bytes.len() % size != 0 {
+            return Err(vm.new_buffer_error(format!(
+                "buffer length is not a multiple of item size {size}"
+            )));
+        }
+        if bytes.as_ptr() as usize % std::mem::align_of::<T>() != 0 {
+            return Err(
+                vm.new_buffer_error(format!("buffer is not aligned for items of size {size}"))
+            );
+        }
+        fn cast_slice<T>(b: &[u8]) -> &[T] {
+            // SAFETY: the caller has checked length and alignment, and
+            // `T: Pod` rules out invalid bit patterns
+            unsafe {
+                std::slice::from_raw_parts(b.as_ptr().cast(), b.len() / std::mem::size_of::<T>())
+            }
+        }
+        Ok(BorrowedValue::map(bytes, cast_slice::<T>))
+    }
+
     pub fn from_byte_vector(bytes: Vec<u8>, vm: &VirtualMachine) -> Self {
         let bytes_len = bytes.len();
         PyBuffer::new(
-            PyPayload::into_pyobject(VecBuffer::from(bytes), vm),
+            VecBuffer::from(bytes).into_ref(&vm.ctx),
             BufferDescriptor::simple(bytes_len, true),
-            &VEC_BUFFER_METHODS,
         )
     }
 
+    /// Slice along the first dimension without copying: the result shares
+    /// this buffer's exporter and windows a sub-range of the same bytes.
+    /// Returns `None` for out-of-bounds ranges and for buffers that cannot
+    /// be expressed as a window (indirect buffers, negative strides); those
+    /// callers keep the copying path.
+    pub fn slice(&self, range: Range<usize>) -> Option<Self> {
+        let &(shape, stride, _) = self.desc.dim_desc.first()?;
+        if range.start > range.end || range.end > shape {
+            return None;
+        }
+        let mut dim_desc = self.desc.dim_desc.clone();
+        dim_desc[0].0 = range.len();
+        self.sub_buffer(range.start as isize * stride, dim_desc)
+    }
+
+    /// Fix dimension `dim` at `index`, dropping it from the shape without
+    /// copying. Returns `None` on out-of-bounds access and for buffers that
+    /// cannot be windowed (see [`slice`](Self::slice)).
+    pub fn index(&self, dim: usize, index: usize) -> Option<Self> {
+        let &(shape, stride, _) = self.desc.dim_desc.get(dim)?;
+        if index >= shape {
+            return None;
+        }
+        let mut dim_desc = self.desc.dim_desc.clone();
+        dim_desc.remove(dim);
+        self.sub_buffer(index as isize * stride, dim_desc)
+    }
+
+    fn sub_buffer(&self, offset: isize, dim_desc: Vec<(usize, isize, isize)>) -> Option<Self> {
+        if self.desc.has_suboffsets() {
+            return None;
+        }
+        let mut len = self.desc.itemsize;
+        // the smallest window covering every position the new descriptor can
+        // produce; only with non-negative strides is `offset` the window start
+        let mut window = self.desc.itemsize;
+        for &(shape, stride, _) in &dim_desc {
+            if stride < 0 {
+                return None;
+            }
+            len *= shape;
+            window += shape.saturating_sub(1) * stride as usize;
+        }
+        if len == 0 {
+            window = 0;
+        }
+        let offset = offset as usize;
+        let zelf = Self {
+            obj: self.obj.clone(),
+            // not `validate`d: `index` on a 1-D buffer legitimately produces
+            // a 0-dim descriptor
+            desc: BufferDescriptor {
+                len,
+                readonly: self.desc.readonly,
+                itemsize: self.desc.itemsize,
+                format: self.desc.format.clone(),
+                dim_desc,
+            },
+            internal: PyRc::new(SubBufferInternal {
+                inner: self.internal.clone(),
+                range: offset..offset + window,
+            }),
+            released: AtomicCell::new(false),
+        };
+        zelf.retain();
+        Some(zelf)
+    }
+
     /// # Safety
     /// assume the buffer is contiguous
     pub unsafe fn contiguous_unchecked(&self) -> BorrowedValue<[u8]> {
@@ -94,9 +252,10 @@ impl PyBuffer {
             buf.extend_from_slice(&bytes);
         } else {
             let bytes = &*self.obj_bytes();
-            self.desc.for_each_segment(true, |range| {
-                buf.extend_from_slice(&bytes[range.start as usize..range.end as usize])
-            });
+            self.desc
+                .for_each_segment(bytes, 0, BufferOrder::C, true, |range| {
+                    buf.extend_from_slice(&bytes[range.start as usize..range.end as usize])
+                });
         }
     }
 
@@ -114,41 +273,69 @@ impl PyBuffer {
         f(v)
     }
 
-    pub fn obj_as<T: PyObjectPayload>(&self) -> &Py<T> {
-        unsafe { self.obj.downcast_unchecked_ref() }
+    /// Copy `src`'s elements into this buffer, which must be writable.
+    /// Shapes must be compatible in the sense of
+    /// [`BufferDescriptor::broadcast`]; both buffers may be non-contiguous.
+    pub fn copy_from(&self, src: &Self, vm: &VirtualMachine) -> PyResult<()> {
+        if self.desc.readonly {
+            return Err(vm.new_type_error("cannot modify read-only memory".to_owned()));
+        }
+        let src_desc = self.desc.broadcast(&src.desc).ok_or_else(|| {
+            vm.new_value_error(
+                "buffer assignment: lvalue and rvalue have different structures".to_owned(),
+            )
+        })?;
+        let mut dest_bytes = self.obj_bytes_mut();
+        let src_bytes = src.obj_bytes();
+        self.desc
+            .zip_eq(&src_desc, BufferOrder::C, true, |a_range, b_range| {
+                let a_range = a_range.start as usize..a_range.end as usize;
+                let b_range = b_range.start as usize..b_range.end as usize;
+                dest_bytes[a_range].copy_from_slice(&src_bytes[b_range]);
+                false
+            });
+        Ok(())
     }
 
     pub fn obj_bytes(&self) -> BorrowedValue<[u8]> {
-        (self.methods.obj_bytes)(self)
+        self.internal.obj_bytes()
     }
 
     pub fn obj_bytes_mut(&self) -> BorrowedValueMut<[u8]> {
-        (self.methods.obj_bytes_mut)(self)
+        self.internal.obj_bytes_mut()
     }
 
+    /// Give up this handle's export of the buffer; the implicit release on
+    /// drop then becomes a no-op. Idempotent.
     pub fn release(&self) {
-        (self.methods.release)(self)
+        if self.released.compare_exchange(false, true).is_ok() {
+            self.internal.release();
+        }
     }
 
     pub fn retain(&self) {
-        (self.methods.retain)(self)
+        self.internal.retain()
     }
 
-    // drop PyBuffer without calling release
-    // after this function, the owner should use forget()
-    // or wrap PyBuffer in the ManaullyDrop to prevent drop()
-    pub(crate) unsafe fn drop_without_release(&mut self) {
-        std::ptr::drop_in_place(&mut self.obj);
-        std::ptr::drop_in_place(&mut self.desc);
+    /// the raw exporter, bypassing this handle's own accounting; for views
+    /// that re-export their source buffer
+    pub(crate) fn internal(&self) -> &dyn BufferInternal {
+        &*self.internal
     }
 }
 
-impl<'a> TryFromBorrowedObject<'a> for PyBuffer {
-    fn try_from_borrowed_object(vm: &VirtualMachine, obj: &'a PyObject) -> PyResult<Self> {
+impl PyBuffer {
+    /// Acquire a buffer from `obj`, rejecting exports that cannot satisfy the
+    /// requested `flags`.
+    pub fn try_from_borrowed_object_with_flags(
+        vm: &VirtualMachine,
+        obj: &PyObject,
+        flags: BufferFlags,
+    ) -> PyResult<Self> {
         let cls = obj.class();
         let as_buffer = cls.mro_find_map(|cls| cls.slots.as_buffer);
         if let Some(f) = as_buffer {
-            return f(obj, vm);
+            return f(obj, flags, vm);
         }
         Err(vm.new_type_error(format!(
             "a bytes-like object is required, not '{}'",
@@ -157,12 +344,85 @@ impl<'a> TryFromBorrowedObject<'a> for PyBuffer {
     }
 }
 
+impl<'a> TryFromBorrowedObject<'a> for PyBuffer {
+    fn try_from_borrowed_object(vm: &VirtualMachine, obj: &'a PyObject) -> PyResult<Self> {
+        // the most permissive read-only request; callers that need more use
+        // `try_from_borrowed_object_with_flags`
+        Self::try_from_borrowed_object_with_flags(vm, obj, BufferFlags::FULL_RO)
+    }
+}
+
 impl Drop for PyBuffer {
     fn drop(&mut self) {
         self.release();
     }
 }
 
+/// windows another exporter's bytes for the zero-copy sub-buffer views
+/// created by [`PyBuffer::slice`] and [`PyBuffer::index`]
+#[derive(Debug)]
+struct SubBufferInternal {
+    inner: PyRc<dyn BufferInternal>,
+    range: Range<usize>,
+}
+
+impl BufferInternal for SubBufferInternal {
+    fn obj_bytes(&self) -> BorrowedValue<[u8]> {
+        BorrowedValue::map(self.inner.obj_bytes(), |b| &b[self.range.clone()])
+    }
+
+    fn obj_bytes_mut(&self) -> BorrowedValueMut<[u8]> {
+        BorrowedValueMut::map(self.inner.obj_bytes_mut(), |b| &mut b[self.range.clone()])
+    }
+
+    fn retain(&self) {
+        self.inner.retain()
+    }
+
+    fn release(&self) {
+        self.inner.release()
+    }
+}
+
+bitflags::bitflags! {
+    /// Buffer request flags, matching the `PyBUF_*` constants of the C buffer
+    /// protocol. A consumer passes the union of what it is able to handle;
+    /// acquisition fails if the exporter cannot satisfy the request.
+    pub struct BufferFlags: u32 {
+        const WRITABLE = 0x0001;
+        const FORMAT = 0x0004;
+        const ND = 0x0008;
+        const STRIDES = 0x0010 | Self::ND.bits;
+        const C_CONTIGUOUS = 0x0020 | Self::STRIDES.bits;
+        const F_CONTIGUOUS = 0x0040 | Self::STRIDES.bits;
+        const ANY_CONTIGUOUS = 0x0080 | Self::STRIDES.bits;
+        const INDIRECT = 0x0100 | Self::STRIDES.bits;
+
+        const CONTIG_RO = Self::ND.bits;
+        const CONTIG = Self::ND.bits | Self::WRITABLE.bits;
+        const STRIDED_RO = Self::STRIDES.bits;
+        const STRIDED = Self::STRIDES.bits | Self::WRITABLE.bits;
+        const RECORDS_RO = Self::STRIDES.bits | Self::FORMAT.bits;
+        const RECORDS = Self::STRIDES.bits | Self::WRITABLE.bits | Self::FORMAT.bits;
+        const FULL_RO = Self::INDIRECT.bits | Self::FORMAT.bits;
+        const FULL = Self::INDIRECT.bits | Self::WRITABLE.bits | Self::FORMAT.bits;
+    }
+}
+
+impl BufferFlags {
+    /// `PyBUF_SIMPLE`: a one-dimensional, C-contiguous, read-only request
+    pub const SIMPLE: Self = Self::empty();
+}
+
+/// Traversal/contiguity order of a multi-dimensional buffer, i.e. which
+/// index varies fastest: the last one (C, row-major) or the first one
+/// (Fortran, column-major).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferOrder {
+    C,
+    F,
+}
+
 #[derive(Debug, Clone)]
 pub struct BufferDescriptor {
     /// product(shape) * itemsize
@@ -173,7 +433,6 @@ pub struct BufferDescriptor {
     pub format: Cow<'static, str>,
     /// (shape, stride, suboffset) for each dimension
     pub dim_desc: Vec<(usize, isize, isize)>,
-    // TODO: flags
 }
 
 impl BufferDescriptor {
@@ -183,7 +442,7 @@ impl BufferDescriptor {
             readonly,
             itemsize: 1,
             format: Cow::Borrowed("B"),
-            dim_desc: vec![(bytes_len, 1, 0)],
+            dim_desc: vec![(bytes_len, 1, -1)],
         }
     }
 
@@ -198,7 +457,7 @@ impl BufferDescriptor {
             readonly,
             itemsize,
             format,
-            dim_desc: vec![(bytes_len / itemsize, itemsize as isize, 0)],
+            dim_desc: vec![(bytes_len / itemsize, itemsize as isize, -1)],
         }
     }
 
@@ -209,7 +468,7 @@ impl BufferDescriptor {
         let mut shape_product = 1;
         for (shape, stride, suboffset) in self.dim_desc.iter().cloned() {
             shape_product *= shape;
-            assert!(suboffset >= 0);
+            assert!(suboffset >= -1);
             assert!(stride != 0);
         }
         assert!(shape_product * self.itemsize == self.len);
@@ -225,37 +484,126 @@ impl BufferDescriptor {
         self.dim_desc.len()
     }
 
-    pub fn is_contiguous(&self) -> bool {
+    /// whether any dimension uses PIL-style indirect addressing
+    pub fn has_suboffsets(&self) -> bool {
+        self.dim_desc
+            .iter()
+            .any(|&(_, _, suboffset)| suboffset >= 0)
+    }
+
+    /// dereference one PIL-style indirection step: the native-endian `usize`
+    /// at `pos` in `bytes` is an offset to the next level, to which the
+    /// suboffset is added
+    pub fn deref_suboffset(bytes: &[u8], pos: isize, suboffset: isize) -> isize {
+        const PTR_SIZE: usize = std::mem::size_of::<usize>();
+        let pos = pos as usize;
+        let mut ptr = [0; PTR_SIZE];
+        ptr.copy_from_slice(&bytes[pos..pos + PTR_SIZE]);
+        usize::from_ne_bytes(ptr) as isize + suboffset
+    }
+
+    /// check that this buffer can satisfy a consumer's request `flags`,
+    /// following the C buffer protocol semantics
+    pub fn check_request(&self, flags: BufferFlags, vm: &VirtualMachine) -> PyResult<()> {
+        if flags.contains(BufferFlags::WRITABLE) && self.readonly {
+            return Err(vm.new_buffer_error("Object is not writable".to_owned()));
+        }
+        if flags.contains(BufferFlags::C_CONTIGUOUS) && !self.is_contiguous(BufferOrder::C) {
+            return Err(vm.new_buffer_error("underlying buffer is not C-contiguous".to_owned()));
+        }
+        if flags.contains(BufferFlags::F_CONTIGUOUS) && !self.is_contiguous(BufferOrder::F) {
+            return Err(
+                vm.new_buffer_error("underlying buffer is not Fortran contiguous".to_owned())
+            );
+        }
+        if flags.contains(BufferFlags::ANY_CONTIGUOUS)
+            && !self.is_contiguous(BufferOrder::C)
+            && !self.is_contiguous(BufferOrder::F)
+        {
+            return Err(vm.new_buffer_error("underlying buffer is not contiguous".to_owned()));
+        }
+        if !flags.contains(BufferFlags::INDIRECT) && self.has_suboffsets() {
+            return Err(vm.new_buffer_error("underlying buffer requires suboffsets".to_owned()));
+        }
+        if !flags.contains(BufferFlags::STRIDES) && !self.is_contiguous(BufferOrder::C) {
+            return Err(vm.new_buffer_error("underlying buffer is not C-contiguous".to_owned()));
+        }
+        Ok(())
+    }
+
+    /// Adapt `src` for a `zip_eq` copy into a buffer described by `self`:
+    /// shapes must match in every dimension, except that a size-1 dimension
+    /// of `src` is broadcast (given a repeating stride of 0) to `self`'s
+    /// extent. Returns `None` if itemsize, format or shape are incompatible.
+    pub fn broadcast(&self, src: &Self) -> Option<Self> {
+        if self.itemsize != src.itemsize || self.format != src.format || self.ndim() != src.ndim() {
+            return None;
+        }
+        let mut dim_desc = src.dim_desc.clone();
+        for (&(shape, _, _), src_dim) in self.dim_desc.iter().zip(dim_desc.iter_mut()) {
+            if src_dim.0 != shape {
+                if src_dim.0 != 1 {
+                    return None;
+                }
+                src_dim.0 = shape;
+                src_dim.1 = 0;
+            }
+        }
+        Some(Self {
+            len: self.len,
+            dim_desc,
+            ..src.clone()
+        })
+    }
+
+    pub fn is_contiguous(&self, order: BufferOrder) -> bool {
+        if self.has_suboffsets() {
+            return false;
+        }
         if self.len == 0 {
             return true;
         }
         let mut sd = self.itemsize;
-        for (shape, stride, _) in self.dim_desc.iter().cloned().rev() {
+        let mut check = |(shape, stride, _): (usize, isize, isize)| {
             if shape > 1 && stride != sd as isize {
                 return false;
             }
             sd *= shape;
+            true
+        };
+        let mut dims = self.dim_desc.iter().cloned();
+        match order {
+            BufferOrder::C => dims.rev().all(&mut check),
+            BufferOrder::F => dims.all(&mut check),
         }
-        true
     }
 
     /// this function do not check the bound
     /// panic if indices.len() != ndim
-    pub fn fast_position(&self, indices: &[usize]) -> isize {
-        let mut pos = 0;
+    pub fn fast_position(&self, bytes: &[u8], start: isize, indices: &[usize]) -> isize {
+        let mut pos = start;
         for (i, (_, stride, suboffset)) in indices
             .iter()
             .cloned()
             .zip_eq(self.dim_desc.iter().cloned())
         {
-            pos += i as isize * stride + suboffset;
+            pos += i as isize * stride;
+            if suboffset >= 0 {
+                pos = Self::deref_suboffset(bytes, pos, suboffset);
+            }
         }
         pos
     }
 
     /// panic if indices.len() != ndim
-    pub fn position(&self, indices: &[isize], vm: &VirtualMachine) -> PyResult<isize> {
-        let mut pos = 0;
+    pub fn position(
+        &self,
+        bytes: &[u8],
+        start: isize,
+        indices: &[isize],
+        vm: &VirtualMachine,
+    ) -> PyResult<isize> {
+        let mut pos = start;
         for (i, (shape, stride, suboffset)) in indices
             .iter()
             .cloned()
@@ -264,37 +612,60 @@ impl BufferDescriptor {
             let i = i.wrapped_at(shape).ok_or_else(|| {
                 vm.new_index_error(format!("index out of bounds on dimension {i}"))
             })?;
-            pos += i as isize * stride + suboffset;
+            pos += i as isize * stride;
+            if suboffset >= 0 {
+                pos = Self::deref_suboffset(bytes, pos, suboffset);
+            }
         }
         Ok(pos)
     }
 
-    pub fn for_each_segment<F>(&self, try_conti: bool, mut f: F)
-    where
+    /// visit the buffer's segments in logical `order`, i.e. with the last
+    /// (for C order) or the first (for F order) index varying fastest;
+    /// the emitted ranges are absolute positions in `bytes`, starting the
+    /// traversal at `start`
+    pub fn for_each_segment<F>(
+        &self,
+        bytes: &[u8],
+        start: isize,
+        order: BufferOrder,
+        try_conti: bool,
+        mut f: F,
+    ) where
         F: FnMut(Range<isize>),
     {
         if self.ndim() == 0 {
-            f(0..self.itemsize as isize);
+            f(start..start + self.itemsize as isize);
             return;
         }
-        if try_conti && self.is_last_dim_contiguous() {
-            self._for_each_segment::<_, true>(0, 0, &mut f);
+        if try_conti && self.is_innermost_dim_contiguous(order) {
+            self._for_each_segment::<_, true>(bytes, order, start, 0, &mut f);
         } else {
-            self._for_each_segment::<_, false>(0, 0, &mut f);
+            self._for_each_segment::<_, false>(bytes, order, start, 0, &mut f);
         }
     }
 
-    fn _for_each_segment<F, const CONTI: bool>(&self, mut index: isize, dim: usize, f: &mut F)
-    where
+    fn _for_each_segment<F, const CONTI: bool>(
+        &self,
+        bytes: &[u8],
+        order: BufferOrder,
+        mut index: isize,
+        level: usize,
+        f: &mut F,
+    ) where
         F: FnMut(Range<isize>),
     {
-        let (shape, stride, suboffset) = self.dim_desc[dim];
-        if dim + 1 == self.ndim() {
+        let (shape, stride, suboffset) = self.dim_desc[self.level_dim(order, level)];
+        if level + 1 == self.ndim() {
             if CONTI {
                 f(index..index + (shape * self.itemsize) as isize);
             } else {
                 for _ in 0..shape {
-                    let pos = index + suboffset;
+                    let pos = if suboffset >= 0 {
+                        Self::deref_suboffset(bytes, index, suboffset)
+                    } else {
+                        index
+                    };
                     f(pos..pos + self.itemsize as isize);
                     index += stride;
                 }
@@ -302,41 +673,53 @@ impl BufferDescriptor {
             return;
         }
         for _ in 0..shape {
-            self._for_each_segment::<F, CONTI>(index + suboffset, dim + 1, f);
+            let base = if suboffset >= 0 {
+                Self::deref_suboffset(bytes, index, suboffset)
+            } else {
+                index
+            };
+            self._for_each_segment::<F, CONTI>(bytes, order, base, level + 1, f);
             index += stride;
         }
     }
 
-    /// zip two BufferDescriptor with the same shape
-    pub fn zip_eq<F>(&self, other: &Self, try_conti: bool, mut f: F)
+    /// zip two BufferDescriptor with the same shape; indirect (suboffset)
+    /// buffers are not supported here
+    pub fn zip_eq<F>(&self, other: &Self, order: BufferOrder, try_conti: bool, mut f: F)
     where
         F: FnMut(Range<isize>, Range<isize>) -> bool,
     {
+        debug_assert!(!self.has_suboffsets() && !other.has_suboffsets());
         if self.ndim() == 0 {
             f(0..self.itemsize as isize, 0..other.itemsize as isize);
             return;
         }
-        if try_conti && self.is_last_dim_contiguous() {
-            self._zip_eq::<_, true>(other, 0, 0, 0, &mut f);
+        if try_conti
+            && self.is_innermost_dim_contiguous(order)
+            && other.is_innermost_dim_contiguous(order)
+        {
+            self._zip_eq::<_, true>(other, order, 0, 0, 0, &mut f);
         } else {
-            self._zip_eq::<_, false>(other, 0, 0, 0, &mut f);
+            self._zip_eq::<_, false>(other, order, 0, 0, 0, &mut f);
         }
     }
 
     fn _zip_eq<F, const CONTI: bool>(
         &self,
         other: &Self,
+        order: BufferOrder,
         mut a_index: isize,
         mut b_index: isize,
-        dim: usize,
+        level: usize,
         f: &mut F,
     ) where
         F: FnMut(Range<isize>, Range<isize>) -> bool,
     {
-        let (shape, a_stride, a_suboffset) = self.dim_desc[dim];
-        let (_b_shape, b_stride, b_suboffset) = other.dim_desc[dim];
+        let dim = self.level_dim(order, level);
+        let (shape, a_stride, _) = self.dim_desc[dim];
+        let (_b_shape, b_stride, _) = other.dim_desc[dim];
         debug_assert_eq!(shape, _b_shape);
-        if dim + 1 == self.ndim() {
+        if level + 1 == self.ndim() {
             if CONTI {
                 if f(
                     a_index..a_index + (shape * self.itemsize) as isize,
@@ -346,11 +729,9 @@ impl BufferDescriptor {
                 }
             } else {
                 for _ in 0..shape {
-                    let a_pos = a_index + a_suboffset;
-                    let b_pos = b_index + b_suboffset;
                     if f(
-                        a_pos..a_pos + self.itemsize as isize,
-                        b_pos..b_pos + other.itemsize as isize,
+                        a_index..a_index + self.itemsize as isize,
+                        b_index..b_index + other.itemsize as isize,
                     ) {
                         return;
                     }
@@ -362,21 +743,23 @@ impl BufferDescriptor {
         }
 
         for _ in 0..shape {
-            self._zip_eq::<F, CONTI>(
-                other,
-                a_index + a_suboffset,
-                b_index + b_suboffset,
-                dim + 1,
-                f,
-            );
+            self._zip_eq::<F, CONTI>(other, order, a_index, b_index, level + 1, f);
             a_index += a_stride;
             b_index += b_stride;
         }
     }
 
-    fn is_last_dim_contiguous(&self) -> bool {
-        let (_, stride, suboffset) = self.dim_desc[self.ndim() - 1];
-        suboffset == 0 && stride == self.itemsize as isize
+    /// the dimension traversed at recursion depth `level` for the given order
+    fn level_dim(&self, order: BufferOrder, level: usize) -> usize {
+        match order {
+            BufferOrder::C => level,
+            BufferOrder::F => self.ndim() - 1 - level,
+        }
+    }
+
+    fn is_innermost_dim_contiguous(&self, order: BufferOrder) -> bool {
+        let (_, stride, suboffset) = self.dim_desc[self.level_dim(order, self.ndim() - 1)];
+        suboffset < 0 && stride == self.itemsize as isize
     }
 
     pub fn is_zero_in_shape(&self) -> bool {
@@ -387,9 +770,21 @@ impl BufferDescriptor {
         }
         false
     }
+}
 
-    // TODO: support fortain order
+/// Plain-old-data element types a buffer can be viewed as via
+/// [`PyBuffer::as_slice_of`].
+///
+/// # Safety
+/// Implementors must be valid for any bit pattern and contain no padding.
+pub unsafe trait Pod: Copy {}
+
+macro_rules! impl_pod {
+    ($($t:ty)*) => {
+        $(unsafe impl Pod for $t {})*
+    };
 }
+impl_pod!(u8 i8 u16 i16 u32 i32 u64 i64 usize isize f32 f64);
 
 pub trait BufferResizeGuard {
     type Resizable<'a>: 'a
@@ -429,29 +824,20 @@ impl Unconstructible for VecBuffer {}
 impl PyRef<VecBuffer> {
     pub fn into_pybuffer(self, readonly: bool) -> PyBuffer {
         let len = self.data.lock().len();
-        PyBuffer::new(
-            self.into(),
-            BufferDescriptor::simple(len, readonly),
-            &VEC_BUFFER_METHODS,
-        )
+        PyBuffer::new(self, BufferDescriptor::simple(len, readonly))
     }
 
     pub fn into_pybuffer_with_descriptor(self, desc: BufferDescriptor) -> PyBuffer {
-        PyBuffer::new(self.into(), desc, &VEC_BUFFER_METHODS)
+        PyBuffer::new(self, desc)
     }
 }
 
-static VEC_BUFFER_METHODS: BufferMethods = BufferMethods {
-    obj_bytes: |buffer| {
-        PyMutexGuard::map_immutable(buffer.obj_as::<VecBuffer>().data.lock(), |x| x.as_slice())
-            .into()
-    },
-    obj_bytes_mut: |buffer| {
-        PyMutexGuard::map(buffer.obj_as::<VecBuffer>().data.lock(), |x| {
-            x.as_mut_slice()
-        })
-        .into()
-    },
-    release: |_| {},
-    retain: |_| {},
-};
+impl BufferInternal for VecBuffer {
+    fn obj_bytes(&self) -> BorrowedValue<[u8]> {
+        PyMutexGuard::map_immutable(self.data.lock(), |x| x.as_slice()).into()
+    }
+
+    fn obj_bytes_mut(&self) -> BorrowedValueMut<[u8]> {
+        PyMutexGuard::map(self.data.lock(), |x| x.as_mut_slice()).into()
+    }
+}
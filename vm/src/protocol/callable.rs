@@ -1,7 +1,11 @@
 use crate::{
+    builtins::{
+        builtin_func::{PyBuiltinFunction, PyBuiltinMethod},
+        PyTuple,
+    },
     function::{FuncArgs, IntoFuncArgs},
     types::GenericMethod,
-    {AsObject, PyObject, PyResult, VirtualMachine},
+    {AsObject, Py, PyObject, PyObjectRef, PyResult, VirtualMachine},
 };
 
 impl PyObject {
@@ -20,14 +24,30 @@ impl PyObject {
         self.call_with_args(args.into_args(vm), vm)
     }
 
+    /// PyObject_Vectorcall: call with the arguments still borrowed from the
+    /// caller's stack — positional arguments followed by the values of
+    /// `kwnames` — so that callees with a vectorcall slot skip the `FuncArgs`
+    /// boxing. Anything else is called through the regular slot, boxing the
+    /// arguments after all.
+    pub fn vectorcall(
+        &self,
+        args: &[PyObjectRef],
+        kwnames: Option<&Py<PyTuple>>,
+        vm: &VirtualMachine,
+    ) -> PyResult {
+        if let Some(vectorcall) = self.class().slots.vectorcall.load() {
+            return vectorcall(self, args, kwnames, vm);
+        }
+        self.call_with_args(FuncArgs::from_vectorcall(args, kwnames, vm), vm)
+    }
+
     /// PyObject_Call
     pub fn call_with_args(&self, args: FuncArgs, vm: &VirtualMachine) -> PyResult {
         vm_trace!("Invoke: {:?} {:?}", callable, args);
         let Some(callable) = self.to_callable() else {
-            return Err(vm.new_type_error(format!(
-                "'{}' object is not callable",
-                self.class().name()
-            )));
+            return Err(
+                vm.new_type_error(format!("'{}' object is not callable", self.class().name()))
+            );
         };
         callable.invoke(args, vm)
     }
@@ -45,70 +65,77 @@ impl<'a> PyCallable<'a> {
     }
 
     pub fn invoke(&self, args: impl IntoFuncArgs, vm: &VirtualMachine) -> PyResult {
-        vm.trace_event(TraceEvent::Call)?;
+        // Python-level functions get their call/return events from frame
+        // execution; native callables are opaque to the frame machinery, so
+        // report them to the profiler here with CPython's c_* events
+        let profiled_native = vm.use_tracing.get()
+            && (self.obj.payload_is::<PyBuiltinFunction>()
+                || self.obj.payload_is::<PyBuiltinMethod>());
+        if profiled_native {
+            vm.profile_event(ProfileEvent::CCall, self.obj.to_owned())?;
+        }
         let result = (self.call)(self.obj, args.into_args(vm), vm);
-        vm.trace_event(TraceEvent::Return)?;
+        if profiled_native {
+            let event = if result.is_ok() {
+                ProfileEvent::CReturn
+            } else {
+                ProfileEvent::CException
+            };
+            vm.profile_event(event, self.obj.to_owned())?;
+        }
         result
     }
 }
 
-/// Trace events for sys.settrace and sys.setprofile.
-enum TraceEvent {
-    Call,
-    Return,
+/// Profile events reported to the sys.setprofile function for calls into
+/// native (builtin) callables.
+pub(crate) enum ProfileEvent {
+    CCall,
+    CReturn,
+    CException,
 }
 
-impl std::fmt::Display for TraceEvent {
+impl std::fmt::Display for ProfileEvent {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        use TraceEvent::*;
+        use ProfileEvent::*;
         match self {
-            Call => write!(f, "call"),
-            Return => write!(f, "return"),
+            CCall => write!(f, "c_call"),
+            CReturn => write!(f, "c_return"),
+            CException => write!(f, "c_exception"),
         }
     }
 }
 
 impl VirtualMachine {
-    /// Call registered trace function.
-    #[inline]
-    fn trace_event(&self, event: TraceEvent) -> PyResult<()> {
-        if self.use_tracing.get() {
-            self._trace_event_inner(event)
-        } else {
-            Ok(())
-        }
-    }
-    fn _trace_event_inner(&self, event: TraceEvent) -> PyResult<()> {
-        let trace_func = self.trace_func.borrow().to_owned();
-        let profile_func = self.profile_func.borrow().to_owned();
-        if self.is_none(&trace_func) && self.is_none(&profile_func) {
+    /// Call the sys.setprofile function with a c_call/c_return/c_exception
+    /// event; `arg` is the native callable itself, like CPython. If the
+    /// profiler raises, profiling is disabled and the error propagated.
+    pub(crate) fn profile_event(&self, event: ProfileEvent, arg: PyObjectRef) -> PyResult<()> {
+        if !self.use_tracing.get() {
             return Ok(());
         }
-
-        let frame_ref = self.current_frame();
-        if frame_ref.is_none() {
+        let profile_func = self.profile_func.borrow().to_owned();
+        if self.is_none(&profile_func) {
             return Ok(());
         }
-
-        let frame = frame_ref.unwrap().as_object().to_owned();
-        let event = self.ctx.new_str(event.to_string()).into();
-        let args = vec![frame, event, self.ctx.none()];
+        let frame = match self.current_frame() {
+            Some(frame) => frame.as_object().to_owned(),
+            None => return Ok(()),
+        };
 
         // temporarily disable tracing, during the call to the
-        // tracing function itself.
-        if !self.is_none(&trace_func) {
-            self.use_tracing.set(false);
-            let res = trace_func.call(args.clone(), self);
-            self.use_tracing.set(true);
-            res?;
-        }
-
-        if !self.is_none(&profile_func) {
-            self.use_tracing.set(false);
-            let res = profile_func.call(args, self);
-            self.use_tracing.set(true);
-            res?;
+        // profile function itself.
+        self.use_tracing.set(false);
+        let res = profile_func.call((frame, self.ctx.new_str(event.to_string()), arg), self);
+        self.use_tracing.set(true);
+        match res {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                self.profile_func.replace(self.ctx.none());
+                let trace_is_none = self.is_none(&self.trace_func.borrow());
+                self.use_tracing.set(!trace_is_none);
+                Err(e)
+            }
         }
-        Ok(())
     }
 }
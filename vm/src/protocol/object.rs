@@ -85,7 +85,7 @@ impl PyObject {
     }
 
     // get_attribute should be used for full attribute access (usually from user code).
-    #[cfg_attr(feature = "flame-it", flame("PyObjectRef"))]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     #[inline]
     pub(crate) fn get_attr_inner(&self, attr_name: &Py<PyStr>, vm: &VirtualMachine) -> PyResult {
         vm_trace!("object.__getattribute__: {:?} {:?}", self, attr_name);
@@ -137,19 +137,32 @@ impl PyObject {
     }
 
     // int PyObject_GenericSetAttr(PyObject *o, PyObject *name, PyObject *value)
-    #[cfg_attr(feature = "flame-it", flame)]
+    #[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
     pub fn generic_setattr(
         &self,
         attr_name: &Py<PyStr>,
         value: PySetterValue,
         vm: &VirtualMachine,
     ) -> PyResult<()> {
-        vm_trace!("object.__setattr__({:?}, {}, {:?})", self, attr_name, value);
-        if let Some(attr) = vm
+        let cls_attr = vm
             .ctx
             .interned_str(attr_name)
-            .and_then(|attr_name| self.get_class_attr(attr_name))
-        {
+            .and_then(|attr_name| self.get_class_attr(attr_name));
+        self.generic_setattr_resolved(attr_name, cls_attr, value, vm)
+    }
+
+    /// The descriptor/instance-dict half of [`Self::generic_setattr`], with
+    /// the MRO lookup already done; `cls_attr` must be the result of
+    /// `self.get_class_attr(attr_name)` (possibly served from an inline cache)
+    pub(crate) fn generic_setattr_resolved(
+        &self,
+        attr_name: &Py<PyStr>,
+        cls_attr: Option<PyObjectRef>,
+        value: PySetterValue,
+        vm: &VirtualMachine,
+    ) -> PyResult<()> {
+        vm_trace!("object.__setattr__({:?}, {}, {:?})", self, attr_name, value);
+        if let Some(attr) = cls_attr {
             let descr_set = attr.class().mro_find_map(|cls| cls.slots.descr_set.load());
             if let Some(descriptor) = descr_set {
                 return descriptor(&attr, self.to_owned(), value, vm);
@@ -198,11 +211,25 @@ impl PyObject {
         name_str: &Py<PyStr>,
         dict: Option<PyDictRef>,
         vm: &VirtualMachine,
+    ) -> PyResult<Option<PyObjectRef>> {
+        let cls_attr_name = vm.ctx.interned_str(name_str);
+        let cls_attr = cls_attr_name.and_then(|name| self.class().get_attr(name));
+        self.generic_getattr_resolved(name_str, cls_attr, dict, vm)
+    }
+
+    /// The descriptor/instance-dict half of [`Self::generic_getattr_opt`], with
+    /// the MRO lookup already done; `cls_attr` must be the result of
+    /// `self.class().get_attr(name_str)` (possibly served from an inline cache)
+    pub(crate) fn generic_getattr_resolved(
+        &self,
+        name_str: &Py<PyStr>,
+        cls_attr: Option<PyObjectRef>,
+        dict: Option<PyDictRef>,
+        vm: &VirtualMachine,
     ) -> PyResult<Option<PyObjectRef>> {
         let name = name_str.as_str();
         let obj_cls = self.class();
-        let cls_attr_name = vm.ctx.interned_str(name_str);
-        let cls_attr = match cls_attr_name.and_then(|name| obj_cls.get_attr(name)) {
+        let cls_attr = match cls_attr {
             Some(descr) => {
                 let descr_cls = descr.class();
                 let descr_get = descr_cls.mro_find_map(|cls| cls.slots.descr_get.load());
@@ -0,0 +1,83 @@
+//! Bridging Python coroutines onto Rust async runtimes.
+//!
+//! [`PyFuture`] wraps an awaitable object — usually a coroutine — so an
+//! embedder can step its frame one `await` at a time instead of parking a
+//! thread while it runs to completion. It also implements
+//! [`std::future::Future`], which lets a runtime like tokio drive Python
+//! coroutines directly on a worker that has entered a vm context.
+
+use crate::{
+    builtins::PyCoroutine, protocol::PyIterReturn, vm::thread, PyObjectRef, PyResult,
+    VirtualMachine,
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// What a single [`PyFuture::poll_once`] step ended with.
+pub enum PyFuturePoll {
+    /// the coroutine suspended at an `await`, yielding this value (for
+    /// asyncio-style coroutines, the future it is waiting on) to its driver
+    Yielded(PyObjectRef),
+    /// the coroutine ran to completion with this return value
+    Ready(PyObjectRef),
+}
+
+/// An awaitable being stepped by an embedder-driven event loop.
+pub struct PyFuture {
+    coro: PyObjectRef,
+    /// what the next step sends into the coroutine: `None` until the
+    /// embedder resolves whatever the last step yielded
+    send: Option<PyObjectRef>,
+}
+
+impl PyFuture {
+    pub fn new(coro: PyObjectRef) -> Self {
+        PyFuture { coro, send: None }
+    }
+
+    /// Resolve the value the last step yielded; the next step sends `value`
+    /// back into the suspended `await` expression.
+    pub fn set_value(&mut self, value: PyObjectRef) {
+        self.send = Some(value);
+    }
+
+    /// Step the coroutine to its next suspension point. Builtin coroutines
+    /// resume their frame directly; anything else is stepped through its
+    /// `send` method, so wrappers and generator-based coroutines work too.
+    pub fn poll_once(&mut self, vm: &VirtualMachine) -> PyResult<PyFuturePoll> {
+        let value = self.send.take().unwrap_or_else(|| vm.ctx.none());
+        let result = match self.coro.payload::<PyCoroutine>() {
+            Some(coro) => coro.as_coro().send(&self.coro, value, vm)?,
+            None => PyIterReturn::from_pyresult(vm.call_method(&self.coro, "send", (value,)), vm)?,
+        };
+        Ok(match result {
+            PyIterReturn::Return(yielded) => PyFuturePoll::Yielded(yielded),
+            PyIterReturn::StopIteration(value) => PyFuturePoll::Ready(vm.unwrap_or_none(value)),
+        })
+    }
+}
+
+impl Future for PyFuture {
+    type Output = PyResult<PyObjectRef>;
+
+    /// Polling must happen on a thread that is inside a vm context (e.g.
+    /// within [`Interpreter::enter`](crate::Interpreter::enter) or a spawned
+    /// vm thread). Every `await` point in the Python code hands control back
+    /// to the runtime once: the task re-wakes itself, so a yielded value the
+    /// embedder does not intercept with [`Self::set_value`] resolves to
+    /// `None` on the next poll.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let zelf = self.get_mut();
+        thread::with_current_vm(|vm| match zelf.poll_once(vm) {
+            Ok(PyFuturePoll::Ready(value)) => Poll::Ready(Ok(value)),
+            Ok(PyFuturePoll::Yielded(_)) => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            Err(exc) => Poll::Ready(Err(exc)),
+        })
+    }
+}
@@ -3,56 +3,164 @@ use crate::{PyResult, VirtualMachine};
 use std::{
     fmt,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU8, Ordering},
         mpsc,
     },
 };
 
 pub(crate) const NSIG: usize = 64;
-static ANY_TRIGGERED: AtomicBool = AtomicBool::new(false);
+
+/// The process-global half of the "eval breaker": a word or'ing together the
+/// kinds of out-of-band work that aren't tied to one interpreter, so the
+/// check at the top of `execute_instruction` is a pair of relaxed loads that
+/// stay zero while nothing is pending. Work queued on a specific interpreter
+/// sets its bit in the [`PyGlobalState`] `eval_breaker` instead, so one
+/// interpreter draining its own queues can't clear flags another one set.
+///
+/// [`PyGlobalState`]: crate::vm::PyGlobalState
+static EVAL_BREAKER: AtomicU8 = AtomicU8::new(0);
+/// an OS signal handler ran; poll [`TRIGGERS`]
+const PENDING_SIGNALS: u8 = 1 << 0;
+/// a [`UserSignal`] was sent; drain the vm's receiver
+const PENDING_USER_SIGNALS: u8 = 1 << 1;
+/// `set_async_exc` scheduled an exception for some thread
+#[cfg(feature = "threading")]
+const PENDING_ASYNC_EXC: u8 = 1 << 2;
+/// `add_pending_call` queued a callback; drain the interpreter's queue
+const PENDING_CALLS: u8 = 1 << 3;
 // hack to get around const array repeat expressions, rust issue #79270
 #[allow(clippy::declare_interior_mutable_const)]
 const ATOMIC_FALSE: AtomicBool = AtomicBool::new(false);
 pub(crate) static TRIGGERS: [AtomicBool; NSIG] = [ATOMIC_FALSE; NSIG];
 
-#[cfg_attr(feature = "flame-it", flame)]
+#[cfg_attr(feature = "flame-it", tracing::instrument(level = "trace", skip_all))]
 #[inline(always)]
 pub fn check_signals(vm: &VirtualMachine) -> PyResult<()> {
+    if EVAL_BREAKER.load(Ordering::Relaxed) == 0
+        && vm.state.eval_breaker.load(Ordering::Relaxed) == 0
+    {
+        return Ok(());
+    }
+
+    check_pending(vm)
+}
+#[inline(never)]
+#[cold]
+fn check_pending(vm: &VirtualMachine) -> PyResult<()> {
+    #[cfg(feature = "threading")]
+    check_async_exc(vm)?;
+
+    if vm.state.eval_breaker.load(Ordering::Relaxed) & PENDING_CALLS != 0 {
+        run_pending_calls(vm)?;
+    }
+
     if vm.signal_handlers.is_none() {
+        // leave the signal bits set for the vm that can run handlers
         return Ok(());
     }
 
-    if !ANY_TRIGGERED.swap(false, Ordering::Acquire) {
+    const SIGNAL_BITS: u8 = PENDING_SIGNALS | PENDING_USER_SIGNALS;
+    let pending = EVAL_BREAKER.fetch_and(!SIGNAL_BITS, Ordering::Acquire) & SIGNAL_BITS;
+    if pending == 0 {
         return Ok(());
     }
 
-    trigger_signals(vm)
+    trigger_signals(vm, pending)
 }
-#[inline(never)]
-#[cold]
-fn trigger_signals(vm: &VirtualMachine) -> PyResult<()> {
-    // unwrap should never fail since we check above
-    let signal_handlers = vm.signal_handlers.as_ref().unwrap().borrow();
-    for (signum, trigger) in TRIGGERS.iter().enumerate().skip(1) {
-        let triggered = trigger.swap(false, Ordering::Relaxed);
-        if triggered {
-            if let Some(handler) = &signal_handlers[signum] {
-                if let Some(callable) = handler.to_callable() {
-                    callable.invoke((signum, vm.ctx.none()), vm)?;
+
+/// Raise the exception `set_async_exc` scheduled for this thread, if there is
+/// one. The pending bit only clears once no thread has an exception waiting,
+/// so other threads keep taking the slow path until then.
+#[cfg(feature = "threading")]
+fn check_async_exc(vm: &VirtualMachine) -> PyResult<()> {
+    if vm.state.eval_breaker.load(Ordering::Relaxed) & PENDING_ASYNC_EXC == 0 {
+        return Ok(());
+    }
+    let ident = crate::stdlib::thread::thread_to_id(&std::thread::current());
+    let mut async_excs = vm.state.async_excs.lock();
+    if let Some(i) = async_excs.iter().position(|(id, _)| *id == ident) {
+        let (_, exc) = async_excs.remove(i);
+        if async_excs.is_empty() {
+            // under the list lock, so a concurrent `set_async_exc` can't
+            // have its flag cleared out from underneath it
+            vm.state
+                .eval_breaker
+                .fetch_and(!PENDING_ASYNC_EXC, Ordering::Relaxed);
+        }
+        drop(async_excs);
+        Err(exc)
+    } else {
+        Ok(())
+    }
+}
+
+/// Run the callbacks queued by [`VirtualMachine::add_pending_call`] on
+/// whichever of the interpreter's threads reaches a safepoint first. The
+/// pending bit only clears once the queue is empty, so a call that fails
+/// leaves the rest of the queue for the next safepoint, like CPython's
+/// `Py_MakePendingCalls`.
+fn run_pending_calls(vm: &VirtualMachine) -> PyResult<()> {
+    loop {
+        let mut pending_calls = vm.state.pending_calls.lock();
+        if pending_calls.is_empty() {
+            // under the queue lock, so a concurrent `add_pending_call` can't
+            // have its flag cleared out from underneath it
+            vm.state
+                .eval_breaker
+                .fetch_and(!PENDING_CALLS, Ordering::Relaxed);
+            return Ok(());
+        }
+        let call = pending_calls.remove(0);
+        // run without the lock held: the call itself may queue more calls
+        drop(pending_calls);
+        call(vm)?;
+    }
+}
+
+fn trigger_signals(vm: &VirtualMachine, pending: u8) -> PyResult<()> {
+    if pending & PENDING_SIGNALS != 0 {
+        // unwrap should never fail since we check above
+        let signal_handlers = vm.signal_handlers.as_ref().unwrap().borrow();
+        for (signum, trigger) in TRIGGERS.iter().enumerate().skip(1) {
+            let triggered = trigger.swap(false, Ordering::Relaxed);
+            if triggered {
+                if let Some(handler) = &signal_handlers[signum] {
+                    if let Some(callable) = handler.to_callable() {
+                        callable.invoke((signum, vm.ctx.none()), vm)?;
+                    }
                 }
             }
         }
     }
-    if let Some(signal_rx) = &vm.signal_rx {
-        for f in signal_rx.rx.try_iter() {
-            f(vm)?;
+    if pending & PENDING_USER_SIGNALS != 0 {
+        if let Some(signal_rx) = &vm.signal_rx {
+            for f in signal_rx.rx.try_iter() {
+                f(vm)?;
+            }
         }
     }
     Ok(())
 }
 
+fn set_pending(bit: u8) {
+    EVAL_BREAKER.fetch_or(bit, Ordering::Release);
+}
+
 pub(crate) fn set_triggered() {
-    ANY_TRIGGERED.store(true, Ordering::Release);
+    set_pending(PENDING_SIGNALS);
+}
+
+#[cfg(feature = "threading")]
+pub(crate) fn set_async_exc_pending(state: &crate::vm::PyGlobalState) {
+    state
+        .eval_breaker
+        .fetch_or(PENDING_ASYNC_EXC, Ordering::Release);
+}
+
+pub(crate) fn set_pending_calls(state: &crate::vm::PyGlobalState) {
+    state
+        .eval_breaker
+        .fetch_or(PENDING_CALLS, Ordering::Release);
 }
 
 pub fn assert_in_range(signum: i32, vm: &VirtualMachine) -> PyResult<()> {
@@ -84,6 +192,9 @@ pub fn set_interrupt_ex(signum: i32, vm: &VirtualMachine) -> PyResult<()> {
 
 pub type UserSignal = Box<dyn FnOnce(&VirtualMachine) -> PyResult<()> + Send>;
 
+/// A callback queued by [`VirtualMachine::add_pending_call`]
+pub type PendingCall = Box<dyn FnOnce(&VirtualMachine) -> PyResult<()> + Send>;
+
 #[derive(Clone, Debug)]
 pub struct UserSignalSender {
     tx: mpsc::Sender<UserSignal>,
@@ -99,7 +210,7 @@ impl UserSignalSender {
         self.tx
             .send(sig)
             .map_err(|mpsc::SendError(sig)| UserSignalSendError(sig))?;
-        set_triggered();
+        set_pending(PENDING_USER_SIGNALS);
         Ok(())
     }
 }
@@ -0,0 +1,59 @@
+//! Execution counters for the interpreter, enabled with the
+//! `instruction-stats` feature: every executed instruction is tallied under
+//! its opcode, and every frame entry under the function it runs, so the
+//! hottest opcodes and functions can be read back with `sys._stats()`.
+
+use crate::{builtins::PyDictRef, bytecode::Instruction, common::lock::PyMutex};
+use crate::{PyResult, VirtualMachine};
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+
+#[derive(Default)]
+struct Stats {
+    opcodes: BTreeMap<String, u64>,
+    functions: BTreeMap<String, u64>,
+}
+
+static STATS: Lazy<PyMutex<Stats>> = Lazy::new(Default::default);
+
+/// Tally one execution of `instruction` under its variant name.
+pub(crate) fn record_instruction(instruction: Instruction) {
+    let name = format!("{instruction:?}");
+    // the variant name is everything up to the first payload
+    let end = name
+        .find(|c: char| matches!(c, '(' | ' ' | '{'))
+        .unwrap_or(name.len());
+    let name = &name[..end];
+
+    let mut stats = STATS.lock();
+    match stats.opcodes.get_mut(name) {
+        Some(count) => *count += 1,
+        None => {
+            stats.opcodes.insert(name.to_owned(), 1);
+        }
+    }
+}
+
+/// Tally one entry (call or resumption) of a frame of the named function.
+pub(crate) fn record_frame_entry(source_path: &str, obj_name: &str) {
+    let name = format!("{source_path}:{obj_name}");
+    *STATS.lock().functions.entry(name).or_insert(0) += 1;
+}
+
+/// The counters gathered so far, as `{"opcodes": {name: count},
+/// "functions": {name: count}}`; backs `sys._stats()`.
+pub(crate) fn report(vm: &VirtualMachine) -> PyResult<PyDictRef> {
+    let stats = STATS.lock();
+    let opcodes = vm.ctx.new_dict();
+    for (name, count) in &stats.opcodes {
+        opcodes.set_item(name.as_str(), vm.ctx.new_int(*count).into(), vm)?;
+    }
+    let functions = vm.ctx.new_dict();
+    for (name, count) in &stats.functions {
+        functions.set_item(name.as_str(), vm.ctx.new_int(*count).into(), vm)?;
+    }
+    let report = vm.ctx.new_dict();
+    report.set_item("opcodes", opcodes.into(), vm)?;
+    report.set_item("functions", functions.into(), vm)?;
+    Ok(report)
+}
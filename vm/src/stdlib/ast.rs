@@ -62,7 +62,7 @@ mod _ast {
                         )));
                     }
                 }
-                zelf.set_attr(vm.ctx.intern_str(key), value, vm)?;
+                zelf.set_attr(vm.ctx.intern_str(&*key), value, vm)?;
             }
             Ok(())
         }
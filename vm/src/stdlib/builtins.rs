@@ -493,7 +493,7 @@ mod builtins {
                         "Cannot specify a default for {func_name}() with multiple positional arguments"
                     )));
                 }
-                args.args
+                args.args.into_vec()
             }
             std::cmp::Ordering::Equal => args.args[0].try_to_value(vm)?,
             std::cmp::Ordering::Less => {
@@ -1015,6 +1015,7 @@ pub fn make_module(vm: &VirtualMachine, module: PyObjectRef) {
         // Exceptions:
         "BaseException" => ctx.exceptions.base_exception_type.to_owned(),
         "BaseExceptionGroup" => ctx.exceptions.base_exception_group.to_owned(),
+        "ExceptionGroup" => ctx.exceptions.exception_group.to_owned(),
         "SystemExit" => ctx.exceptions.system_exit.to_owned(),
         "KeyboardInterrupt" => ctx.exceptions.keyboard_interrupt.to_owned(),
         "GeneratorExit" => ctx.exceptions.generator_exit.to_owned(),
@@ -1086,6 +1087,11 @@ pub fn make_module(vm: &VirtualMachine, module: PyObjectRef) {
         "EncodingWarning" => ctx.exceptions.encoding_warning.to_owned(),
     });
 
+    // rustpython-specific, raised when run_code_with_budget runs dry
+    extend_module!(vm, module, {
+        "BudgetExceeded" => ctx.exceptions.budget_exceeded.to_owned(),
+    });
+
     #[cfg(feature = "jit")]
     extend_module!(vm, module, {
         "JitError" => ctx.exceptions.jit_error.to_owned(),
@@ -2,7 +2,14 @@ pub(crate) use _functools::make_module;
 
 #[pymodule]
 mod _functools {
-    use crate::{function::OptionalArg, protocol::PyIter, PyObjectRef, PyResult, VirtualMachine};
+    use crate::{
+        builtins::{PyStrRef, PyTypeRef},
+        common::lock::PyMutex,
+        function::OptionalArg,
+        protocol::PyIter,
+        types::{Constructor, GetDescriptor},
+        AsObject, PyObjectRef, PyPayload, PyResult, VirtualMachine,
+    };
 
     #[pyfunction]
     fn reduce(
@@ -30,4 +37,107 @@ mod _functools {
         }
         Ok(accumulator)
     }
+
+    #[pyattr]
+    #[pyclass(name = "cached_property")]
+    #[derive(Debug, PyPayload)]
+    struct PyCachedProperty {
+        func: PyObjectRef,
+        name: PyMutex<Option<PyStrRef>>,
+    }
+
+    #[pyclass(with(GetDescriptor, Constructor), flags(BASETYPE))]
+    impl PyCachedProperty {
+        #[pygetset]
+        fn func(&self) -> PyObjectRef {
+            self.func.clone()
+        }
+
+        #[pygetset(magic)]
+        fn doc(&self, vm: &VirtualMachine) -> PyResult {
+            self.func.get_attr("__doc__", vm)
+        }
+
+        #[pymethod(magic)]
+        fn set_name(
+            &self,
+            _owner: PyObjectRef,
+            name: PyStrRef,
+            vm: &VirtualMachine,
+        ) -> PyResult<()> {
+            let mut stored = self.name.lock();
+            match &*stored {
+                None => {
+                    *stored = Some(name);
+                    Ok(())
+                }
+                Some(stored) if stored.as_str() == name.as_str() => Ok(()),
+                Some(stored) => Err(vm.new_type_error(format!(
+                    "Cannot assign the same cached_property to two different names ({} and {})",
+                    stored.repr(vm)?,
+                    name.repr(vm)?,
+                ))),
+            }
+        }
+    }
+
+    impl GetDescriptor for PyCachedProperty {
+        fn descr_get(
+            zelf: PyObjectRef,
+            obj: Option<PyObjectRef>,
+            _cls: Option<PyObjectRef>,
+            vm: &VirtualMachine,
+        ) -> PyResult {
+            let (zelf, obj) = match Self::_check(&zelf, obj, vm) {
+                Some(obj) => obj,
+                None => return Ok(zelf),
+            };
+            let name = zelf.name.lock().clone().ok_or_else(|| {
+                vm.new_type_error(
+                    "Cannot use cached_property instance without calling __set_name__ on it."
+                        .to_owned(),
+                )
+            })?;
+            let dict = match obj.dict() {
+                Some(dict) => dict,
+                None => {
+                    return Err(vm.new_type_error(format!(
+                        "No '__dict__' attribute on {} instance to cache {} property.",
+                        obj.class().name(),
+                        name.repr(vm)?,
+                    )))
+                }
+            };
+            if let Some(cached) = dict.get_item_opt(&*name, vm)? {
+                return Ok(cached);
+            }
+            let value = zelf.func.call((obj.clone(),), vm)?;
+            // another thread may have stored a value while we computed ours;
+            // the first one in wins, as with CPython's lock-free 3.12 design
+            if let Some(cached) = dict.get_item_opt(&*name, vm)? {
+                return Ok(cached);
+            }
+            if dict.set_item(&*name, value.clone(), vm).is_err() {
+                return Err(vm.new_type_error(format!(
+                    "The '__dict__' attribute on {} instance does not support item assignment for caching {} property.",
+                    obj.class().name(),
+                    name.repr(vm)?,
+                )));
+            }
+            Ok(value)
+        }
+    }
+
+    impl Constructor for PyCachedProperty {
+        type Args = PyObjectRef;
+
+        fn py_new(cls: PyTypeRef, func: Self::Args, vm: &VirtualMachine) -> PyResult {
+            PyCachedProperty {
+                func,
+                name: PyMutex::new(None),
+            }
+            .into_ref_with_type(vm, cls)
+            .map(Into::into)
+        }
+    }
 }
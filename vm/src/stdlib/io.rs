@@ -105,9 +105,12 @@ mod _io {
             PyStrRef, PyType, PyTypeRef,
         },
         class::StaticType,
-        common::lock::{
-            PyMappedThreadMutexGuard, PyRwLock, PyRwLockReadGuard, PyRwLockWriteGuard,
-            PyThreadMutex, PyThreadMutexGuard,
+        common::{
+            borrow::{BorrowedValue, BorrowedValueMut},
+            lock::{
+                PyMappedThreadMutexGuard, PyRwLock, PyRwLockReadGuard, PyRwLockWriteGuard,
+                PyThreadMutex, PyThreadMutexGuard,
+            },
         },
         convert::ToPyObject,
         function::{
@@ -115,7 +118,7 @@ mod _io {
             OptionalArg, OptionalOption, PySetterValue,
         },
         protocol::{
-            BufferDescriptor, BufferMethods, BufferResizeGuard, PyBuffer, PyIterReturn, VecBuffer,
+            BufferDescriptor, BufferInternal, BufferResizeGuard, PyBuffer, PyIterReturn, VecBuffer,
         },
         recursion::ReprGuard,
         types::{
@@ -3330,35 +3333,30 @@ mod _io {
         #[pymethod]
         fn getbuffer(self, vm: &VirtualMachine) -> PyResult<PyMemoryView> {
             let len = self.buffer.read().cursor.get_ref().len();
-            let buffer = PyBuffer::new(
-                self.into(),
-                BufferDescriptor::simple(len, false),
-                &BYTES_IO_BUFFER_METHODS,
-            );
+            let buffer = PyBuffer::new(self, BufferDescriptor::simple(len, false));
             let view = PyMemoryView::from_buffer(buffer, vm)?;
             Ok(view)
         }
     }
 
-    static BYTES_IO_BUFFER_METHODS: BufferMethods = BufferMethods {
-        obj_bytes: |buffer| {
-            let zelf = buffer.obj_as::<BytesIO>();
-            PyRwLockReadGuard::map(zelf.buffer.read(), |x| x.cursor.get_ref().as_slice()).into()
-        },
-        obj_bytes_mut: |buffer| {
-            let zelf = buffer.obj_as::<BytesIO>();
-            PyRwLockWriteGuard::map(zelf.buffer.write(), |x| x.cursor.get_mut().as_mut_slice())
+    impl BufferInternal for BytesIO {
+        fn obj_bytes(&self) -> BorrowedValue<[u8]> {
+            PyRwLockReadGuard::map(self.buffer.read(), |x| x.cursor.get_ref().as_slice()).into()
+        }
+
+        fn obj_bytes_mut(&self) -> BorrowedValueMut<[u8]> {
+            PyRwLockWriteGuard::map(self.buffer.write(), |x| x.cursor.get_mut().as_mut_slice())
                 .into()
-        },
+        }
 
-        release: |buffer| {
-            buffer.obj_as::<BytesIO>().exports.fetch_sub(1);
-        },
+        fn release(&self) {
+            self.exports.fetch_sub(1);
+        }
 
-        retain: |buffer| {
-            buffer.obj_as::<BytesIO>().exports.fetch_add(1);
-        },
-    };
+        fn retain(&self) {
+            self.exports.fetch_add(1);
+        }
+    }
 
     impl BufferResizeGuard for BytesIO {
         type Resizable<'a> = PyRwLockWriteGuard<'a, BufferedIO>;
@@ -3702,7 +3700,7 @@ mod _io {
 #[cfg(any(not(target_arch = "wasm32"), target_os = "wasi"))]
 #[pymodule]
 mod fileio {
-    use super::{Offset, _io::*};
+    use super::{_io::*, Offset};
     use crate::{
         builtins::{PyStr, PyStrRef},
         common::crt_fd::Fd,
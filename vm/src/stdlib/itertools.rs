@@ -36,7 +36,7 @@ mod decl {
     impl PyItertoolsChain {
         #[pyslot]
         fn slot_new(cls: PyTypeRef, args: FuncArgs, vm: &VirtualMachine) -> PyResult {
-            let args_list = PyList::from(args.args);
+            let args_list = PyList::from(args.args.into_vec());
             PyItertoolsChain {
                 source: PyRwLock::new(Some(args_list.to_pyobject(vm).get_iter(vm)?)),
                 active: PyRwLock::new(None),
@@ -19,6 +19,7 @@ mod decl {
     use num_complex::Complex64;
     use num_traits::Zero;
     use rustpython_compiler_core::marshal;
+    use std::cell::RefCell;
 
     #[pyattr(name = "version")]
     use marshal::FORMAT_VERSION;
@@ -53,7 +54,11 @@ mod decl {
                     f(Complex(pycomplex.to_complex64()))
                 }
                 ref pystr @ PyStr => {
-                    f(Str(pystr.as_str()))
+                    if self.is_interned() {
+                        f(InternedStr(pystr.as_str()))
+                    } else {
+                        f(Str(pystr.as_str()))
+                    }
                 }
                 ref pylist @ PyList => {
                     f(List(&pylist.borrow_vec()))
@@ -86,6 +91,15 @@ mod decl {
             });
             Ok(ret)
         }
+        fn identity(&self) -> Option<usize> {
+            // singletons always deserialize to the same object, so a
+            // back-reference to them would only waste space
+            let singleton = self.payload_is::<PyNone>()
+                || self.payload_is::<PyEllipsis>()
+                || self.class().is(PyBool::static_type())
+                || self.is(PyStopIteration::static_type());
+            (!singleton).then(|| self.get_id())
+        }
     }
 
     #[pyfunction]
@@ -94,16 +108,13 @@ mod decl {
         _version: OptionalArg<i32>,
         vm: &VirtualMachine,
     ) -> PyResult<PyBytes> {
-        use marshal::Dumpable;
         let mut buf = Vec::new();
-        value
-            .with_dump(|val| marshal::serialize_value(&mut buf, val))
-            .unwrap_or_else(Err)
-            .map_err(|DumpError| {
-                vm.new_not_implemented_error(
-                    "TODO: not implemented yet or marshal unsupported type".to_owned(),
-                )
-            })?;
+        let mut refs = marshal::DumpRefs::default();
+        marshal::serialize_object(&mut buf, &value, &mut refs).map_err(|DumpError| {
+            vm.new_not_implemented_error(
+                "TODO: not implemented yet or marshal unsupported type".to_owned(),
+            )
+        })?;
         Ok(PyBytes::from(buf))
     }
 
@@ -120,54 +131,57 @@ mod decl {
     }
 
     #[derive(Copy, Clone)]
-    struct PyMarshalBag<'a>(&'a VirtualMachine);
+    struct PyMarshalBag<'a> {
+        vm: &'a VirtualMachine,
+        refs: &'a RefCell<Vec<PyObjectRef>>,
+    }
 
     impl<'a> marshal::MarshalBag for PyMarshalBag<'a> {
         type Value = PyObjectRef;
         fn make_bool(&self, value: bool) -> Self::Value {
-            self.0.ctx.new_bool(value).into()
+            self.vm.ctx.new_bool(value).into()
         }
         fn make_none(&self) -> Self::Value {
-            self.0.ctx.none()
+            self.vm.ctx.none()
         }
         fn make_ellipsis(&self) -> Self::Value {
-            self.0.ctx.ellipsis()
+            self.vm.ctx.ellipsis()
         }
         fn make_float(&self, value: f64) -> Self::Value {
-            self.0.ctx.new_float(value).into()
+            self.vm.ctx.new_float(value).into()
         }
         fn make_complex(&self, value: Complex64) -> Self::Value {
-            self.0.ctx.new_complex(value).into()
+            self.vm.ctx.new_complex(value).into()
         }
         fn make_str(&self, value: &str) -> Self::Value {
-            self.0.ctx.new_str(value).into()
+            self.vm.ctx.new_str(value).into()
         }
         fn make_bytes(&self, value: &[u8]) -> Self::Value {
-            self.0.ctx.new_bytes(value.to_vec()).into()
+            self.vm.ctx.new_bytes(value.to_vec()).into()
         }
         fn make_int(&self, value: BigInt) -> Self::Value {
-            self.0.ctx.new_int(value).into()
+            self.vm.ctx.new_int(value).into()
         }
         fn make_tuple(&self, elements: impl Iterator<Item = Self::Value>) -> Self::Value {
-            self.0.ctx.new_tuple(elements.collect()).into()
+            self.vm.ctx.new_tuple(elements.collect()).into()
         }
         fn make_code(&self, code: CodeObject) -> Self::Value {
-            self.0.ctx.new_code(code).into()
+            self.vm.ctx.new_code(code).into()
         }
         fn make_stop_iter(&self) -> Result<Self::Value, marshal::MarshalError> {
-            Ok(self.0.ctx.exceptions.stop_iteration.to_owned().into())
+            Ok(self.vm.ctx.exceptions.stop_iteration.to_owned().into())
         }
         fn make_list(
             &self,
             it: impl Iterator<Item = Self::Value>,
         ) -> Result<Self::Value, marshal::MarshalError> {
-            Ok(self.0.ctx.new_list(it.collect()).into())
+            Ok(self.vm.ctx.new_list(it.collect()).into())
         }
         fn make_set(
             &self,
             it: impl Iterator<Item = Self::Value>,
         ) -> Result<Self::Value, marshal::MarshalError> {
-            let vm = self.0;
+            let vm = self.vm;
             let set = PySet::new_ref(&vm.ctx);
             for elem in it {
                 set.add(elem, vm).unwrap()
@@ -178,23 +192,32 @@ mod decl {
             &self,
             it: impl Iterator<Item = Self::Value>,
         ) -> Result<Self::Value, marshal::MarshalError> {
-            let vm = self.0;
+            let vm = self.vm;
             Ok(PyFrozenSet::from_iter(vm, it).unwrap().to_pyobject(vm))
         }
         fn make_dict(
             &self,
             it: impl Iterator<Item = (Self::Value, Self::Value)>,
         ) -> Result<Self::Value, marshal::MarshalError> {
-            let vm = self.0;
+            let vm = self.vm;
             let dict = vm.ctx.new_dict();
             for (k, v) in it {
                 dict.set_item(&*k, v, vm).unwrap()
             }
             Ok(dict.into())
         }
+        fn make_interned_str(&self, value: &str) -> Self::Value {
+            self.vm.ctx.intern_str(value).to_object()
+        }
+        fn register_ref(&self, value: &Self::Value) {
+            self.refs.borrow_mut().push(value.clone());
+        }
+        fn get_ref(&self, index: u32) -> Option<Self::Value> {
+            self.refs.borrow().get(index as usize).cloned()
+        }
         type ConstantBag = PyObjBag<'a>;
         fn constant_bag(self) -> Self::ConstantBag {
-            PyObjBag(&self.0.ctx)
+            PyObjBag(&self.vm.ctx)
         }
     }
 
@@ -203,7 +226,9 @@ mod decl {
         let buf = pybuffer.as_contiguous().ok_or_else(|| {
             vm.new_buffer_error("Buffer provided to marshal.loads() is not contiguous".to_owned())
         })?;
-        marshal::deserialize_value(&mut &buf[..], PyMarshalBag(vm)).map_err(|e| match e {
+        let refs = RefCell::new(Vec::new());
+        let bag = PyMarshalBag { vm, refs: &refs };
+        marshal::deserialize_value(&mut &buf[..], bag).map_err(|e| match e {
             marshal::MarshalError::Eof => vm.new_exception_msg(
                 vm.ctx.exceptions.eof_error.to_owned(),
                 "marshal data too short".to_owned(),
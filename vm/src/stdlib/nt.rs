@@ -17,7 +17,7 @@ pub(crate) mod module {
         function::Either,
         function::OptionalArg,
         stdlib::os::{
-            errno_err, DirFd, FollowSymlinks, OsPath, SupportFunc, TargetIsDirectory, _os,
+            _os, errno_err, DirFd, FollowSymlinks, OsPath, SupportFunc, TargetIsDirectory,
         },
         PyResult, TryFromObject, VirtualMachine,
     };
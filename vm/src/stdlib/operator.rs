@@ -6,7 +6,7 @@ mod _operator {
     use crate::{
         builtins::{PyInt, PyIntRef, PyStr, PyStrRef, PyTupleRef, PyTypeRef},
         function::Either,
-        function::{ArgBytesLike, FuncArgs, KwArgs, OptionalArg},
+        function::{ArgBytesLike, FuncArgs, OptionalArg},
         identifier,
         protocol::PyIter,
         recursion::ReprGuard,
@@ -486,9 +486,11 @@ mod _operator {
             if args.args.is_empty() {
                 return Err(vm.new_type_error("itemgetter expected 1 argument, got 0.".to_owned()));
             }
-            PyItemGetter { items: args.args }
-                .into_ref_with_type(vm, cls)
-                .map(Into::into)
+            PyItemGetter {
+                items: args.args.into_vec(),
+            }
+            .into_ref_with_type(vm, cls)
+            .map(Into::into)
         }
     }
 
@@ -558,18 +560,18 @@ mod _operator {
             // With no kwargs, return (type(obj), (name, *args)) tuple.
             if zelf.args.kwargs.is_empty() {
                 let mut pargs = vec![zelf.name.as_object().to_owned()];
-                pargs.append(&mut zelf.args.args.clone());
+                pargs.extend(zelf.args.args.iter().cloned());
                 Ok(vm.new_tuple((zelf.class().to_owned(), vm.ctx.new_tuple(pargs))))
             } else {
                 // If we have kwargs, create a partial function that contains them and pass back that
                 // along with the args.
                 let partial = vm.import("functools", None, 0)?.get_attr("partial", vm)?;
-                let args = FuncArgs::new(
-                    vec![zelf.class().to_owned().into(), zelf.name.clone().into()],
-                    KwArgs::new(zelf.args.kwargs.clone()),
-                );
+                let args = FuncArgs {
+                    args: vec![zelf.class().to_owned().into(), zelf.name.clone().into()].into(),
+                    kwargs: zelf.args.kwargs.clone(),
+                };
                 let callable = partial.call(args, vm)?;
-                Ok(vm.new_tuple((callable, vm.ctx.new_tuple(zelf.args.args.clone()))))
+                Ok(vm.new_tuple((callable, vm.ctx.new_tuple(zelf.args.args.to_vec()))))
             }
         }
     }
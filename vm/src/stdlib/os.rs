@@ -302,7 +302,7 @@ pub(super) mod _os {
         errno_err, DirFd, FollowSymlinks, IOErrorBuilder, OsPath, OsPathOrFd, OutputMode,
         SupportFunc,
     };
-    use crate::common::lock::{OnceCell, PyRwLock};
+    use crate::common::lock::PyRwLock;
     use crate::{
         builtins::{
             PyBytesRef, PyGenericAlias, PyIntRef, PyStrRef, PyTuple, PyTupleRef, PyTypeRef,
@@ -317,7 +317,7 @@ pub(super) mod _os {
         recursion::ReprGuard,
         types::{IterNext, IterNextIterable, PyStructSequence},
         vm::VirtualMachine,
-        AsObject, PyObjectRef, PyPayload, PyRef, PyResult, TryFromObject,
+        AsObject, PyObjectRef, PyOnceRef, PyPayload, PyRef, PyResult, TryFromObject,
     };
     use crossbeam_utils::atomic::AtomicCell;
     use itertools::Itertools;
@@ -611,8 +611,8 @@ pub(super) mod _os {
         pathval: PathBuf,
         file_type: io::Result<fs::FileType>,
         mode: OutputMode,
-        stat: OnceCell<PyObjectRef>,
-        lstat: OnceCell<PyObjectRef>,
+        stat: PyOnceRef,
+        lstat: PyOnceRef,
         #[cfg(unix)]
         ino: AtomicCell<u64>,
         #[cfg(not(unix))]
@@ -830,8 +830,8 @@ pub(super) mod _os {
                                     pathval: entry.path(),
                                     file_type: entry.file_type(),
                                     mode: zelf.mode,
-                                    lstat: OnceCell::new(),
-                                    stat: OnceCell::new(),
+                                    lstat: PyOnceRef::new(),
+                                    stat: PyOnceRef::new(),
                                     ino: AtomicCell::new(ino),
                                 }
                                 .into_ref(&vm.ctx)
@@ -25,8 +25,8 @@ pub mod module {
         convert::{IntoPyException, ToPyObject, TryFromObject},
         function::{Either, OptionalArg},
         stdlib::os::{
-            errno_err, DirFd, FollowSymlinks, OsPath, OsPathOrFd, SupportFunc, TargetIsDirectory,
-            _os, fs_metadata, IOErrorBuilder,
+            _os, errno_err, fs_metadata, DirFd, FollowSymlinks, IOErrorBuilder, OsPath, OsPathOrFd,
+            SupportFunc, TargetIsDirectory,
         },
         types::Constructor,
         utils::ToCString,
@@ -12,7 +12,7 @@ pub(crate) fn make_module(vm: &VirtualMachine) -> PyObjectRef {
 pub(crate) mod module {
     use crate::{
         builtins::PyStrRef,
-        stdlib::os::{DirFd, OsPath, SupportFunc, TargetIsDirectory, _os},
+        stdlib::os::{_os, DirFd, OsPath, SupportFunc, TargetIsDirectory},
         PyObjectRef, PyResult, VirtualMachine,
     };
     use std::env;
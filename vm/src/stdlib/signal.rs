@@ -10,6 +10,11 @@ pub(crate) fn make_module(vm: &VirtualMachine) -> PyObjectRef {
 
 #[pymodule]
 pub(crate) mod _signal {
+    #[cfg(all(unix, not(target_os = "redox")))]
+    use crate::{
+        builtins::{PyBaseExceptionRef, PyTypeRef},
+        function::OptionalArg,
+    };
     use crate::{
         convert::{IntoPyException, TryFromBorrowedObject},
         signal, PyObjectRef, PyResult, VirtualMachine,
@@ -80,6 +85,20 @@ pub(crate) mod _signal {
     #[pyattr]
     use libc::{SIGPWR, SIGSTKFLT};
 
+    #[cfg(all(unix, not(target_os = "redox")))]
+    #[pyattr]
+    use libc::{ITIMER_PROF, ITIMER_REAL, ITIMER_VIRTUAL};
+
+    #[cfg(all(unix, not(target_os = "redox")))]
+    #[pyattr(once, name = "ItimerError")]
+    fn itimer_error(vm: &VirtualMachine) -> PyTypeRef {
+        vm.ctx.new_exception_type(
+            "signal",
+            "ItimerError",
+            Some(vec![vm.ctx.exceptions.os_error.to_owned()]),
+        )
+    }
+
     pub(super) fn init_signal_handlers(module: &PyObjectRef, vm: &VirtualMachine) {
         let sig_dfl = vm.new_pyobj(SIG_DFL as u8);
         let sig_ign = vm.new_pyobj(SIG_IGN as u8);
@@ -171,6 +190,79 @@ pub(crate) mod _signal {
         prev_time.unwrap_or(0)
     }
 
+    /// Send a signal to the calling process and service its handler right
+    /// away instead of waiting for the next eval-breaker check, the way
+    /// CPython's `raise_signal` does.
+    #[pyfunction]
+    fn raise_signal(signum: i32, vm: &VirtualMachine) -> PyResult<()> {
+        signal::assert_in_range(signum, vm)?;
+        let res = unsafe { libc::raise(signum) };
+        if res != 0 {
+            return Err(crate::stdlib::os::errno_err(vm));
+        }
+        signal::check_signals(vm)
+    }
+
+    #[cfg(all(unix, not(target_os = "redox")))]
+    fn timeval_from_secs(secs: f64) -> libc::timeval {
+        libc::timeval {
+            tv_sec: secs as libc::time_t,
+            tv_usec: (secs.fract() * 1_000_000.0) as libc::suseconds_t,
+        }
+    }
+
+    #[cfg(all(unix, not(target_os = "redox")))]
+    fn secs_from_timeval(tv: libc::timeval) -> f64 {
+        tv.tv_sec as f64 + (tv.tv_usec as f64 / 1_000_000.0)
+    }
+
+    #[cfg(all(unix, not(target_os = "redox")))]
+    fn itimer_err(vm: &VirtualMachine) -> PyBaseExceptionRef {
+        let err = std::io::Error::last_os_error();
+        vm.new_exception_msg(vm.class("_signal", "ItimerError"), err.to_string())
+    }
+
+    #[cfg(all(unix, not(target_os = "redox")))]
+    #[pyfunction]
+    fn setitimer(
+        which: i32,
+        seconds: f64,
+        interval: OptionalArg<f64>,
+        vm: &VirtualMachine,
+    ) -> PyResult<(f64, f64)> {
+        let new = libc::itimerval {
+            it_interval: timeval_from_secs(interval.unwrap_or(0.0)),
+            it_value: timeval_from_secs(seconds),
+        };
+        let old = unsafe {
+            let mut old = std::mem::MaybeUninit::<libc::itimerval>::uninit();
+            if libc::setitimer(which, &new, old.as_mut_ptr()) < 0 {
+                return Err(itimer_err(vm));
+            }
+            old.assume_init()
+        };
+        Ok((
+            secs_from_timeval(old.it_value),
+            secs_from_timeval(old.it_interval),
+        ))
+    }
+
+    #[cfg(all(unix, not(target_os = "redox")))]
+    #[pyfunction]
+    fn getitimer(which: i32, vm: &VirtualMachine) -> PyResult<(f64, f64)> {
+        let value = unsafe {
+            let mut value = std::mem::MaybeUninit::<libc::itimerval>::uninit();
+            if libc::getitimer(which, value.as_mut_ptr()) < 0 {
+                return Err(itimer_err(vm));
+            }
+            value.assume_init()
+        };
+        Ok((
+            secs_from_timeval(value.it_value),
+            secs_from_timeval(value.it_interval),
+        ))
+    }
+
     #[pyfunction]
     fn default_int_handler(
         _signum: PyObjectRef,
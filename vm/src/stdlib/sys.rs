@@ -414,6 +414,30 @@ mod sys {
         obj.strong_count()
     }
 
+    #[pyfunction]
+    fn getallocatedblocks() -> usize {
+        crate::object::allocated_blocks()
+    }
+
+    /// There are no pymalloc arenas or pools to report on, objects come
+    /// straight from the system allocator; dump the numbers that do exist.
+    #[pyfunction]
+    fn _debugmallocstats(vm: &VirtualMachine) {
+        let stderr = super::PyStderr(vm);
+        writeln!(
+            stderr,
+            "{} allocated blocks",
+            crate::object::allocated_blocks()
+        );
+        if crate::tracemalloc::is_tracing() {
+            let (current, peak) = crate::tracemalloc::traced_memory();
+            writeln!(
+                stderr,
+                "traced memory: {current} bytes (peak: {peak} bytes)"
+            );
+        }
+    }
+
     #[pyfunction]
     fn getrecursionlimit(vm: &VirtualMachine) -> usize {
         vm.recursion_limit.get()
@@ -461,6 +485,14 @@ mod sys {
         vm.profile_func.borrow().clone()
     }
 
+    /// Counters collected by the `instruction-stats` feature: executions per
+    /// opcode and frame entries per function, as nested dicts.
+    #[cfg(feature = "instruction-stats")]
+    #[pyfunction]
+    fn _stats(vm: &VirtualMachine) -> PyResult<PyDictRef> {
+        crate::stats::report(vm)
+    }
+
     #[pyfunction]
     fn _getframe(offset: OptionalArg<usize>, vm: &VirtualMachine) -> PyResult<FrameRef> {
         let offset = offset.into_option().unwrap_or(0);
@@ -1,11 +1,11 @@
 //! Implementation of the _thread module
 #[cfg_attr(target_arch = "wasm32", allow(unused_imports))]
-pub(crate) use _thread::{make_module, RawRMutex};
+pub(crate) use _thread::{make_module, thread_to_id, RawRMutex};
 
 #[pymodule]
 pub(crate) mod _thread {
     use crate::{
-        builtins::{PyDictRef, PyStr, PyTupleRef, PyTypeRef},
+        builtins::{PyBaseExceptionRef, PyDictRef, PyStr, PyTupleRef, PyTypeRef},
         convert::ToPyException,
         function::{ArgCallable, Either, FuncArgs, KwArgs, OptionalArg, PySetterValue},
         types::{Constructor, GetAttr, SetAttr},
@@ -222,7 +222,7 @@ pub(crate) mod _thread {
         thread_to_id(&thread::current())
     }
 
-    fn thread_to_id(t: &thread::Thread) -> u64 {
+    pub(crate) fn thread_to_id(t: &thread::Thread) -> u64 {
         use std::hash::{Hash, Hasher};
         struct U64Hash {
             v: Option<u64>,
@@ -315,6 +315,19 @@ pub(crate) mod _thread {
         Err(vm.new_exception_empty(vm.ctx.exceptions.system_exit.to_owned()))
     }
 
+    /// What ctypes users reach through `PyThreadState_SetAsyncExc` in
+    /// CPython: schedule `exc` to be raised in the thread `ident`, or cancel
+    /// a pending exception with `None`. Returns the exception that was
+    /// already pending for the thread, if any.
+    #[pyfunction]
+    fn _set_async_exc(
+        ident: u64,
+        exc: Option<PyBaseExceptionRef>,
+        vm: &VirtualMachine,
+    ) -> Option<PyBaseExceptionRef> {
+        vm.set_async_exc(ident, exc)
+    }
+
     thread_local!(static SENTINELS: RefCell<Vec<PyRef<Lock>>> = RefCell::default());
 
     #[pyfunction]
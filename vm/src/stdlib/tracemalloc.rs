@@ -0,0 +1,83 @@
+pub(crate) use tracemalloc::make_module;
+
+#[pymodule]
+mod tracemalloc {
+    use crate::{builtins::PyListRef, PyObjectRef, VirtualMachine};
+
+    #[pyfunction]
+    fn start() {
+        crate::tracemalloc::start();
+    }
+
+    #[pyfunction]
+    fn stop() {
+        crate::tracemalloc::stop();
+    }
+
+    #[pyfunction]
+    fn is_tracing() -> bool {
+        crate::tracemalloc::is_tracing()
+    }
+
+    #[pyfunction]
+    fn clear_traces() {
+        crate::tracemalloc::clear_traces();
+    }
+
+    #[pyfunction]
+    fn get_traced_memory() -> (usize, usize) {
+        crate::tracemalloc::traced_memory()
+    }
+
+    fn site_fields(
+        site: Option<crate::tracemalloc::AllocSite>,
+        vm: &VirtualMachine,
+    ) -> (PyObjectRef, PyObjectRef) {
+        match site {
+            Some(site) => (
+                vm.ctx.new_str(site.filename).into(),
+                vm.ctx.new_int(site.lineno).into(),
+            ),
+            None => (vm.ctx.none(), vm.ctx.none()),
+        }
+    }
+
+    /// Every currently live traced allocation, as `(size, filename, lineno)`
+    /// tuples; the location is `None` when no frame was running.
+    #[pyfunction]
+    fn take_snapshot(vm: &VirtualMachine) -> PyListRef {
+        let traces = crate::tracemalloc::snapshot();
+        let elements = traces
+            .into_iter()
+            .map(|trace| {
+                let (filename, lineno) = site_fields(trace.site, vm);
+                vm.ctx
+                    .new_tuple(vec![vm.ctx.new_int(trace.size).into(), filename, lineno])
+                    .into()
+            })
+            .collect();
+        vm.ctx.new_list(elements)
+    }
+
+    /// Live allocations grouped by the line that made them, as
+    /// `(filename, lineno, size, count)` tuples sorted by size, largest first.
+    #[pyfunction]
+    fn statistics(vm: &VirtualMachine) -> PyListRef {
+        let stats = crate::tracemalloc::statistics();
+        let elements = stats
+            .into_iter()
+            .map(|(site, size, count)| {
+                let (filename, lineno) = site_fields(site, vm);
+                vm.ctx
+                    .new_tuple(vec![
+                        filename,
+                        lineno,
+                        vm.ctx.new_int(size).into(),
+                        vm.ctx.new_int(count).into(),
+                    ])
+                    .into()
+            })
+            .collect();
+        vm.ctx.new_list(elements)
+    }
+}
@@ -10,7 +10,10 @@ pub(crate) use _weakref::make_module;
 mod _weakref {
     use crate::{
         builtins::{PyDictRef, PyTypeRef, PyWeak},
-        PyObjectRef, PyResult, VirtualMachine,
+        common::lock::PyMutex,
+        function::FuncArgs,
+        types::{Callable, Constructor, Representable},
+        AsObject, Py, PyObjectRef, PyPayload, PyRef, PyResult, VirtualMachine,
     };
 
     #[pyattr(name = "ref")]
@@ -47,6 +50,178 @@ mod _weakref {
         }
     }
 
+    /// The native half of `weakref.finalize`. The finalizer object doubles as
+    /// the callback of the weakref it holds, so it also fires from the
+    /// weakref-clearing phase when its referent dies as part of a collected
+    /// cycle; the one-shot `info` slot guarantees `func` runs at most once,
+    /// whether triggered by the callback, a direct call, or atexit.
+    #[pyattr]
+    #[pyclass(name = "finalize")]
+    #[derive(Debug, PyPayload)]
+    struct PyFinalize {
+        info: PyMutex<Option<FinalizeInfo>>,
+    }
+
+    #[derive(Debug)]
+    struct FinalizeInfo {
+        weakref: PyRef<PyWeak>,
+        func: PyObjectRef,
+        args: FuncArgs,
+        atexit: bool,
+    }
+
+    #[pyclass(with(Callable, Constructor, Representable))]
+    impl PyFinalize {
+        /// If alive, mark as dead and return `(obj, func, args, kwargs)`
+        /// without calling `func`; otherwise return `None`.
+        #[pymethod]
+        fn detach(&self, vm: &VirtualMachine) -> PyResult {
+            let mut guard = self.info.lock();
+            let obj = match guard.as_ref().and_then(|info| info.weakref.upgrade()) {
+                Some(obj) => obj,
+                None => return Ok(vm.ctx.none()),
+            };
+            let info = guard.take().unwrap();
+            drop(guard);
+            Self::info_tuple(obj, &info, vm)
+        }
+
+        /// If alive, return `(obj, func, args, kwargs)`; otherwise `None`.
+        #[pymethod]
+        fn peek(&self, vm: &VirtualMachine) -> PyResult {
+            let guard = self.info.lock();
+            match guard
+                .as_ref()
+                .and_then(|info| Some((info, info.weakref.upgrade()?)))
+            {
+                Some((info, obj)) => Self::info_tuple(obj, info, vm),
+                None => Ok(vm.ctx.none()),
+            }
+        }
+
+        #[pygetset]
+        fn alive(&self) -> bool {
+            self.info.lock().is_some()
+        }
+
+        #[pygetset]
+        fn atexit(&self) -> bool {
+            self.info.lock().as_ref().map_or(false, |info| info.atexit)
+        }
+
+        #[pygetset(setter)]
+        fn set_atexit(&self, value: bool) {
+            if let Some(info) = self.info.lock().as_mut() {
+                info.atexit = value;
+            }
+        }
+
+        /// Run the finalizer if it is still pending and has `atexit` set;
+        /// registered with `atexit` by the constructor.
+        #[pymethod]
+        fn _at_exit(&self, vm: &VirtualMachine) -> PyResult<()> {
+            let info = {
+                let mut guard = self.info.lock();
+                if guard.as_ref().map_or(false, |info| info.atexit) {
+                    guard.take()
+                } else {
+                    None
+                }
+            };
+            if let Some(info) = info {
+                info.func.call(info.args, vm)?;
+            }
+            Ok(())
+        }
+
+        fn info_tuple(obj: PyObjectRef, info: &FinalizeInfo, vm: &VirtualMachine) -> PyResult {
+            let kwargs = vm.ctx.new_dict();
+            for (name, value) in &info.args.kwargs {
+                kwargs.set_item(name.as_ref(), value.clone(), vm)?;
+            }
+            Ok(vm
+                .ctx
+                .new_tuple(vec![
+                    obj,
+                    info.func.clone(),
+                    vm.ctx.new_tuple(info.args.args.to_vec()).into(),
+                    kwargs.into(),
+                ])
+                .into())
+        }
+    }
+
+    impl Constructor for PyFinalize {
+        type Args = FuncArgs;
+
+        fn py_new(cls: PyTypeRef, mut args: Self::Args, vm: &VirtualMachine) -> PyResult {
+            if args.args.len() < 2 {
+                return Err(vm.new_type_error(format!(
+                    "finalize expected at least 2 positional arguments, got {}",
+                    args.args.len()
+                )));
+            }
+            let obj = args.args.remove(0);
+            let func = args.args.remove(0);
+            let zelf = PyFinalize {
+                info: PyMutex::new(None),
+            }
+            .into_ref_with_type(vm, cls)?;
+            // the finalizer itself is the weakref callback, like the
+            // pure-python implementation; it receives the dead weakref as its
+            // single (ignored) argument
+            let weakref = obj.downgrade(Some(zelf.as_object().to_owned()), vm)?;
+            *zelf.info.lock() = Some(FinalizeInfo {
+                weakref,
+                func,
+                args,
+                atexit: true,
+            });
+            // leftover finalizers with atexit set still run at interpreter
+            // exit, newest first, through the regular atexit machinery
+            let run = zelf.as_object().get_attr("_at_exit", vm)?;
+            vm.state
+                .atexit_funcs
+                .lock()
+                .push((run, FuncArgs::default()));
+            Ok(zelf.into())
+        }
+    }
+
+    impl Callable for PyFinalize {
+        type Args = FuncArgs;
+
+        // the ignored argument is the dead weakref, when invoked as its
+        // callback
+        fn call(zelf: &Py<Self>, _args: FuncArgs, vm: &VirtualMachine) -> PyResult {
+            let info = zelf.info.lock().take();
+            match info {
+                Some(info) => info.func.call(info.args, vm),
+                None => Ok(vm.ctx.none()),
+            }
+        }
+    }
+
+    impl Representable for PyFinalize {
+        #[inline]
+        fn repr_str(zelf: &Py<Self>, _vm: &VirtualMachine) -> PyResult<String> {
+            let obj = zelf
+                .info
+                .lock()
+                .as_ref()
+                .and_then(|info| info.weakref.upgrade());
+            Ok(match obj {
+                Some(obj) => format!(
+                    "<finalize object at {:#x}; for '{}' at {:#x}>",
+                    zelf.get_id(),
+                    obj.class().name(),
+                    obj.get_id()
+                ),
+                None => format!("<finalize object at {:#x}; dead>", zelf.get_id()),
+            })
+        }
+    }
+
     #[pyfunction]
     fn _remove_dead_weakref(
         dict: PyDictRef,
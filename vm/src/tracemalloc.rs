@@ -0,0 +1,153 @@
+//! Runtime side of the `tracemalloc` module: once tracing is started, every
+//! object allocation records its size and the Python source line it happened
+//! at, and every deallocation retires that record, so live memory can be
+//! snapshotted and grouped by the line that allocated it.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// Where an allocation happened, if a frame was running at the time.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) struct AllocSite {
+    pub(crate) filename: String,
+    pub(crate) lineno: usize,
+}
+
+/// One live allocation.
+#[derive(Clone)]
+pub(crate) struct AllocTrace {
+    pub(crate) size: usize,
+    pub(crate) site: Option<AllocSite>,
+}
+
+#[derive(Default)]
+struct Traces {
+    live: HashMap<usize, AllocTrace>,
+    /// total size of the live traced allocations
+    traced: usize,
+    /// high-water mark of `traced` since tracing started
+    peak: usize,
+}
+
+static TRACING: AtomicBool = AtomicBool::new(false);
+// a std mutex rather than a PyMutex: the latter is only Sync with the
+// threading feature on, and the hooks are cold paths anyway
+static TRACES: Lazy<Mutex<Traces>> = Lazy::new(Default::default);
+
+fn lock_traces() -> MutexGuard<'static, Traces> {
+    // the table stays consistent even if a holder panicked mid-update, so
+    // recover from poisoning rather than aborting inside a dealloc hook
+    TRACES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Record one object allocation at its final address. A no-op unless
+/// `tracemalloc.start()` ran, costing a single relaxed load on the fast path.
+#[inline]
+pub(crate) fn trace_alloc(ptr: *const (), size: usize) {
+    if TRACING.load(Ordering::Relaxed) {
+        record_alloc(ptr as usize, size);
+    }
+}
+
+/// Retire the record for the allocation at `ptr`, if there is one.
+#[inline]
+pub(crate) fn trace_dealloc(ptr: *const ()) {
+    if TRACING.load(Ordering::Relaxed) {
+        record_dealloc(ptr as usize);
+    }
+}
+
+#[cold]
+fn record_alloc(ptr: usize, size: usize) {
+    // read the location before taking the lock; nothing below may allocate a
+    // python object, or the hook would re-enter itself
+    let site = current_site();
+    let mut traces = lock_traces();
+    traces.traced += size;
+    traces.peak = traces.peak.max(traces.traced);
+    traces.live.insert(ptr, AllocTrace { size, site });
+}
+
+#[cold]
+fn record_dealloc(ptr: usize) {
+    let mut traces = lock_traces();
+    // allocations from before tracing started aren't in the table
+    if let Some(trace) = traces.live.remove(&ptr) {
+        traces.traced -= trace.size;
+    }
+}
+
+/// The source line the innermost frame is currently at, if a vm is entered on
+/// this thread and the frame already executed an instruction.
+fn current_site() -> Option<AllocSite> {
+    crate::vm::thread::try_with_current_vm(|vm| {
+        let frames = vm.frames.try_borrow().ok()?;
+        let frame = frames.last()?;
+        if frame.lasti() == 0 {
+            return None;
+        }
+        Some(AllocSite {
+            filename: frame.code.source_path.as_str().to_owned(),
+            lineno: frame.current_location().row(),
+        })
+    })
+    .flatten()
+}
+
+pub(crate) fn start() {
+    clear_traces();
+    TRACING.store(true, Ordering::Relaxed);
+}
+
+pub(crate) fn stop() {
+    TRACING.store(false, Ordering::Relaxed);
+    clear_traces();
+}
+
+pub(crate) fn is_tracing() -> bool {
+    TRACING.load(Ordering::Relaxed)
+}
+
+pub(crate) fn clear_traces() {
+    let mut traces = lock_traces();
+    traces.live.clear();
+    traces.traced = 0;
+    traces.peak = 0;
+}
+
+/// `(current, peak)` traced sizes, backing `tracemalloc.get_traced_memory`.
+pub(crate) fn traced_memory() -> (usize, usize) {
+    let traces = lock_traces();
+    (traces.traced, traces.peak)
+}
+
+/// A copy of every live trace, taken in one go under the lock so the python
+/// objects the caller builds from it don't show up in their own snapshot.
+pub(crate) fn snapshot() -> Vec<AllocTrace> {
+    lock_traces().live.values().cloned().collect()
+}
+
+/// Live traces grouped by allocation site as `(site, total size, count)`,
+/// largest first; allocations made while no frame was running group under
+/// `None`.
+pub(crate) fn statistics() -> Vec<(Option<AllocSite>, usize, usize)> {
+    let mut grouped: HashMap<Option<AllocSite>, (usize, usize)> = HashMap::new();
+    {
+        let traces = lock_traces();
+        for trace in traces.live.values() {
+            let entry = grouped.entry(trace.site.clone()).or_default();
+            entry.0 += trace.size;
+            entry.1 += 1;
+        }
+    }
+    let mut stats: Vec<_> = grouped
+        .into_iter()
+        .map(|(site, (size, count))| (site, size, count))
+        .collect();
+    stats.sort_by(|a, b| b.1.cmp(&a.1));
+    stats
+}
@@ -1,6 +1,7 @@
 use crate::{
     builtins::{
-        type_::PointerSlot, PyFloat, PyInt, PyStr, PyStrInterned, PyStrRef, PyType, PyTypeRef,
+        type_::PointerSlot, PyFloat, PyInt, PyStr, PyStrInterned, PyStrRef, PyTuple, PyType,
+        PyTypeRef,
     },
     bytecode::ComparisonOperator,
     common::hash::PyHash,
@@ -8,8 +9,8 @@ use crate::{
     function::{Either, FromArgs, FuncArgs, OptionalArg, PyComparisonValue, PySetterValue},
     identifier,
     protocol::{
-        PyBuffer, PyIterReturn, PyMapping, PyMappingMethods, PyNumber, PyNumberMethods,
-        PyNumberSlots, PySequence, PySequenceMethods,
+        BufferFlags, PyBuffer, PyIterReturn, PyMapping, PyMappingMethods, PyNumber,
+        PyNumberMethods, PyNumberSlots, PySequence, PySequenceMethods,
     },
     vm::Context,
     AsObject, Py, PyObject, PyObjectRef, PyPayload, PyRef, PyResult, VirtualMachine,
@@ -48,6 +49,11 @@ pub struct PyTypeSlots {
     // More standard operations (here for binary compatibility)
     pub hash: AtomicCell<Option<HashFunc>>,
     pub call: AtomicCell<Option<GenericMethod>>,
+    /// Fast calling convention: arguments are borrowed from the caller's
+    /// value stack instead of boxed into a `FuncArgs`. Looked up on the
+    /// exact type only, never through the MRO, so a `__call__` override on
+    /// a subclass cannot be bypassed; see `PyObject::vectorcall`.
+    pub vectorcall: AtomicCell<Option<VectorcallFunc>>,
     // tp_str
     pub repr: AtomicCell<Option<StringifyFunc>>,
     pub getattro: AtomicCell<Option<GetattroFunc>>,
@@ -84,6 +90,13 @@ pub struct PyTypeSlots {
     // tp_bases
     // tp_mro
     // tp_cache
+    // tp_version_tag
+    /// Lazily-assigned, globally unique tag retired whenever the attributes
+    /// of this type (or of a base) change; guards attribute lookup caches.
+    /// 0 means "no tag assigned". See [`PyType::version_tag`].
+    ///
+    /// [`PyType::version_tag`]: crate::builtins::PyType::version_tag
+    pub version_tag: AtomicCell<u32>,
     // tp_subclasses
     // tp_weaklist
     pub del: AtomicCell<Option<DelFunc>>,
@@ -155,13 +168,15 @@ impl Default for PyTypeFlags {
 }
 
 pub(crate) type GenericMethod = fn(&PyObject, FuncArgs, &VirtualMachine) -> PyResult;
+pub(crate) type VectorcallFunc =
+    fn(&PyObject, &[PyObjectRef], Option<&Py<PyTuple>>, &VirtualMachine) -> PyResult;
 pub(crate) type HashFunc = fn(&PyObject, &VirtualMachine) -> PyResult<PyHash>;
 // CallFunc = GenericMethod
 pub(crate) type StringifyFunc = fn(&PyObject, &VirtualMachine) -> PyResult<PyStrRef>;
 pub(crate) type GetattroFunc = fn(&PyObject, &Py<PyStr>, &VirtualMachine) -> PyResult;
 pub(crate) type SetattroFunc =
     fn(&PyObject, &Py<PyStr>, PySetterValue, &VirtualMachine) -> PyResult<()>;
-pub(crate) type AsBufferFunc = fn(&PyObject, &VirtualMachine) -> PyResult<PyBuffer>;
+pub(crate) type AsBufferFunc = fn(&PyObject, BufferFlags, &VirtualMachine) -> PyResult<PyBuffer>;
 pub(crate) type RichCompareFunc = fn(
     &PyObject,
     &PyObject,
@@ -1171,14 +1186,19 @@ pub trait SetAttr: PyPayload {
 
 #[pyclass]
 pub trait AsBuffer: PyPayload {
-    // TODO: `flags` parameter
     #[inline]
     #[pyslot]
-    fn slot_as_buffer(zelf: &PyObject, vm: &VirtualMachine) -> PyResult<PyBuffer> {
+    fn slot_as_buffer(
+        zelf: &PyObject,
+        flags: BufferFlags,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyBuffer> {
         let zelf = zelf
             .downcast_ref()
             .ok_or_else(|| vm.new_type_error("unexpected payload for as_buffer".to_owned()))?;
-        Self::as_buffer(zelf, vm)
+        let buffer = Self::as_buffer(zelf, vm)?;
+        buffer.desc.check_request(flags, vm)?;
+        Ok(buffer)
     }
 
     fn as_buffer(zelf: &Py<Self>, vm: &VirtualMachine) -> PyResult<PyBuffer>;
@@ -29,6 +29,7 @@ pub struct TypeZoo {
     pub callable_iterator: &'static Py<PyType>,
     pub cell_type: &'static Py<PyType>,
     pub classmethod_type: &'static Py<PyType>,
+    pub classmethod_descriptor_type: &'static Py<PyType>,
     pub code_type: &'static Py<PyType>,
     pub coroutine_type: &'static Py<PyType>,
     pub coroutine_wrapper_type: &'static Py<PyType>,
@@ -109,6 +110,7 @@ impl TypeZoo {
             bytearray_type: bytearray::PyByteArray::init_builtin_type(),
             bytes_type: bytes::PyBytes::init_builtin_type(),
             classmethod_type: classmethod::PyClassMethod::init_builtin_type(),
+            classmethod_descriptor_type: builtin_func::PyBuiltinClassMethod::init_builtin_type(),
             complex_type: complex::PyComplex::init_builtin_type(),
             dict_type: dict::PyDict::init_builtin_type(),
             enumerate_type: enumerate::PyEnumerate::init_builtin_type(),
@@ -39,10 +39,17 @@ pub struct Context {
     pub empty_bytes: PyRef<PyBytes>,
     pub ellipsis: PyRef<PyEllipsis>,
     pub not_implemented: PyRef<PyNotImplemented>,
+    /// Stands in for the `self` slot pushed by `LoadMethod` when the lookup
+    /// did not produce an unbound method (CPython pushes NULL there);
+    /// compared by identity in `CallMethod*` and never reachable from Python.
+    pub(crate) no_self_sentinel: PyObjectRef,
 
     pub types: TypeZoo,
     pub exceptions: exceptions::ExceptionZoo,
     pub int_cache_pool: Vec<PyIntRef>,
+    // shared single-character strings for the ascii range, like CPython's
+    // latin-1 character cache
+    pub ascii_char_cache: Vec<PyRef<PyStr>>,
     // there should only be exact objects of str in here, no non-str objects and no subclasses
     pub(crate) string_pool: StringPool,
     pub(crate) slot_new_wrapper: PyRef<PyBuiltinFunction>,
@@ -230,12 +237,21 @@ declare_const_name! {
     copy,
     flush,
     close,
+    derive,
+    split,
+    subgroup,
 }
 
 // Basic objects:
 impl Context {
     pub const INT_CACHE_POOL_MIN: i32 = -5;
     pub const INT_CACHE_POOL_MAX: i32 = 256;
+    /// the inclusive range of small ints kept preallocated in
+    /// [`Context::int_cache_pool`]; adjust the bounds above to resize it
+    pub const INT_CACHE_POOL_RANGE: std::ops::RangeInclusive<i32> =
+        Self::INT_CACHE_POOL_MIN..=Self::INT_CACHE_POOL_MAX;
+
+    const ASCII_CHAR_CACHE_LEN: u32 = 128;
 
     pub fn genesis() -> &'static PyRc<Self> {
         rustpython_common::static_cell! {
@@ -260,8 +276,9 @@ impl Context {
         let none = create_object(PyNone, PyNone::static_type());
         let ellipsis = create_object(PyEllipsis, PyEllipsis::static_type());
         let not_implemented = create_object(PyNotImplemented, PyNotImplemented::static_type());
+        let no_self_sentinel = create_object(object::PyBaseObject, types.object_type).into();
 
-        let int_cache_pool = (Self::INT_CACHE_POOL_MIN..=Self::INT_CACHE_POOL_MAX)
+        let int_cache_pool = Self::INT_CACHE_POOL_RANGE
             .map(|v| {
                 PyRef::new_ref(
                     PyInt::from(BigInt::from(v)),
@@ -271,6 +288,17 @@ impl Context {
             })
             .collect();
 
+        let ascii_char_cache = (0..Self::ASCII_CHAR_CACHE_LEN)
+            .map(|c| {
+                let c = char::from_u32(c).unwrap();
+                PyRef::new_ref(
+                    pystr::PyStr::from(c.to_string()),
+                    types.str_type.to_owned(),
+                    None,
+                )
+            })
+            .collect();
+
         let true_value = create_object(PyInt::from(1), types.bool_type);
         let false_value = create_object(PyInt::from(0), types.bool_type);
 
@@ -305,10 +333,12 @@ impl Context {
 
             ellipsis,
             not_implemented,
+            no_self_sentinel,
 
             types,
             exceptions,
             int_cache_pool,
+            ascii_char_cache,
             string_pool,
             slot_new_wrapper,
             names,
@@ -322,6 +352,13 @@ impl Context {
         unsafe { self.string_pool.intern(s, self.types.str_type.to_owned()) }
     }
 
+    /// Intern a string literal. This is the public entry point for embedders
+    /// who want to pre-intern attribute names they look up repeatedly.
+    #[inline]
+    pub fn intern_static_str(&self, s: &'static str) -> &'static PyStrInterned {
+        self.intern_str(s)
+    }
+
     pub fn interned_str<S: MaybeInternedString + ?Sized>(
         &self,
         s: &S,
@@ -358,7 +395,7 @@ impl Context {
     #[inline]
     pub fn new_int<T: Into<BigInt> + ToPrimitive>(&self, i: T) -> PyIntRef {
         if let Some(i) = i.to_i32() {
-            if (Self::INT_CACHE_POOL_MIN..=Self::INT_CACHE_POOL_MAX).contains(&i) {
+            if Self::INT_CACHE_POOL_RANGE.contains(&i) {
                 let inner_idx = (i - Self::INT_CACHE_POOL_MIN) as usize;
                 return self.int_cache_pool[inner_idx].clone();
             }
@@ -369,7 +406,7 @@ impl Context {
     #[inline]
     pub fn new_bigint(&self, i: &BigInt) -> PyIntRef {
         if let Some(i) = i.to_i32() {
-            if (Self::INT_CACHE_POOL_MIN..=Self::INT_CACHE_POOL_MAX).contains(&i) {
+            if Self::INT_CACHE_POOL_RANGE.contains(&i) {
                 let inner_idx = (i - Self::INT_CACHE_POOL_MIN) as usize;
                 return self.int_cache_pool[inner_idx].clone();
             }
@@ -396,6 +433,17 @@ impl Context {
         pystr::PyStr::new_ref(s, self)
     }
 
+    /// a single-character string, served from [`Context::ascii_char_cache`]
+    /// when the character is ascii
+    #[inline]
+    pub fn new_char(&self, c: char) -> PyRef<PyStr> {
+        if (c as u32) < Self::ASCII_CHAR_CACHE_LEN {
+            self.ascii_char_cache[c as usize].clone()
+        } else {
+            self.new_str(c.to_string())
+        }
+    }
+
     pub fn interned_or_new_str<S, M>(&self, s: S) -> PyRef<PyStr>
     where
         S: Into<PyStr> + AsRef<M>,
@@ -437,6 +485,12 @@ impl Context {
         PyDict::new_ref(self)
     }
 
+    /// a dict presized for `capacity` entries, for builders that know how
+    /// many they are about to insert
+    pub fn new_dict_with_capacity(&self, capacity: usize) -> PyDictRef {
+        PyDict::new_ref_with_capacity(self, capacity)
+    }
+
     pub fn new_class(
         &self,
         module: Option<&str>,
@@ -593,7 +647,7 @@ impl Context {
 
     pub fn new_code(&self, code: impl code::IntoCodeObject) -> PyRef<PyCode> {
         let code = code.into_code_object(self);
-        PyRef::new_ref(PyCode { code }, self.types.code_type.to_owned(), None)
+        PyRef::new_ref(PyCode::new(code), self.types.code_type.to_owned(), None)
     }
 }
 
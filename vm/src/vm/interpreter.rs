@@ -1,10 +1,36 @@
 use super::{setting::Settings, thread, Context, VirtualMachine};
+#[cfg(feature = "rustpython-compiler")]
+use crate::AsObject;
 use crate::{
     stdlib::{atexit, sys},
     PyResult,
 };
 use std::sync::atomic::Ordering;
 
+/// Why [`Interpreter::run_expr`] or [`Interpreter::run_script_capture`] failed.
+#[cfg(feature = "rustpython-compiler")]
+#[derive(Debug)]
+pub enum EvalError {
+    /// the source couldn't be compiled
+    Compile(crate::compiler::CompileError),
+    /// running the code (or converting its result) raised an exception,
+    /// rendered the way the interpreter would print it
+    Exception(String),
+}
+
+#[cfg(feature = "rustpython-compiler")]
+impl std::fmt::Display for EvalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvalError::Compile(e) => e.fmt(f),
+            EvalError::Exception(msg) => f.write_str(msg),
+        }
+    }
+}
+
+#[cfg(feature = "rustpython-compiler")]
+impl std::error::Error for EvalError {}
+
 /// The general interface for the VM
 ///
 /// # Examples
@@ -59,31 +85,156 @@ impl Interpreter {
         thread::enter_vm(&self.vm, || f(&self.vm))
     }
 
+    /// Compile and evaluate `src` as a single expression in a fresh scope and
+    /// convert its value into `T`, taking care of entering the vm and
+    /// rendering any exception, so embedders don't have to repeat the
+    /// `enter()`/compile/`run_code_obj` boilerplate.
+    ///
+    /// ```
+    /// use rustpython_vm::Interpreter;
+    /// let val: i64 = Interpreter::without_stdlib(Default::default())
+    ///     .run_expr("21 * 2")
+    ///     .unwrap();
+    /// assert_eq!(val, 42);
+    /// ```
+    #[cfg(feature = "rustpython-compiler")]
+    pub fn run_expr<T: crate::TryFromObject>(&self, src: &str) -> Result<T, EvalError> {
+        self.enter(|vm| {
+            let scope = vm.new_scope_with_builtins();
+            let code_obj = vm
+                .compile(src, crate::compiler::Mode::Eval, "<run_expr>".to_owned())
+                .map_err(EvalError::Compile)?;
+            vm.run_code_obj(code_obj, scope)
+                .and_then(|value| T::try_from_object(vm, value))
+                .map_err(|exc| EvalError::Exception(render_exception(vm, &exc)))
+        })
+    }
+
+    /// Run `src` as a script in a fresh scope, with `sys.stdout` and
+    /// `sys.stderr` temporarily swapped for in-memory `io.StringIO` buffers.
+    /// Returns the exit status the script would have produced (0 on success,
+    /// honoring `SystemExit`) together with everything written to stdout and
+    /// stderr; uncaught exceptions are rendered into the captured stderr.
+    #[cfg(feature = "rustpython-compiler")]
+    pub fn run_script_capture(&self, src: &str) -> Result<(u8, String, String), EvalError> {
+        self.enter(|vm| {
+            let as_eval_error = |exc| EvalError::Exception(render_exception(vm, &exc));
+            let scope = vm.new_scope_with_builtins();
+            let code_obj = vm
+                .compile(src, crate::compiler::Mode::Exec, "<run_script>".to_owned())
+                .map_err(EvalError::Compile)?;
+
+            let string_io = vm
+                .import("io", None, 0)
+                .and_then(|io| io.get_attr("StringIO", vm))
+                .map_err(as_eval_error)?;
+            let stdout = string_io.call((), vm).map_err(as_eval_error)?;
+            let stderr = string_io.call((), vm).map_err(as_eval_error)?;
+            let old_stdout = vm.sys_module.get_attr("stdout", vm).ok();
+            let old_stderr = vm.sys_module.get_attr("stderr", vm).ok();
+            vm.sys_module
+                .set_attr("stdout", stdout.clone(), vm)
+                .map_err(as_eval_error)?;
+            vm.sys_module
+                .set_attr("stderr", stderr.clone(), vm)
+                .map_err(as_eval_error)?;
+
+            let result = vm.run_code_obj(code_obj, scope);
+            flush_std(vm);
+            let status = match result {
+                Ok(_) => 0,
+                Err(exc) => {
+                    // render the exception into the captured stderr, the way
+                    // the interpreter would print it to the real one
+                    let mut rendered = String::new();
+                    let _ = vm.write_exception(&mut rendered, &exc);
+                    let _ = vm.call_method(&stderr, "write", (rendered,));
+                    if exc.fast_isinstance(vm.ctx.exceptions.system_exit) {
+                        vm.handle_exit_exception(exc)
+                    } else {
+                        1
+                    }
+                }
+            };
+
+            // restore the real streams before reading the buffers back
+            if let Some(old) = old_stdout {
+                let _ = vm.sys_module.set_attr("stdout", old, vm);
+            }
+            if let Some(old) = old_stderr {
+                let _ = vm.sys_module.set_attr("stderr", old, vm);
+            }
+            let getvalue = |buf: &crate::PyObjectRef| -> PyResult<String> {
+                let value = vm.call_method(buf, "getvalue", ())?;
+                value.try_into_value(vm)
+            };
+            let out = getvalue(&stdout).map_err(as_eval_error)?;
+            let err = getvalue(&stderr).map_err(as_eval_error)?;
+            Ok((status, out, err))
+        })
+    }
+
     pub fn run<F, R>(self, f: F) -> u8
     where
         F: FnOnce(&VirtualMachine) -> PyResult<R>,
     {
-        self.enter(|vm| {
+        let exit_code = self.enter(|vm| {
             let res = f(vm);
             flush_std(vm);
 
             // See if any exception leaked out:
-            let exit_code = res
-                .map(|_| 0)
+            res.map(|_| 0)
                 .map_err(|exc| vm.handle_exit_exception(exc))
-                .unwrap_or_else(|code| code);
+                .unwrap_or_else(|code| code)
+        });
+        self.finalize(exit_code)
+    }
 
+    /// Shut the interpreter down in a deterministic order: run the `atexit`
+    /// callbacks, flush the std streams, do a final garbage collection so
+    /// finalizers on cycle members still run, release the module registry so
+    /// `__del__` on module globals runs too, and flush whatever those
+    /// finalizers printed. Consumes the interpreter and returns `exit_code`
+    /// unchanged, so it can be passed straight to `std::process::exit`.
+    pub fn finalize(self, exit_code: u8) -> u8 {
+        self.enter(|vm| {
             atexit::_run_exitfuncs(vm);
 
             vm.state.finalizing.store(true, Ordering::Release);
 
             flush_std(vm);
 
-            exit_code
-        })
+            // one last cycle collection while the vm is still fully alive, so
+            // `__del__` on cycle members can run python code
+            #[cfg(feature = "gc_bacon")]
+            crate::object::gc::collect();
+
+            // dropping the module registry is what actually releases module
+            // globals; reference counting then runs their `__del__` right away
+            if let Ok(modules) = vm.sys_module.get_attr("modules", vm) {
+                if let Some(dict) = modules.downcast_ref::<crate::builtins::PyDict>() {
+                    dict.clear();
+                }
+            }
+
+            flush_std(vm);
+            debug!(
+                "{} objects still allocated after interpreter finalization",
+                crate::object::allocated_blocks()
+            );
+        });
+        exit_code
     }
 }
 
+#[cfg(feature = "rustpython-compiler")]
+fn render_exception(vm: &VirtualMachine, exc: &crate::builtins::PyBaseExceptionRef) -> String {
+    let mut msg = String::new();
+    // writing into a String cannot fail
+    let _ = vm.write_exception(&mut msg, exc);
+    msg
+}
+
 pub(crate) fn flush_std(vm: &VirtualMachine) {
     if let Ok(stdout) = sys::get_stdout(vm) {
         let _ = vm.call_method(&stdout, identifier!(vm, flush).as_str(), ());
@@ -113,6 +264,16 @@ mod tests {
         })
     }
 
+    #[test]
+    #[cfg(feature = "rustpython-compiler")]
+    fn test_run_expr() {
+        let interp = Interpreter::without_stdlib(Default::default());
+        let val: i32 = interp.run_expr("6 * 7").unwrap();
+        assert_eq!(val, 42);
+        assert!(interp.run_expr::<i32>("1 +").is_err());
+        assert!(interp.run_expr::<i32>("1 / 0").is_err());
+    }
+
     #[test]
     fn test_multiply_str() {
         Interpreter::without_stdlib(Default::default()).enter(|vm| {
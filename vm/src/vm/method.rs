@@ -28,9 +28,24 @@ impl PyMethod {
 
         // any correct method name is always interned already.
         let interned_name = vm.ctx.interned_str(name);
+        let cls_attr = interned_name.and_then(|name| cls.get_attr(name));
+        Self::get_resolved(obj, name, cls_attr, vm)
+    }
+
+    /// The descriptor/instance-dict half of [`Self::get`], with the MRO lookup
+    /// already done; `cls_attr` must be the result of
+    /// `obj.class().get_attr(name)` (possibly served from an inline cache) and
+    /// `obj` must use the generic `__getattribute__`
+    pub(crate) fn get_resolved(
+        obj: PyObjectRef,
+        name: &Py<PyStr>,
+        cls_attr: Option<PyObjectRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult<Self> {
+        let cls = obj.class();
         let mut is_method = false;
 
-        let cls_attr = match interned_name.and_then(|name| cls.get_attr(name)) {
+        let cls_attr = match cls_attr {
             Some(descr) => {
                 let descr_cls = descr.class();
                 let descr_get = if descr_cls.slots.flags.has_feature(PyTypeFlags::METHOD_DESCR) {
@@ -90,13 +105,23 @@ impl PyMethod {
         name: &'static PyStrInterned,
         vm: &VirtualMachine,
     ) -> PyResult<Option<Self>> {
-        let obj_cls = obj.class();
-        let func = match obj_cls.get_attr(name) {
+        let func = match obj.class().get_attr(name) {
             Some(f) => f,
             None => {
                 return Ok(None);
             }
         };
+        Self::get_special_resolved(obj, func, vm).map(Some)
+    }
+
+    /// The binding half of [`Self::get_special`], with the MRO lookup already
+    /// done; `func` must be the result of `obj.class().get_attr(name)`
+    /// (possibly served from an inline cache)
+    pub(crate) fn get_special_resolved(
+        obj: &PyObject,
+        func: PyObjectRef,
+        vm: &VirtualMachine,
+    ) -> PyResult<Self> {
         let meth = if func
             .class()
             .slots
@@ -108,13 +133,13 @@ impl PyMethod {
                 func,
             }
         } else {
-            let obj_cls = obj_cls.to_owned().into();
+            let obj_cls = obj.class().to_owned().into();
             let attr = vm
                 .call_get_descriptor_specific(&func, Some(obj.to_owned()), Some(obj_cls))
                 .unwrap_or(Ok(func))?;
             Self::Attribute(attr)
         };
-        Ok(Some(meth))
+        Ok(meth)
     }
 
     pub fn invoke(self, args: impl IntoFuncArgs, vm: &VirtualMachine) -> PyResult {
@@ -26,7 +26,7 @@ use crate::{
     codecs::CodecsRegistry,
     common::{hash::HashSecret, lock::PyMutex, rc::PyRc},
     convert::ToPyObject,
-    frame::{ExecutionResult, Frame, FrameRef},
+    frame::{ExecutionResult, Frame, FrameRef, StackPool},
     frozen,
     function::{ArgMapping, FuncArgs, PySetterValue},
     import,
@@ -42,7 +42,7 @@ use nix::{
     sys::signal::{kill, sigaction, SaFlags, SigAction, SigSet, Signal::SIGINT},
     unistd::getpid,
 };
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicU8};
 use std::{
     borrow::Cow,
     cell::{Cell, Ref, RefCell},
@@ -50,6 +50,8 @@ use std::{
 };
 
 pub use context::Context;
+#[cfg(feature = "rustpython-compiler")]
+pub use interpreter::EvalError;
 pub use interpreter::Interpreter;
 pub(crate) use method::PyMethod;
 pub use setting::Settings;
@@ -72,12 +74,23 @@ pub struct VirtualMachine {
     pub trace_func: RefCell<PyObjectRef>,
     pub use_tracing: Cell<bool>,
     pub recursion_limit: Cell<usize>,
+    /// instructions left before `run_code_with_budget` gives up, or `None`
+    /// when execution is not budgeted
+    instruction_budget: Cell<Option<u64>>,
+    /// set by [`VirtualMachine::request_frame_suspension`] and consumed at
+    /// the next frame safepoint
+    suspend_requested: Cell<bool>,
+    /// value-stack buffers reused between the frames this vm creates
+    pub(crate) stack_pool: PyRc<StackPool>,
     pub(crate) signal_handlers: Option<Box<RefCell<[Option<PyObjectRef>; signal::NSIG]>>>,
     pub(crate) signal_rx: Option<signal::UserSignalReceiver>,
     pub repr_guards: RefCell<HashSet<usize>>,
     pub state: PyRc<PyGlobalState>,
     pub initialized: bool,
     recursion_depth: Cell<usize>,
+    /// whether a RecursionError is currently being handled: its handlers may
+    /// recurse a little past the limit, but not past the extra headroom
+    recursion_headroom: Cell<bool>,
 }
 
 #[derive(Debug, Default)]
@@ -94,12 +107,75 @@ pub struct PyGlobalState {
     pub thread_count: AtomicCell<usize>,
     pub hash_secret: HashSecret,
     pub atexit_funcs: PyMutex<Vec<(PyObjectRef, FuncArgs)>>,
+    /// the per-interpreter half of the eval breaker: bits for out-of-band
+    /// work queued on this interpreter specifically, so one interpreter
+    /// draining its own queues can't clear flags another interpreter set
+    pub(crate) eval_breaker: AtomicU8,
+    /// exceptions scheduled by [`VirtualMachine::set_async_exc`], keyed by the
+    /// id of the thread they are to be raised in
+    #[cfg(feature = "threading")]
+    pub(crate) async_excs: PyMutex<Vec<(u64, PyBaseExceptionRef)>>,
+    /// callbacks scheduled by [`VirtualMachine::add_pending_call`], waiting
+    /// for an interpreter thread to reach a safepoint
+    pub(crate) pending_calls: PyMutex<Vec<signal::PendingCall>>,
     pub codec_registry: CodecsRegistry,
     pub finalizing: AtomicBool,
     pub warnings: WarningsState,
     pub override_frozen_modules: AtomicCell<isize>,
 }
 
+/// How far [`VirtualMachine::run_frame_resumable`] got: the code either ran
+/// to completion, or stopped at a safepoint with its call stack packed up.
+pub enum FrameExecution {
+    Finished(PyObjectRef),
+    Suspended(SuspendedFrame),
+}
+
+/// A paused Python call stack, detached from the vm that was running it.
+/// Everything the code was doing — each frame's value stack, block stack and
+/// next instruction — lives in the frame objects themselves, so the stack
+/// can be stored anywhere and [resumed](Self::resume) later, on the same OS
+/// thread or (with the `threading` feature) a different one. This is the
+/// building block for greenlet/fiber style schedulers on top of the
+/// interpreter.
+pub struct SuspendedFrame {
+    /// outermost caller first; the last frame is the one that suspended
+    frames: Vec<FrameRef>,
+}
+
+impl SuspendedFrame {
+    /// Pick the call stack back up on `vm` — the vm of whichever OS thread
+    /// the scheduler chose — and run it until it finishes or suspends again.
+    pub fn resume(self, vm: &VirtualMachine) -> PyResult<FrameExecution> {
+        let Self { mut frames } = self;
+        let current = frames.pop().expect("a suspended stack is never empty");
+        let callers = frames;
+        let base = callers.first().unwrap_or(&current).clone();
+        vm.with_frame(base, |_base| {
+            // every frame above the base one goes back on the vm's frame
+            // stack, mirroring the pops when the stack suspended
+            let mut pushed = 0;
+            let repush = (|| -> PyResult<()> {
+                for frame in callers.iter().chain(Some(&current)).skip(1) {
+                    vm.push_frame(frame.clone())?;
+                    pushed += 1;
+                }
+                Ok(())
+            })();
+            if let Err(exception) = repush {
+                // this vm's recursion limit is too low for the stack; put
+                // the frames down untouched and report that to the resumer
+                for _ in 0..pushed {
+                    vm.pop_frame();
+                }
+                return Err(exception);
+            }
+            let result = current.run(vm);
+            vm.drive_frame(callers, current, result)
+        })
+    }
+}
+
 pub fn process_hash_secret_seed() -> u32 {
     use once_cell::sync::OnceCell;
     static SEED: OnceCell<u32> = OnceCell::new();
@@ -160,6 +236,9 @@ impl VirtualMachine {
             trace_func,
             use_tracing: Cell::new(false),
             recursion_limit: Cell::new(if cfg!(debug_assertions) { 256 } else { 1000 }),
+            instruction_budget: Cell::new(None),
+            suspend_requested: Cell::new(false),
+            stack_pool: PyRc::new(StackPool::default()),
             signal_handlers,
             signal_rx: None,
             repr_guards: RefCell::default(),
@@ -171,6 +250,10 @@ impl VirtualMachine {
                 thread_count: AtomicCell::new(0),
                 hash_secret,
                 atexit_funcs: PyMutex::default(),
+                eval_breaker: AtomicU8::new(0),
+                #[cfg(feature = "threading")]
+                async_excs: PyMutex::default(),
+                pending_calls: PyMutex::default(),
                 codec_registry,
                 finalizing: AtomicBool::new(false),
                 warnings,
@@ -178,6 +261,7 @@ impl VirtualMachine {
             }),
             initialized: false,
             recursion_depth: Cell::new(0),
+            recursion_headroom: Cell::new(false),
         };
 
         if vm.state.hash_secret.hash_str("")
@@ -343,10 +427,51 @@ impl VirtualMachine {
     }
 
     pub fn run_code_obj(&self, code: PyRef<PyCode>, scope: Scope) -> PyResult {
-        let frame = Frame::new(code, scope, self.builtins.dict(), &[], self).into_ref(&self.ctx);
+        let frame = Frame::new(
+            code,
+            Some(scope.locals),
+            scope.globals,
+            self.builtins.dict(),
+            &[],
+            self,
+        )
+        .into_ref(&self.ctx);
         self.run_frame(frame)
     }
 
+    /// Run a code object like [`Self::run_code_obj`], but give up with a
+    /// `BudgetExceeded` exception once `max_instructions` bytecode
+    /// instructions have executed, so an untrusted snippet can't run forever.
+    /// The budget stays exhausted while the exception unwinds: handlers in
+    /// the snippet see it, but get no instructions to swallow it with.
+    pub fn run_code_with_budget(
+        &self,
+        code: PyRef<PyCode>,
+        scope: Scope,
+        max_instructions: u64,
+    ) -> PyResult {
+        let prev = self.instruction_budget.replace(Some(max_instructions));
+        let result = self.run_code_obj(code, scope);
+        self.instruction_budget.set(prev);
+        result
+    }
+
+    /// Charge one instruction against the active budget, if any; called by
+    /// the frame loop for every instruction it executes.
+    #[inline]
+    pub(crate) fn check_instruction_budget(&self) -> PyResult<()> {
+        match self.instruction_budget.get() {
+            None => Ok(()),
+            Some(0) => {
+                Err(self.new_budget_exceeded_error("instruction budget exhausted".to_owned()))
+            }
+            Some(n) => {
+                self.instruction_budget.set(Some(n - 1));
+                Ok(())
+            }
+        }
+    }
+
     #[cold]
     pub fn run_unraisable(&self, e: PyBaseExceptionRef, msg: Option<String>, object: PyObjectRef) {
         let sys_module = self.import("sys", None, 0).unwrap();
@@ -367,11 +492,124 @@ impl VirtualMachine {
         }
     }
 
-    #[inline(always)]
     pub fn run_frame(&self, frame: FrameRef) -> PyResult {
-        match self.with_frame(frame, |f| f.run(self))? {
-            ExecutionResult::Return(value) => Ok(value),
-            _ => panic!("Got unexpected result from function"),
+        match self.run_frame_resumable(frame)? {
+            FrameExecution::Finished(value) => Ok(value),
+            // the suspended stack is dropped: frames that were never entered
+            // resumably cannot be handed back to anyone
+            FrameExecution::Suspended(_) => Err(self.new_runtime_error(
+                "cannot suspend a frame that was not run through run_frame_resumable".to_owned(),
+            )),
+        }
+    }
+
+    /// Run a frame like [`Self::run_frame`], but when
+    /// [`Self::request_frame_suspension`] fires at a safepoint, hand the
+    /// paused call stack back instead of a value, to be
+    /// [resumed](SuspendedFrame::resume) later — possibly on another vm.
+    pub fn run_frame_resumable(&self, frame: FrameRef) -> PyResult<FrameExecution> {
+        self.with_frame(frame, |frame| {
+            let result = frame.run(self);
+            self.drive_frame(Vec::new(), frame, result)
+        })
+    }
+
+    /// The dispatch loop behind [`Self::run_frame_resumable`] and
+    /// [`SuspendedFrame::resume`]. `callers` are frames suspended in the
+    /// middle of a call instruction, outermost first, each waiting for the
+    /// frame pushed above it to finish. Keeping them here and returning to
+    /// this loop instead of nesting another `Frame::run` per call keeps Rust
+    /// stack usage flat, so call depth is bounded by the recursion limit
+    /// alone rather than by the native stack.
+    fn drive_frame(
+        &self,
+        mut callers: Vec<FrameRef>,
+        mut current: FrameRef,
+        mut result: PyResult<ExecutionResult>,
+    ) -> PyResult<FrameExecution> {
+        loop {
+            result = match result {
+                Ok(ExecutionResult::Call(callee)) => match self.push_frame(callee.clone()) {
+                    Ok(()) => {
+                        callers.push(std::mem::replace(&mut current, callee));
+                        current.run(self)
+                    }
+                    // the callee was never entered; the RecursionError
+                    // unwinds the caller like any error from the call
+                    Err(exception) => current.resume_call(Err(exception), self),
+                },
+                Ok(ExecutionResult::Suspended) => {
+                    // take the caller frames back off this vm's frame stack;
+                    // the base frame is popped by with_frame. The stack may
+                    // well resume on a different vm.
+                    for _ in &callers {
+                        self.pop_frame();
+                    }
+                    callers.push(current);
+                    return Ok(FrameExecution::Suspended(SuspendedFrame {
+                        frames: callers,
+                    }));
+                }
+                result => {
+                    let caller = match callers.pop() {
+                        Some(caller) => caller,
+                        None => {
+                            return match result? {
+                                ExecutionResult::Return(value) => {
+                                    Ok(FrameExecution::Finished(value))
+                                }
+                                _ => panic!("Got unexpected result from function"),
+                            }
+                        }
+                    };
+                    self.pop_frame();
+                    current = caller;
+                    match result {
+                        Ok(ExecutionResult::Return(value)) => current.resume_call(Ok(value), self),
+                        Err(exception) => current.resume_call(Err(exception), self),
+                        Ok(_) => unreachable!("stackless callees run to completion"),
+                    }
+                }
+            };
+        }
+    }
+
+    /// Ask the dispatch loop to stop at the next safepoint — between two
+    /// instructions of a non-generator frame — and hand the paused call
+    /// stack back from [`Self::run_frame_resumable`]. Meant to be called
+    /// from a host function invoked by the running code, e.g. the "yield to
+    /// scheduler" primitive of a green-thread library; code reached through
+    /// a plain [`Self::run_frame`] raises a RuntimeError instead of
+    /// suspending.
+    pub fn request_frame_suspension(&self) {
+        self.suspend_requested.set(true);
+    }
+
+    #[inline]
+    pub(crate) fn take_suspend_request(&self) -> bool {
+        let requested = self.suspend_requested.get();
+        if requested {
+            self.suspend_requested.set(false);
+        }
+        requested
+    }
+
+    /// Manual counterpart of [`Self::with_frame`] for the callee frames that
+    /// `run_frame` manages itself; every successful `push_frame` is paired
+    /// with a `pop_frame` once the frame finishes.
+    fn push_frame(&self, frame: FrameRef) -> PyResult<()> {
+        self.check_recursive_call("")?;
+        self.recursion_depth.set(self.recursion_depth.get() + 1);
+        self.frames.borrow_mut().push(frame);
+        Ok(())
+    }
+
+    fn pop_frame(&self) {
+        let _popped = self.frames.borrow_mut().pop();
+        let depth = self.recursion_depth.get() - 1;
+        self.recursion_depth.set(depth);
+        if depth < self.recursion_limit.get() {
+            self.recursion_headroom.set(false);
         }
     }
 
@@ -386,7 +624,13 @@ impl VirtualMachine {
         self.check_recursive_call(_where)?;
         self.recursion_depth.set(self.recursion_depth.get() + 1);
         let result = f();
-        self.recursion_depth.set(self.recursion_depth.get() - 1);
+        let depth = self.recursion_depth.get() - 1;
+        self.recursion_depth.set(depth);
+        if depth < self.recursion_limit.get() {
+            // back below the limit: a raised RecursionError (if any) has been
+            // handled or propagated out, so revoke the extra headroom
+            self.recursion_headroom.set(false);
+        }
         result
     }
 
@@ -413,9 +657,27 @@ impl VirtualMachine {
         }
     }
 
+    /// extra call depth granted past the recursion limit so that the handlers
+    /// of a RecursionError can run; mirrors CPython's recursion headroom
+    const RECURSION_HEADROOM: usize = 50;
+
     // To be called right before raising the recursion depth.
     fn check_recursive_call(&self, _where: &str) -> PyResult<()> {
-        if self.recursion_depth.get() >= self.recursion_limit.get() {
+        let depth = self.recursion_depth.get();
+        let limit = self.recursion_limit.get();
+        if self.recursion_headroom.get() {
+            if depth > limit + Self::RECURSION_HEADROOM {
+                // overflowed again while handling the RecursionError: the
+                // Rust stack is about to go next, nothing left to do but die
+                // the same way CPython does
+                panic!("Cannot recover from stack overflow.");
+            }
+            Ok(())
+        } else if depth >= limit {
+            // grant some headroom so except/finally blocks handling the
+            // error can themselves make a few calls; revoked by
+            // `with_recursion` once the depth drops back below the limit
+            self.recursion_headroom.set(true);
             Err(self.new_recursion_error(format!("maximum recursion depth exceeded {_where}")))
         } else {
             Ok(())
@@ -515,7 +777,7 @@ impl VirtualMachine {
                     })?;
 
                 let (locals, globals) = if let Some(frame) = self.current_frame() {
-                    (Some(frame.locals.clone()), Some(frame.globals.clone()))
+                    (Some(frame.lazy_locals(self)), Some(frame.globals.clone()))
                 } else {
                     (None, None)
                 };
@@ -681,6 +943,43 @@ impl VirtualMachine {
         }
     }
 
+    /// Schedule `exc` to be raised in the thread with the given id the next
+    /// time that thread's frame loop checks for pending work, like
+    /// `PyThreadState_SetAsyncExc`; `None` cancels a pending exception
+    /// instead. Returns the exception that was already pending for the
+    /// thread, if any.
+    #[cfg(feature = "threading")]
+    pub fn set_async_exc(
+        &self,
+        thread_id: u64,
+        exc: Option<PyBaseExceptionRef>,
+    ) -> Option<PyBaseExceptionRef> {
+        let mut async_excs = self.state.async_excs.lock();
+        let prev = async_excs
+            .iter()
+            .position(|(id, _)| *id == thread_id)
+            .map(|i| async_excs.remove(i).1);
+        if let Some(exc) = exc {
+            async_excs.push((thread_id, exc));
+            // while the lock is still held, so the flag can't outrun the map
+            signal::set_async_exc_pending(&self.state);
+        }
+        prev
+    }
+
+    /// Schedule `f` to run on an interpreter thread at its next safepoint,
+    /// like CPython's `Py_AddPendingCall`. Meant for background Rust threads
+    /// (holding a vm of their own via `new_thread`) that need work done on a
+    /// thread that is busy running Python code; the call runs on whichever
+    /// of the interpreter's threads reaches the eval breaker first, and an
+    /// error it returns is raised there.
+    pub fn add_pending_call(&self, f: signal::PendingCall) {
+        let mut pending_calls = self.state.pending_calls.lock();
+        pending_calls.push(f);
+        // while the lock is still held, so the flag can't outrun the queue
+        signal::set_pending_calls(&self.state);
+    }
+
     pub(crate) fn push_exception(&self, exc: Option<PyBaseExceptionRef>) {
         let mut excs = self.exceptions.borrow_mut();
         let prev = std::mem::take(&mut *excs);
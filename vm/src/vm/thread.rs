@@ -20,6 +20,26 @@ pub fn with_current_vm<R>(f: impl FnOnce(&VirtualMachine) -> R) -> R {
     })
 }
 
+/// Like [`with_current_vm`], but returns `None` instead of panicking when no
+/// vm is entered on this thread (e.g. while a `Context` is being created).
+pub fn try_with_current_vm<R>(f: impl FnOnce(&VirtualMachine) -> R) -> Option<R> {
+    VM_CURRENT.with(|x| unsafe { x.clone().into_inner().as_ref().map(f) })
+}
+
+/// Run `f` once for every vm entered on this thread, innermost first. Meant
+/// for crash reporting, where whatever interpreter state still stands should
+/// be dumped; does nothing when the stack is (or can't be proven) unborrowed.
+pub fn for_each_vm_on_thread(mut f: impl FnMut(&VirtualMachine)) {
+    VM_STACK.with(|vms| {
+        if let Ok(vms) = vms.try_borrow() {
+            for intp in vms.iter().rev() {
+                // SAFETY: all references in VM_STACK are valid while entered
+                f(unsafe { intp.as_ref() });
+            }
+        }
+    });
+}
+
 pub fn enter_vm<R>(vm: &VirtualMachine, f: impl FnOnce() -> R) -> R {
     VM_STACK.with(|vms| {
         vms.borrow_mut().push(vm.into());
@@ -93,8 +113,15 @@ impl ThreadedVirtualMachine {
     where
         F: FnOnce(&VirtualMachine) -> R,
     {
-        let vm = &self.vm;
-        enter_vm(vm, || f(vm))
+        // register this thread with the collector first, so a collection that
+        // stopped the world before the thread existed finishes before any
+        // object is touched here, and detach again once the vm is gone
+        #[cfg(feature = "gc_bacon")]
+        let _attached = crate::object::gc::GLOBAL_COLLECTOR.attach_thread();
+        // destructure so the vm (and every object it holds) drops before the
+        // attach guard does
+        let Self { vm } = self;
+        enter_vm(&vm, || f(&vm))
     }
 }
 
@@ -142,6 +169,7 @@ impl VirtualMachine {
     /// specific guaranteed behavior.
     #[cfg(feature = "threading")]
     pub fn new_thread(&self) -> ThreadedVirtualMachine {
+        use crate::common::rc::PyRc;
         use std::cell::Cell;
         let vm = VirtualMachine {
             builtins: self.builtins.clone(),
@@ -155,12 +183,17 @@ impl VirtualMachine {
             trace_func: RefCell::new(self.ctx.none()),
             use_tracing: Cell::new(false),
             recursion_limit: self.recursion_limit.clone(),
+            // budgets only bound the vm they were installed on
+            instruction_budget: Cell::new(None),
+            suspend_requested: Cell::new(false),
+            stack_pool: PyRc::new(Default::default()),
             signal_handlers: None,
             signal_rx: None,
             repr_guards: RefCell::default(),
             state: self.state.clone(),
             initialized: self.initialized,
             recursion_depth: Cell::new(0),
+            recursion_headroom: Cell::new(false),
         };
         ThreadedVirtualMachine { vm }
     }
@@ -209,6 +209,11 @@ impl VirtualMachine {
         self.new_exception_msg(recursion_error, msg)
     }
 
+    pub fn new_budget_exceeded_error(&self, msg: String) -> PyBaseExceptionRef {
+        let budget_exceeded = self.ctx.exceptions.budget_exceeded.to_owned();
+        self.new_exception_msg(budget_exceeded, msg)
+    }
+
     pub fn new_zero_division_error(&self, msg: String) -> PyBaseExceptionRef {
         let zero_division_error = self.ctx.exceptions.zero_division_error.to_owned();
         self.new_exception_msg(zero_division_error, msg)
@@ -108,9 +108,10 @@ pub fn py_to_js(vm: &VirtualMachine, py_obj: PyObjectRef) -> JsValue {
                     if let Some(ref kwargs) = kwargs {
                         for pair in object_entries(kwargs) {
                             let (key, val) = pair?;
-                            py_func_args
-                                .kwargs
-                                .insert(js_sys::JsString::from(key).into(), js_to_py(vm, val));
+                            py_func_args.kwargs.insert(
+                                String::from(js_sys::JsString::from(key)).into(),
+                                js_to_py(vm, val),
+                            );
                         }
                     }
                     let result = py_obj.call(py_func_args, vm);
@@ -220,7 +221,7 @@ pub fn js_to_py(vm: &VirtualMachine, js_val: JsValue) -> PyObjectRef {
                 move |args: FuncArgs, vm: &VirtualMachine| -> PyResult {
                     let this = Object::new();
                     for (k, v) in args.kwargs {
-                        Reflect::set(&this, &k.into(), &py_to_js(vm, v))
+                        Reflect::set(&this, &k.as_ref().into(), &py_to_js(vm, v))
                             .expect("property to be settable");
                     }
                     let js_args = args